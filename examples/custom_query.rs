@@ -0,0 +1,46 @@
+// a question the command line tool doesn't have a command for, answered with the library's pieces:
+// the most home runs by a shortstop in a season since 1990, written as a markdown table
+//
+//   cargo run --example custom_query
+
+use std::error::Error;
+
+use rust_baseball_stats::data::load_seasons;
+use rust_baseball_stats::options::Options;
+use rust_baseball_stats::positions::position_matches;
+use rust_baseball_stats::ranking::{rank_by, SortOrder};
+use rust_baseball_stats::table::{Format, Output, Table};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let seasons = load_seasons("mlb_season_data.csv")?;
+
+    // rank_by skips anything the value function gives None for, and keeps ties at the cutoff
+    // (shared, with the default options; set Options::ties to break them)
+    let ranked = rank_by(&seasons, 10, SortOrder::Descending, &Options::default(), |s| {
+        if s.season >= 1990 && position_matches("SS", &s.position) { Some(s.homeruns as f64) } else { None }
+    });
+
+    let mut table = Table::new("Most home runs by a shortstop in a season, since 1990")
+        .column("Rank", 4)
+        .column("Player", 24)
+        .column("Team", 5)
+        .right("Season", 6)
+        .right("HR", 4);
+
+    for row in &ranked {
+        let s = row.item;
+        table.row(vec![
+            row.rank.to_string(),
+            format!("{} {}", s.first_name.as_deref().unwrap_or(""), s.last_name),
+            s.team.to_string(),
+            s.season.to_string(),
+            s.homeruns.to_string(),
+        ]);
+    }
+
+    let mut out = Output::stdout(Format::Markdown, false);
+    out.table(&table);
+    out.finish()?;
+
+    Ok(())
+}
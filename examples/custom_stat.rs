@@ -0,0 +1,77 @@
+// a stat the library doesn't know about, registered so the leaderboards can rank it like any other:
+// isolated power (slugging minus batting average, so extra bases only), career leaders among qualified players
+//
+//   cargo run --example custom_stat
+
+use std::error::Error;
+
+use rust_baseball_stats::careers::{aggregate_players, AggregatedPlayer};
+use rust_baseball_stats::data::{load_seasons, CleanPlayerSeason};
+use rust_baseball_stats::leaders::{leader_table, LeaderArgs, Scope};
+use rust_baseball_stats::options::Options;
+use rust_baseball_stats::stats::{register, Stat, StatFormat};
+use rust_baseball_stats::table::{Format, Output};
+
+// (2B + 2 x 3B + 3 x HR) / AB, for a season or from the career totals
+struct IsolatedPower;
+
+fn isolated_power(doubles: u32, triples: u32, homeruns: u32, at_bats: u32) -> Option<f64> {
+    (at_bats > 0).then(|| (doubles + 2 * triples + 3 * homeruns) as f64 / at_bats as f64)
+}
+
+impl Stat for IsolatedPower {
+    fn name(&self) -> &'static str {
+        "iso"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["isolated_power"]
+    }
+
+    fn description(&self) -> &'static str {
+        "isolated power, SLG - AVG (rate)"
+    }
+
+    fn season_value(&self, season: &CleanPlayerSeason) -> Option<f64> {
+        isolated_power(season.doubles, season.triples, season.homeruns, season.at_bats)
+    }
+
+    fn career_value(&self, player: &AggregatedPlayer) -> Option<f64> {
+        isolated_power(player.total_doubles, player.total_triples, player.total_homeruns, player.total_at_bats)
+    }
+
+    fn has_career_value(&self) -> bool {
+        true
+    }
+
+    fn format(&self) -> StatFormat {
+        StatFormat::Rate
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    register(IsolatedPower)?;
+
+    // the default options are the tool's without --ties, --missing or --prorate-short-seasons
+    let options = Options::default();
+    let seasons = load_seasons("mlb_season_data.csv")?;
+    let careers = aggregate_players(&seasons, &options);
+
+    // the same list as `baseball-stats top --stat iso --scope career` would be, qualifying minimum and all
+    let args = LeaderArgs {
+        stat: "isolated_power".to_string(),
+        scope: Scope::Career,
+        min_ab: None,
+        min_attempts: None,
+        ascending: false,
+        position: None,
+        adjusted: false,
+    };
+    let table = leader_table(&args, false, 10, &seasons, &careers, &options)?;
+
+    let mut out = Output::stdout(Format::Text, false);
+    out.table(&table);
+    out.finish()?;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+// load a season file, roll it up into careers, and say what's in it
+//
+//   cargo run --example load_dataset
+//   cargo run --example load_dataset -- other_seasons.csv
+
+use std::error::Error;
+
+use rust_baseball_stats::careers::aggregate_players;
+use rust_baseball_stats::data::load_seasons;
+use rust_baseball_stats::franchises::FranchiseMap;
+use rust_baseball_stats::options::Options;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let file_path = std::env::args().nth(1).unwrap_or_else(|| "mlb_season_data.csv".to_string());
+
+    let mut seasons = load_seasons(&file_path)?;
+
+    // the tool groups old team codes by franchise (MON -> WSH) before anything else; leave this out for raw codes
+    FranchiseMap::builtin().assign(&mut seasons);
+    let careers = aggregate_players(&seasons, &Options::default());
+
+    let first = seasons.iter().map(|s| s.season).min().unwrap_or(0);
+    let last = seasons.iter().map(|s| s.season).max().unwrap_or(0);
+
+    println!("{}: {} season rows, {} players, {}-{}", file_path, seasons.len(), careers.len(), first, last);
+
+    // the longest career, as a taste of what an AggregatedPlayer holds
+    if let Some(player) = careers.iter().max_by_key(|p| p.seasons_played) {
+        println!(
+            "Longest career: {} {}, {} seasons ({}-{}) for {}",
+            player.first_name, player.last_name, player.seasons_played, player.first_season, player.last_season, player.teams
+        );
+    }
+
+    Ok(())
+}
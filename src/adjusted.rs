@@ -0,0 +1,86 @@
+// era-adjusted stats: a player's numbers against the league average of his season, as an index
+// where 100 is average (so 150 is half again as good as the league that year, whatever the era)
+//
+// counting stats are compared per at bat (30 home runs in 1920 and in 2000 aren't the same thing),
+// rates are compared as they are, and a career index is the at-bat weighted average of its seasons
+// with a league column, each league's average is its own (a AAA season against AAA, not against the majors)
+
+use std::collections::HashMap;
+
+use crate::data::{league_season, CleanPlayerSeason, LeagueSeason};
+use crate::stats::{is_rate_stat, season_value};
+
+// how a season's value gets compared: per at bat for counting stats, as it is for rates
+fn comparable_value(stat: &str, season: &CleanPlayerSeason) -> Option<f64> {
+    let value = season_value(stat, season)?;
+    if is_rate_stat(stat) {
+        Some(value)
+    } else if season.at_bats > 0 {
+        Some(value / season.at_bats as f64)
+    } else {
+        None
+    }
+}
+
+// can this stat be adjusted? (games, at bats and times led per at bat don't mean anything, a share of the
+// team is already measured against its own season, and stolen base runs can average out near zero)
+pub fn can_adjust(stat: &str) -> bool {
+    !matches!(stat, "games" | "at_bats" | "homerun_share" | "run_share" | "hit_share" | "times_led" | "baserunning_runs")
+}
+
+// the league's average for one stat, season by season (and league by league, when the data has them)
+pub struct LeagueBaselines {
+    stat: String,
+    by_season: HashMap<LeagueSeason, f64>,
+}
+
+impl LeagueBaselines {
+    // one pass over every row, weighting each by its at bats (for counting stats that's just the
+    // league total over the league's at bats); rows without the stat are left out of that season's average
+    pub fn build(stat: &str, clean_records: &[CleanPlayerSeason]) -> LeagueBaselines {
+        let mut totals: HashMap<LeagueSeason, (f64, f64)> = HashMap::new();
+
+        for season in clean_records {
+            let Some(value) = comparable_value(stat, season) else {
+                continue;
+            };
+            let entry = totals.entry(league_season(season)).or_default();
+            entry.0 += value * season.at_bats as f64;
+            entry.1 += season.at_bats as f64;
+        }
+
+        let by_season = totals
+            .into_iter()
+            .filter(|(_, (weighted, at_bats))| *at_bats > 0.0 && *weighted > 0.0)
+            .map(|(key, (weighted, at_bats))| (key, weighted / at_bats))
+            .collect();
+
+        LeagueBaselines { stat: stat.to_string(), by_season }
+    }
+
+    // a season's index (None when the stat or the league average is missing)
+    pub fn season_index(&self, season: &CleanPlayerSeason) -> Option<f64> {
+        let baseline = self.by_season.get(&league_season(season))?;
+        Some(comparable_value(&self.stat, season)? / baseline * 100.0)
+    }
+
+    // every player's career index, by link: the average of his season indexes, weighted by at bats
+    pub fn career_indexes(&self, clean_records: &[CleanPlayerSeason]) -> HashMap<String, f64> {
+        let mut totals: HashMap<&str, (f64, f64)> = HashMap::new();
+
+        for season in clean_records {
+            let Some(index) = self.season_index(season) else {
+                continue;
+            };
+            let entry = totals.entry(season.link.as_ref()).or_default();
+            entry.0 += index * season.at_bats as f64;
+            entry.1 += season.at_bats as f64;
+        }
+
+        totals
+            .into_iter()
+            .filter(|(_, (_, at_bats))| *at_bats > 0.0)
+            .map(|(link, (weighted, at_bats))| (link.to_string(), weighted / at_bats))
+            .collect()
+    }
+}
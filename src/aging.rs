@@ -0,0 +1,194 @@
+// the aging curve: every qualified season in the data bucketed by how old the player was, with the
+// average and median of a stat at each age, to see where hitters peak and how fast they fall off
+// the data has no birthdates, so they come from a file (--birthdates) joined on the player's link
+//
+// the birthdates file is a csv with a link column and a birth_date column (1934-02-05, or just the
+// year when that's all there is); a season's age is the player's age on June 30th of it, the usual
+// baseball convention, so a player born in August is a year younger for the season than one born in May
+
+use std::collections::{BTreeMap, HashMap};
+
+use csv::ReaderBuilder;
+
+use crate::chart::BarChart;
+use crate::data::{current_year, CleanPlayerSeason};
+use crate::error::{check_exists, Error, Result};
+use crate::leaders::SEASON_QUALIFYING_AT_BATS;
+use crate::options::Options;
+use crate::schedule::prorated_note;
+use crate::stats::{format_stat_value, has_season_value, higher_is_better, is_rate_stat, normalize_stat_name, season_value, unavailable_reason, unknown_stat};
+use crate::table::{Output, Table};
+
+const BAR_WIDTH: usize = 40;      // characters in the longest bar with --chart
+
+// when someone was born; month and day are None when the file only has the year
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Birthdate {
+    pub year: u32,
+    pub month_day: Option<(u32, u32)>,
+}
+
+impl Birthdate {
+    // "1934-02-05" or "1934"
+    pub fn parse(text: &str) -> Option<Birthdate> {
+        let parts: Vec<&str> = text.trim().split('-').collect();
+        match parts.as_slice() {
+            [year] => Some(Birthdate { year: year.parse().ok()?, month_day: None }),
+            [year, month, day] => {
+                let (month, day): (u32, u32) = (month.parse().ok()?, day.parse().ok()?);
+                if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+                    return None;
+                }
+                Some(Birthdate { year: year.parse().ok()?, month_day: Some((month, day)) })
+            }
+            _ => None,
+        }
+    }
+
+    // his age on June 30th of the season (with only the year, the season less the year)
+    pub fn season_age(&self, season: u32) -> Option<u32> {
+        let age = season.checked_sub(self.year)?;
+        match self.month_day {
+            Some(month_day) if month_day > (6, 30) => age.checked_sub(1),
+            _ => Some(age),
+        }
+    }
+}
+
+// read a link,birth_date csv: link to birthdate, leaving out rows with the date blank
+pub fn load_birthdates(file_path: &str) -> Result<HashMap<String, Birthdate>> {
+    check_exists("birthdates file", file_path)?;
+    let mut reader = ReaderBuilder::new().has_headers(true).trim(csv::Trim::All).from_path(file_path)?;
+    let headers: Vec<String> = reader.headers()?.iter().map(|h| h.to_lowercase()).collect();
+
+    let position = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+    let link_column = position(&["link"]).ok_or_else(|| Error::Data(format!("{} has no link column", file_path)))?;
+    let date_column = position(&["birth_date", "birthdate", "born"]).ok_or_else(|| Error::Data(format!("{} has no birth_date column", file_path)))?;
+
+    let this_year = current_year();
+    let mut birthdates = HashMap::new();
+    for (line, result) in reader.records().enumerate() {
+        let row = result?;
+        let text = row.get(date_column).unwrap_or("");
+        if text.is_empty() || text == "--" {
+            continue;
+        }
+        let birthdate = Birthdate::parse(text)
+            .filter(|b| b.year > 1800 && b.year <= this_year)
+            .ok_or_else(|| Error::Data(format!("{} line {}: \"{}\" isn't a birth date (1934-02-05, or just 1934)", file_path, line + 2, text)))?;
+        birthdates.insert(row.get(link_column).unwrap_or("").to_string(), birthdate);
+    }
+    Ok(birthdates)
+}
+
+// the middle value, or the average of the middle two (values sorted already)
+fn median(sorted: &[f64]) -> f64 {
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) { (sorted[middle - 1] + sorted[middle]) / 2.0 } else { sorted[middle] }
+}
+
+// an average in the stat's own format, with a decimal for counting stats (17.4 home runs, not 17)
+fn format_average(stat: &str, value: f64) -> String {
+    if is_rate_stat(stat) { format_stat_value(stat, value) } else { format!("{:.1}", value) }
+}
+
+// the aging-curve command (its own options, then the data and the options it was loaded with)
+#[allow(clippy::too_many_arguments)]
+pub fn show_aging_curve(
+    out: &mut Output,
+    stat: &str,
+    birthdates: &HashMap<String, Birthdate>,
+    min_ab: Option<u32>,
+    min_seasons: usize,
+    chart: bool,
+    clean_records: &[CleanPlayerSeason],
+    options: &Options,
+) -> Result<()> {
+    let stat = normalize_stat_name(stat).ok_or_else(|| unknown_stat(stat))?;
+    if !has_season_value(stat) {
+        return Err(Error::Usage(format!("{} is only worked out for careers", stat)));
+    }
+    if let Some(reason) = unavailable_reason(stat, clean_records) {
+        return Err(Error::Data(format!("{} can't be shown: {}", stat, reason)));
+    }
+
+    // the usual season qualifier for every stat, counting ones too, so a September call-up's 2 home runs
+    // don't pull the average at 21 down
+    let min_at_bats = min_ab.unwrap_or(SEASON_QUALIFYING_AT_BATS);
+    let season_min = |year: u32| if min_ab.is_some() { min_at_bats } else { options.prorated(min_at_bats, year) };
+
+    let mut by_age: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+    let mut undated = 0;
+    for season in clean_records.iter().filter(|s| s.at_bats >= season_min(s.season)) {
+        let Some(value) = season_value(stat, season) else {
+            continue;
+        };
+        match birthdates.get(season.link.as_ref()).and_then(|b| b.season_age(season.season)) {
+            Some(age) => by_age.entry(age).or_default().push(value),
+            None => undated += 1,
+        }
+    }
+
+    // an age only a handful of players reached says more about them than about aging
+    let thin_ages = by_age.values().filter(|values| values.len() < min_seasons).count();
+    by_age.retain(|_, values| values.len() >= min_seasons);
+    if by_age.is_empty() {
+        out.line(&format!("No ages with {} or more qualified seasons that have a birth date", min_seasons));
+        return Ok(());
+    }
+
+    let curve: Vec<(u32, usize, f64, f64)> = by_age
+        .iter_mut()
+        .map(|(&age, values)| {
+            values.sort_by(|a, b| a.total_cmp(b));
+            let average = values.iter().sum::<f64>() / values.len() as f64;
+            (age, values.len(), average, median(values))
+        })
+        .collect();
+    let seasons: usize = curve.iter().map(|&(_, count, _, _)| count).sum();
+
+    let prorated_qualifier = if min_ab.is_none() { prorated_note(options.prorate) } else { "" };
+    let mut table = Table::new(format!("{} by age ({} player seasons, min {} AB{})", stat, seasons, min_at_bats, prorated_qualifier))
+        .right("Age", 4)
+        .right("Seasons", 7)
+        .right("Average", 8)
+        .right("Median", 8);
+    if chart {
+        table = table.column("", BAR_WIDTH);
+    }
+
+    // bars run from the lowest average to the highest, so a .020 swing in OPS is something to see
+    // (the lowest still gets a mark)
+    let lowest = curve.iter().map(|&(_, _, average, _)| average).fold(f64::INFINITY, f64::min);
+    let highest = curve.iter().map(|&(_, _, average, _)| average).fold(f64::NEG_INFINITY, f64::max);
+    let mut bar_chart = BarChart::new(format!("Average {} by age", stat));
+    bar_chart.decimals = if is_rate_stat(stat) { 3 } else { 1 };
+
+    for &(age, count, average, middle) in &curve {
+        let mut row = vec![age.to_string(), count.to_string(), format_average(stat, average), format_average(stat, middle)];
+        if chart {
+            let share = if highest > lowest { (average - lowest) / (highest - lowest) } else { 1.0 };
+            row.push("#".repeat(((share * (BAR_WIDTH - 1) as f64).round() as usize) + 1));
+        }
+        table.row(row);
+        bar_chart.bar(age.to_string(), average);
+    }
+    out.table(&table);
+    out.chart(&bar_chart);
+
+    // the peak, by the average (lowest for stats where less is better)
+    let better = higher_is_better(stat);
+    let peak = curve.iter().reduce(|best, row| if (row.2 > best.2) == better && row.2 != best.2 { row } else { best });
+    if let Some(&(age, count, average, _)) = peak {
+        out.line("");
+        out.line(&format!("Peak: age {} ({} average over {} seasons)", age, format_average(stat, average), count));
+    }
+    if thin_ages > 0 {
+        let ages = if thin_ages == 1 { "age" } else { "ages" };
+        out.line(&format!("{} {} with fewer than {} seasons left out", thin_ages, ages, min_seasons));
+    }
+    if undated > 0 {
+        out.line(&format!("{} qualified seasons have no birth date in the file and were left out", undated));
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+// the active career leaders as they stood at the end of a season: only players who played that
+// season, and only what they'd done up to and including it ("who led active players in career hits
+// after 1974")
+
+use crate::careers::{group_by_player, AggregatedPlayer, CareerBuilder};
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::leaders::CAREER_QUALIFYING_AT_BATS;
+use crate::options::Options;
+use crate::ranking::{rank_by, SortOrder};
+use crate::stats::{career_value, format_stat_value, has_career_value, higher_is_better, is_rate_stat, normalize_stat_name, unavailable_reason, unknown_stat};
+use crate::table::{Output, Table};
+
+// each active player's career through the season: his seasons in order, cut off after that one
+// (a season split between teams is all there, since every row of it has the same season)
+fn careers_through(season: u32, clean_records: &[CleanPlayerSeason], options: &Options) -> Vec<AggregatedPlayer> {
+    let builder = CareerBuilder::new(clean_records, options);
+    let mut careers = Vec::new();
+
+    for (link, mut seasons) in group_by_player(clean_records) {
+        if !seasons.iter().any(|s| s.season == season) {
+            continue;
+        }
+        seasons.sort_by_key(|s| s.season);
+        let through = seasons.partition_point(|s| s.season <= season);
+        careers.push(builder.career(link, &seasons[..through]));
+    }
+
+    careers
+}
+
+// the leaders-asof command
+pub fn show_leaders_as_of(out: &mut Output, stat: &str, season: u32, top: usize, min_ab: Option<u32>, clean_records: &[CleanPlayerSeason], options: &Options) -> Result<()> {
+    let stat = normalize_stat_name(stat).ok_or_else(|| unknown_stat(stat))?;
+    if let Some(reason) = unavailable_reason(stat, clean_records) {
+        return Err(Error::Data(format!("{} can't be shown: {}", stat, reason)));
+    }
+    if !has_career_value(stat) {
+        return Err(Error::Usage(format!("{} isn't available for careers yet", stat)));
+    }
+
+    let careers = careers_through(season, clean_records, options);
+    if careers.is_empty() {
+        return Err(Error::NotFound { what: "season", path: season.to_string() });
+    }
+
+    // a career rate needs a career behind it, same as top --scope career
+    let min_at_bats = min_ab.unwrap_or(if is_rate_stat(stat) { CAREER_QUALIFYING_AT_BATS } else { 0 });
+    let order = SortOrder::for_stat(higher_is_better(stat), false);
+
+    let ranked = rank_by(&careers, top, order, options, |p| {
+        if p.total_at_bats < min_at_bats { None } else { career_value(stat, p) }
+    });
+
+    let qualifier = if min_at_bats > 0 { format!(" (min {} AB)", min_at_bats) } else { String::new() };
+    let mut table = Table::new(format!("Top {} {} among players active in {}, careers through {}{}", top, stat, season, season, qualifier))
+        .column("Rank", 4)
+        .column("Player", 25)
+        .column("From", 6)
+        .right("Seasons", 7)
+        .right("AB", 6)
+        .right(stat, stat.len().max(8));
+
+    for row in &ranked {
+        let player = row.item;
+        table.row(vec![
+            row.rank.to_string(),
+            format!("{} {}", player.first_name, player.last_name),
+            player.first_season.to_string(),
+            player.seasons_played.to_string(),
+            player.total_at_bats.to_string(),
+            format_stat_value(stat, row.value),
+        ]);
+    }
+
+    out.table(&table);
+    Ok(())
+}
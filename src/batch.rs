@@ -0,0 +1,148 @@
+// the batch command: a toml file of reports, run one after another on a single load of the data instead
+// of starting the tool (and reading and adding up every season) once for each
+//
+//   top = 20                      # settings every report gets unless it says otherwise
+//   format = "csv"
+//
+//   [[report]]
+//   title = "career home runs"    # for the log (the report's command line otherwise)
+//   command = "top"
+//   stat = "homeruns"
+//   scope = "career"
+//   out = "reports/career_hr.csv"
+//
+// a report's keys are its command's options (min_ab or min-ab for --min-ab, true for a flag), its
+// arguments by name (name = "ken griffey" for player), and top, format, out, out_dir, jobs and wrap; anything
+// else that shapes the data (--join, --missing, ...) goes on the batch command line, since every report
+// shares the one load
+//
+// the reports come back as command lines, checked against the tool's own definition, for it to parse
+
+use std::fs;
+
+use clap::{Arg, ArgAction, Command};
+use toml::{Table, Value};
+
+use crate::error::{check_exists, Error, Result};
+
+// the options that can change from report to report; the rest of the global ones are the batch's
+const REPORT_OPTIONS: &[&str] = &["top", "format", "out", "out_dir", "jobs", "wrap"];
+
+// the commands that make no sense as a report: another batch, or a quiz waiting on the keyboard
+const NOT_IN_A_BATCH: &[&str] = &["batch", "quiz"];
+
+// one report, as the command line that would run it on its own (starting with the program name)
+pub struct BatchReport {
+    pub title: String,
+    pub args: Vec<String>,
+}
+
+// a toml value as the text it'd be typed as; arrays are one value per element
+fn values(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(text) => vec![text.clone()],
+        Value::Array(items) => items.iter().flat_map(values).collect(),
+        other => vec![other.to_string()],
+    }
+}
+
+// the key's option: one of the command's own, or a global one a report can set
+fn find_arg<'a>(key: &str, command: &'a Command, cli: &'a Command) -> std::result::Result<&'a Arg, String> {
+    let id = key.replace('-', "_");
+    if let Some(arg) = command.get_arguments().find(|arg| arg.get_id() == id.as_str()) {
+        return Ok(arg);
+    }
+    match cli.get_arguments().find(|arg| arg.get_id() == id.as_str() && arg.is_global_set()) {
+        Some(arg) if REPORT_OPTIONS.contains(&id.as_str()) => Ok(arg),
+        Some(arg) => Err(format!(
+            "{} is the same for every report, so it goes on the batch command line (--{})",
+            key,
+            arg.get_long().unwrap_or(key)
+        )),
+        None => Err(format!("{} has no {} option", command.get_name(), key)),
+    }
+}
+
+// a report's table as a command line
+fn report_args(report: &Table, defaults: &Table, cli: &Command) -> std::result::Result<Vec<String>, String> {
+    let command_name = match report.get("command") {
+        Some(Value::String(name)) => name.as_str(),
+        Some(_) => return Err("command has to be a name, like command = \"top\"".to_string()),
+        None => return Err("there's no command (command = \"top\", say)".to_string()),
+    };
+    if NOT_IN_A_BATCH.contains(&command_name) {
+        return Err(format!("{} can't be run in a batch", command_name));
+    }
+    let command = cli.find_subcommand(command_name).ok_or_else(|| format!("there's no {} command", command_name))?;
+
+    let mut options = Vec::new();
+    let mut positionals: Vec<(usize, Vec<String>)> = Vec::new();
+    // the report's own keys, then the defaults it doesn't override
+    let keys = report.iter().chain(defaults.iter().filter(|(key, _)| !report.contains_key(*key)));
+    for (key, value) in keys.filter(|(key, _)| !matches!(key.as_str(), "command" | "title")) {
+        let arg = find_arg(key, command, cli)?;
+        if arg.is_positional() {
+            positionals.push((arg.get_index().unwrap_or(0), values(value)));
+            continue;
+        }
+
+        let long = format!("--{}", arg.get_long().unwrap_or(key));
+        match (arg.get_action(), value) {
+            (ArgAction::SetTrue, Value::Boolean(set)) => {
+                if *set {
+                    options.push(long);
+                }
+            }
+            (ArgAction::SetTrue, _) => return Err(format!("{} is true or false", key)),
+            _ => {
+                for value in values(value) {
+                    options.push(long.clone());
+                    options.push(value);
+                }
+            }
+        }
+    }
+
+    positionals.sort_by_key(|(index, _)| *index);
+    let mut args = vec!["baseball-stats".to_string(), command_name.to_string()];
+    args.extend(positionals.into_iter().flat_map(|(_, values)| values));
+    args.extend(options);
+    Ok(args)
+}
+
+// read the file into its reports, each as a command line; a report that can't be turned into one is
+// an error naming it, before anything runs
+pub fn load_batch(file_path: &str, cli: &Command) -> Result<Vec<BatchReport>> {
+    check_exists("batch file", file_path)?;
+    let mut file: Table = toml::from_str(&fs::read_to_string(file_path)?).map_err(|e| Error::Data(format!("{} isn't toml: {}", file_path, e)))?;
+
+    let reports = match file.remove("report") {
+        Some(Value::Array(reports)) => reports,
+        Some(_) => return Err(Error::Data(format!("{}: reports go in [[report]] tables", file_path))),
+        None => return Err(Error::Data(format!("{} has no [[report]] tables", file_path))),
+    };
+
+    // what's left at the top is settings for every report (a single out file would have them all
+    // writing over each other, so that one's for each report to say)
+    let defaults = file;
+    if let Some(key) = defaults.keys().find(|key| key.as_str() == "out" || !REPORT_OPTIONS.contains(&key.replace('-', "_").as_str())) {
+        return Err(Error::Usage(format!(
+            "{}: {} can't be set for every report (only top, format, out_dir, jobs and wrap can)",
+            file_path, key
+        )));
+    }
+
+    let mut batch = Vec::new();
+    for (number, report) in reports.iter().enumerate() {
+        let Value::Table(report) = report else {
+            return Err(Error::Data(format!("{}: report {} isn't a table", file_path, number + 1)));
+        };
+        let args = report_args(report, &defaults, cli).map_err(|e| Error::Usage(format!("{} report {}: {}", file_path, number + 1, e)))?;
+        let title = match report.get("title") {
+            Some(Value::String(title)) => title.clone(),
+            _ => args[1..].join(" "),
+        };
+        batch.push(BatchReport { title, args });
+    }
+    Ok(batch)
+}
@@ -0,0 +1,155 @@
+// every career written out as data for other programs (export-careers), one row per player, in a
+// fixed set of columns that only changes with a new schema version, so a pipeline reading the file
+// can check the version and count on the columns being what it expects
+//
+// version 1: everything through strikeout_rate
+// version 2: adds on_base_percentage and ops
+// (joined --join columns aren't part of the schema, since they change from run to run)
+
+use std::fs::File;
+use std::io::Write;
+
+use serde_json::{json, Map, Value};
+
+use crate::careers::AggregatedPlayer;
+use crate::error::{Error, Result};
+use crate::stats::career_value;
+use crate::table::Format;
+
+pub const CAREER_SCHEMA_VERSION: u32 = 2;
+
+// the columns, in order, with what's in them (this is the documentation, and goes in export-careers --help)
+pub const CAREER_COLUMNS: &[(&str, &str)] = &[
+    ("schema_version", "the version of these columns, 2 for now"),
+    ("link", "the player's link, unique per player"),
+    ("first_name", "first name"),
+    ("last_name", "last name"),
+    ("first_season", "first season in the data"),
+    ("last_season", "last season in the data"),
+    ("seasons_played", "season rows (a season split between teams counts once per team)"),
+    ("positions", "every position he played, comma separated, in the order they first came up"),
+    ("primary_position", "the position he played the most games at"),
+    ("teams", "every franchise he played for, comma separated, in order"),
+    ("team_count", "how many franchises"),
+    ("games", "career games"),
+    ("at_bats", "career at bats"),
+    ("runs", "career runs"),
+    ("hits", "career hits"),
+    ("doubles", "career doubles"),
+    ("triples", "career triples"),
+    ("homeruns", "career home runs"),
+    ("rbi", "career runs batted in (seasons without them count 0, or an estimate with --missing estimate)"),
+    ("walks", "career walks"),
+    ("strikeouts", "career strikeouts (seasons without them count 0, or an estimate with --missing estimate)"),
+    ("stolen_bases", "career stolen bases (seasons without them count 0, or an estimate with --missing estimate)"),
+    ("caught_stealing", "career caught stealing (seasons without them count 0, or an estimate with --missing estimate)"),
+    ("batting_average", "hits over at bats, to 4 places"),
+    ("on_base_percentage", "each season's times on base added up, over at bats plus walks, to 4 places"),
+    ("slugging_percentage", "total bases over at bats, to 4 places"),
+    ("ops", "on_base_percentage plus slugging_percentage, to 4 places"),
+    ("speed", "Bill James speed score, to 4 places"),
+    ("tto", "share of plate appearances ending in a home run, walk or strikeout, to 4 places"),
+    ("strikeout_rate", "share of plate appearances ending in a strikeout, to 4 places"),
+];
+
+// the list of columns that goes at the bottom of export-careers --help
+pub fn career_schema_help() -> String {
+    let mut help = format!("Columns (schema version {}; missing values are empty in csv and null in json):\n", CAREER_SCHEMA_VERSION);
+    for (name, description) in CAREER_COLUMNS {
+        help.push_str(&format!("  {:<20} {}\n", name, description));
+    }
+    help
+}
+
+fn rounded(value: Option<f64>) -> Value {
+    match value {
+        Some(v) if v.is_finite() => json!((v * 10000.0).round() / 10000.0),
+        _ => Value::Null,
+    }
+}
+
+// one player's values, in CAREER_COLUMNS order
+fn career_values(player: &AggregatedPlayer) -> Vec<Value> {
+    vec![
+        json!(CAREER_SCHEMA_VERSION),
+        json!(player.link),
+        json!(player.first_name),
+        json!(player.last_name),
+        json!(player.first_season),
+        json!(player.last_season),
+        json!(player.seasons_played),
+        json!(player.positions),
+        json!(player.primary_position),
+        json!(player.teams),
+        json!(player.team_count),
+        json!(player.total_games_played),
+        json!(player.total_at_bats),
+        json!(player.total_runs),
+        json!(player.total_hits),
+        json!(player.total_doubles),
+        json!(player.total_triples),
+        json!(player.total_homeruns),
+        json!(player.total_rbi),
+        json!(player.total_walks),
+        json!(player.total_strikeouts),
+        json!(player.total_stolen_bases),
+        json!(player.total_caught_stealing),
+        rounded(player.batting_average),
+        rounded(player.on_base_percentage),
+        rounded(player.slugging_percentage),
+        rounded(player.ops),
+        rounded(career_value("speed", player)),
+        rounded(career_value("tto", player)),
+        rounded(career_value("strikeout_rate", player)),
+    ]
+}
+
+// a value as a csv cell: strings without their quotes, null as nothing
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+// write every career to a csv or json file (which one goes by the extension), sorted by link so the
+// same data always gives the same file; returns how many were written
+pub fn export_careers(path: &str, aggregated_players: &[AggregatedPlayer]) -> Result<usize> {
+    let mut players: Vec<&AggregatedPlayer> = aggregated_players.iter().collect();
+    players.sort_by(|a, b| a.link.cmp(&b.link));
+
+    match Format::from_path(path) {
+        Some(Format::Csv) => {
+            let mut writer = csv::Writer::from_path(path)?;
+            writer.write_record(CAREER_COLUMNS.iter().map(|(name, _)| *name))?;
+            for player in &players {
+                writer.write_record(career_values(player).iter().map(csv_cell))?;
+            }
+            writer.flush()?;
+        }
+        Some(Format::Json) => {
+            let rows: Vec<Value> = players
+                .iter()
+                .map(|player| {
+                    let fields: Map<String, Value> = CAREER_COLUMNS.iter().map(|(name, _)| name.to_string()).zip(career_values(player)).collect();
+                    Value::Object(fields)
+                })
+                .collect();
+            let columns: Vec<Value> = CAREER_COLUMNS.iter().map(|(name, description)| json!({ "name": name, "description": description })).collect();
+            let document = json!({
+                "schema": "careers",
+                "schema_version": CAREER_SCHEMA_VERSION,
+                "columns": columns,
+                "careers": rows,
+            });
+
+            let mut file = File::create(path)?;
+            serde_json::to_writer_pretty(&mut file, &document)?;
+            writeln!(file)?;
+        }
+        _ => return Err(Error::Usage(format!("{} has to end in .csv or .json", path))),
+    }
+
+    Ok(players.len())
+}
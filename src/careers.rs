@@ -0,0 +1,314 @@
+// rolling individual seasons up into one record per player
+
+// tool for looking up data
+use std::collections::BTreeMap;
+
+use log::info;
+
+use crate::data::CleanPlayerSeason;
+use crate::leaders::SEASON_QUALIFYING_AT_BATS;
+use crate::missing::{add_estimates, missing_counts, skipped_stats, LeagueRates, MissingPolicy};
+use crate::options::Options;
+use crate::positions::primary_position;
+use crate::progress;
+
+#[derive(Debug, Clone)]
+pub struct AggregatedPlayer {
+    pub first_name: String,
+    pub last_name: String,
+    pub first_season: u32,      // lowest season number
+    pub last_season: u32,       // highest season number
+    pub link: String,
+    pub seasons_played: u32,    // count of seasons
+    pub positions: String, // all unique positions
+    pub primary_position: String, // the one with the most games (see positions.rs)
+    pub teams: String,     // all unique teams (by franchise, so an Expo who became a National lists WSH once)
+    pub team_count: u32,
+    pub leagues: String,   // all unique leagues, when the data has a league column (empty otherwise)
+    pub total_games_played: u32,
+    pub total_at_bats: u32,
+    pub total_runs: u32,
+    pub total_hits: u32,
+    pub total_doubles: u32,
+    pub total_triples: u32,
+    pub total_homeruns: u32,
+    pub total_rbi: u32,
+    pub total_walks: u32,
+    pub total_strikeouts: u32,
+    pub total_stolen_bases: u32,
+    pub total_caught_stealing: u32,
+    // the career rates, from the summed counts rather than an average of the season rates (None without the counts)
+    pub batting_average: Option<f64>,
+    pub on_base_percentage: Option<f64>,
+    pub slugging_percentage: Option<f64>,
+    pub ops: Option<f64>,
+    pub qualified_ops: Vec<f64>,  // OPS in each season he qualified for the batting title, in order (for consistency)
+    pub missing: Vec<&'static str>,  // optional counts (rbi, strikeouts, ...) some season doesn't have (see missing.rs)
+    pub skipped: Vec<&'static str>,  // --missing skip: the career stats he's left out of, built on a count he's missing
+    pub times_led: BTreeMap<&'static str, u32>,  // how many seasons he led the majors in each stat (see leader_counts.rs)
+    pub extras: BTreeMap<String, f64>,  // joined columns: the seasons' values added up, or a career value joined on link
+}
+
+// group players by their unique link, in link order so anything built from the groups comes out the same every run
+// (the groups point back into clean_records rather than holding copies, so there's only ever one copy of each season)
+pub fn group_by_player(clean_records: &[CleanPlayerSeason]) -> BTreeMap<&str, Vec<&CleanPlayerSeason>> {
+    // create a new data set, using the links from the cleanplayerseason dataset as the identifiers, but for now it's blank
+    let mut player_groups: BTreeMap<&str, Vec<&CleanPlayerSeason>> = BTreeMap::new();
+
+    // for every row in the clean_players dataset
+    for player in clean_records {
+        // either add it to an existing record in the player_groups dataset (where it matches the link column) or create a new record
+        player_groups.entry(player.link.as_ref()).or_default().push(player);
+    }
+
+    player_groups
+}
+
+// career on base percentage: the data has no hit by pitch or sacrifice flies to add up, so each season's
+// times on base are worked back out of its on base percentage over at bats plus walks, then summed
+// (None if any season with a plate appearance is missing it, since a partial career would overstate it)
+fn career_on_base(seasons: &[&CleanPlayerSeason]) -> Option<f64> {
+    let mut times_on_base = 0.0;
+    let mut plate_appearances = 0;
+    for season in seasons {
+        let season_plate_appearances = season.at_bats + season.walks;
+        if season_plate_appearances == 0 {
+            continue;
+        }
+        times_on_base += season.on_base_percentage? * season_plate_appearances as f64;
+        plate_appearances += season_plate_appearances;
+    }
+    (plate_appearances > 0).then(|| times_on_base / plate_appearances as f64)
+}
+
+// his OPS in each season with enough at bats to qualify for the batting title (502, pro-rated for a short
+// season with --prorate-short-seasons), a season split between teams added up first so a traded regular
+// counts; seasons without an on base percentage are left out
+fn qualified_ops(seasons: &[&CleanPlayerSeason], options: &Options) -> Vec<f64> {
+    let mut by_season: BTreeMap<u32, Vec<&CleanPlayerSeason>> = BTreeMap::new();
+    for season in seasons {
+        by_season.entry(season.season).or_default().push(season);
+    }
+
+    by_season
+        .into_iter()
+        .filter_map(|(year, rows)| {
+            let at_bats: u32 = rows.iter().map(|s| s.at_bats).sum();
+            if at_bats == 0 || at_bats < options.prorated(SEASON_QUALIFYING_AT_BATS, year) {
+                return None;
+            }
+            let total_bases: u32 = rows.iter().map(|s| s.hits + s.doubles + 2 * s.triples + 3 * s.homeruns).sum();
+            Some(career_on_base(&rows)? + total_bases as f64 / at_bats as f64)
+        })
+        .collect()
+}
+
+// add up one player's seasons into a career record (see CareerBuilder, which every career goes through)
+fn aggregate_player(link: &str, seasons: &[&CleanPlayerSeason], options: &Options) -> AggregatedPlayer {
+    // get basic info from first season
+    let first_season_record = &seasons[0];
+
+    // find min and max seasons
+    let first_season = seasons.iter().map(|s| s.season).min().unwrap();
+    let last_season = seasons.iter().map(|s| s.season).max().unwrap();
+
+    // collect unique positions, teams and leagues
+    let mut unique_positions = Vec::new();
+    let mut unique_teams = Vec::new();
+    let mut unique_leagues = Vec::new();
+
+    for season in seasons {
+        if !unique_positions.contains(&season.position) {
+            unique_positions.push(season.position.clone());
+        }
+        if !unique_teams.contains(&season.franchise) {
+            unique_teams.push(season.franchise.clone());
+        }
+        if let Some(league) = &season.league
+            && !unique_leagues.contains(league)
+        {
+            unique_leagues.push(league.clone());
+        }
+    }
+
+    // calculate career totals - iterate through each line and add them up
+    let total_games_played: u32 = seasons.iter().map(|s| s.games_played).sum();
+    let total_at_bats: u32 = seasons.iter().map(|s| s.at_bats).sum();
+    let total_runs: u32 = seasons.iter().map(|s| s.runs).sum();
+    let total_hits: u32 = seasons.iter().map(|s| s.hits).sum();
+    let total_doubles: u32 = seasons.iter().map(|s| s.doubles).sum();
+    let total_triples: u32 = seasons.iter().map(|s| s.triples).sum();
+    let total_homeruns: u32 = seasons.iter().map(|s| s.homeruns).sum();
+    let total_walks: u32 = seasons.iter().map(|s| s.walks).sum();
+
+    // handle the optional fields (treat None as 0; --missing can estimate them instead, see CareerBuilder)
+    let total_rbi: u32 = seasons.iter().map(|s| s.rbi.unwrap_or(0)).sum();
+    let total_strikeouts: u32 = seasons.iter().map(|s| s.strikeouts.unwrap_or(0)).sum();
+    let total_stolen_bases: u32 = seasons.iter().map(|s| s.stolen_bases.unwrap_or(0)).sum();
+    let total_caught_stealing: u32 = seasons.iter().map(|s| s.caught_stealing.unwrap_or(0)).sum();
+
+    // the rates from those totals
+    let per_at_bat = |count: u32| (total_at_bats > 0).then(|| count as f64 / total_at_bats as f64);
+    let batting_average = per_at_bat(total_hits);
+    let slugging_percentage = per_at_bat(total_hits + total_doubles + 2 * total_triples + 3 * total_homeruns);
+    let on_base_percentage = career_on_base(seasons);
+    let ops = on_base_percentage.zip(slugging_percentage).map(|(obp, slg)| obp + slg);
+
+    let mut times_led: BTreeMap<&'static str, u32> = BTreeMap::new();
+    for season in seasons {
+        for stat in &season.led {
+            *times_led.entry(stat).or_default() += 1;
+        }
+    }
+
+    // joined columns add up like the counting stats
+    let mut extras: BTreeMap<String, f64> = BTreeMap::new();
+    for season in seasons {
+        for (name, value) in &season.extras {
+            *extras.entry(name.clone()).or_default() += value;
+        }
+    }
+
+    // populate the aggregated player record
+    AggregatedPlayer {
+        link: link.to_string(),
+        first_name: first_season_record.first_name.as_deref().unwrap_or("N/A").to_string(),
+        last_name: first_season_record.last_name.clone(),
+        first_season,
+        last_season,
+        seasons_played: seasons.len() as u32,
+        positions: unique_positions.join(", "),
+        primary_position: primary_position(seasons),
+        teams: unique_teams.join(", "),
+        team_count: unique_teams.len() as u32,
+        leagues: unique_leagues.join(", "),
+        total_games_played,
+        total_at_bats,
+        total_runs,
+        total_hits,
+        total_doubles,
+        total_triples,
+        total_homeruns,
+        total_rbi,
+        total_walks,
+        total_strikeouts,
+        total_stolen_bases,
+        total_caught_stealing,
+        batting_average,
+        on_base_percentage,
+        slugging_percentage,
+        ops,
+        qualified_ops: qualified_ops(seasons, options),
+        missing: missing_counts(seasons),
+        skipped: Vec::new(),
+        times_led,
+        extras,
+    }
+}
+
+// adds seasons up into careers the way the options say, with --missing applied to the counts a season
+// doesn't have: the league's estimates added in (worked out once, from all the data), or the stats built
+// on them marked as ones the career is left out of; every career is
+// built through one of these, so the career lists, careers as of a season, player pages and team lines
+// all agree
+pub struct CareerBuilder {
+    options: Options,
+    rates: Option<LeagueRates>,
+}
+
+impl CareerBuilder {
+    pub fn new(clean_records: &[CleanPlayerSeason], options: &Options) -> CareerBuilder {
+        let rates = (options.missing == MissingPolicy::Estimate).then(|| LeagueRates::build(clean_records));
+        CareerBuilder { options: options.clone(), rates }
+    }
+
+    // one career (or anything else added up the same way, like a team's season)
+    pub fn career(&self, link: &str, seasons: &[&CleanPlayerSeason]) -> AggregatedPlayer {
+        let mut player = aggregate_player(link, seasons, &self.options);
+        match (self.options.missing, &self.rates) {
+            (MissingPolicy::Estimate, Some(rates)) => add_estimates(&mut player, seasons, rates),
+            (MissingPolicy::Skip, _) => player.skipped = skipped_stats(&player.missing),
+            _ => {}
+        }
+        player
+    }
+}
+
+// build a career record for every player in the data, sorted by link
+pub fn aggregate_players(clean_records: &[CleanPlayerSeason], options: &Options) -> Vec<AggregatedPlayer> {
+    info!("Identifying individual players...");
+    let player_groups = group_by_player(clean_records);
+    info!("Found {} unique players", player_groups.len());
+
+    // populate aggregated player records
+    info!("Creating aggregated player records...");
+    let bar = progress::bar(player_groups.len() as u64, "Aggregating", false);
+    let mut aggregated_players = Vec::new();
+
+    let builder = CareerBuilder::new(clean_records, options);
+    for (link, seasons) in &player_groups {
+        aggregated_players.push(builder.career(link, seasons));
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+
+    info!("Successfully created {} player records", aggregated_players.len());
+
+    aggregated_players
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::seasons_from_csv;
+    use crate::stats::career_value;
+
+    // Smith's 1885 has no rbi, and Jones's 500 at bats and 50 rbi make that season's league rate .1 a bat;
+    // 1890 has them for everyone
+    const SEASONS: &str = "\
+1885,John,Smith,/player/1,1B,BSN,100,400,60,120,20,5,4,--,30,20,10,5,.300,.350,.420,.770
+1885,Tom,Jones,/player/2,SS,PRO,110,500,70,140,25,6,2,50,35,25,12,6,.280,.330,.380,.710
+1890,John,Smith,/player/1,1B,BSN,80,300,45,90,15,3,6,30,25,15,8,4,.300,.355,.440,.795
+1890,Nate,Nobody,/player/3,P,BSN,5,0,0,0,0,0,0,--,0,0,0,0,.000,.000,.000,.000
+";
+
+    fn smith(missing: MissingPolicy) -> AggregatedPlayer {
+        let options = Options { missing, ..Options::default() };
+        aggregate_players(&seasons_from_csv(SEASONS), &options).into_iter().find(|p| p.link == "/player/1").unwrap()
+    }
+
+    #[test]
+    fn zero_counts_the_missing_season_as_nothing() {
+        let player = smith(MissingPolicy::Zero);
+        assert_eq!(player.missing, vec!["rbi"]);
+        assert_eq!(player.total_rbi, 30);
+        assert_eq!(career_value("rbi", &player), Some(30.0));
+        assert!(player.skipped.is_empty());
+    }
+
+    #[test]
+    fn skip_leaves_him_out_of_the_stats_built_on_it() {
+        let player = smith(MissingPolicy::Skip);
+        assert_eq!(player.skipped, vec!["rbi"]);
+        assert_eq!(career_value("rbi", &player), None);
+        assert_eq!(career_value("homeruns", &player), Some(10.0));
+        assert_eq!(career_value("strikeouts", &player), Some(35.0));
+    }
+
+    #[test]
+    fn estimate_adds_the_league_rate() {
+        // 400 at bats at Jones's .1 rbi a bat
+        let player = smith(MissingPolicy::Estimate);
+        assert_eq!(player.total_rbi, 70);
+        assert_eq!(career_value("rbi", &player), Some(70.0));
+        assert!(player.skipped.is_empty());
+    }
+
+    #[test]
+    fn a_season_without_a_plate_appearance_isnt_missing_anything() {
+        let options = Options { missing: MissingPolicy::Skip, ..Options::default() };
+        let careers = aggregate_players(&seasons_from_csv(SEASONS), &options);
+        let nobody = careers.iter().find(|p| p.link == "/player/3").unwrap();
+        assert!(nobody.missing.is_empty() && nobody.skipped.is_empty());
+    }
+}
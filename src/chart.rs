@@ -0,0 +1,154 @@
+// simple charts (bars, and timeline bands), drawn as svg so an html page can carry them with no extra files
+// (terminal, csv and json output just leave them out)
+
+use crate::table::escape_html;
+
+const BAR_WIDTH: usize = 18;
+const BAR_GAP: usize = 4;
+const PLOT_HEIGHT: usize = 160;
+const MARGIN_TOP: usize = 30;     // room for the title
+const MARGIN_LEFT: usize = 10;
+const MARGIN_BOTTOM: usize = 40;  // room for the sideways labels
+
+const YEAR_WIDTH: usize = 24;     // timeline: across per season
+const BAND_HEIGHT: usize = 18;    // timeline: down per band
+const BAND_GAP: usize = 4;
+const LABEL_WIDTH: usize = 60;    // timeline: room for the band labels on the left
+
+// anything Output::chart can put on a page
+pub trait Chart {
+    fn render_svg(&self) -> String;
+}
+
+#[derive(Debug, Clone)]
+pub struct BarChart {
+    pub title: String,
+    pub bars: Vec<(String, f64)>,
+    pub decimals: usize,  // 0 for counts, 3 for rates
+}
+
+impl BarChart {
+    pub fn new(title: impl Into<String>) -> BarChart {
+        BarChart {
+            title: title.into(),
+            bars: Vec::new(),
+            decimals: 0,
+        }
+    }
+
+    pub fn bar(&mut self, label: impl Into<String>, value: f64) {
+        self.bars.push((label.into(), value));
+    }
+
+}
+
+impl Chart for BarChart {
+    // the chart as an <svg> element, one bar per label, each with its value on top
+    fn render_svg(&self) -> String {
+        let width = MARGIN_LEFT * 2 + self.bars.len() * (BAR_WIDTH + BAR_GAP);
+        let height = MARGIN_TOP + PLOT_HEIGHT + MARGIN_BOTTOM;
+        let baseline = MARGIN_TOP + PLOT_HEIGHT;
+        let max = self.bars.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" class=\"chart\">\n",
+            width.max(200),
+            height
+        );
+        svg.push_str(&format!("<text x=\"{}\" y=\"18\" class=\"chart-title\">{}</text>\n", MARGIN_LEFT, escape_html(&self.title)));
+
+        for (i, (label, value)) in self.bars.iter().enumerate() {
+            let bar_height = if max > 0.0 { (value / max * PLOT_HEIGHT as f64).round() as usize } else { 0 };
+            let x = MARGIN_LEFT + i * (BAR_WIDTH + BAR_GAP);
+            let y = baseline - bar_height;
+            let centre = x + BAR_WIDTH / 2;
+            let shown = format!("{:.*}", self.decimals, value);
+            let shown = if self.decimals > 0 { shown.strip_prefix('0').unwrap_or(&shown).to_string() } else { shown };
+
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"><title>{}: {}</title></rect>\n",
+                x,
+                y,
+                BAR_WIDTH,
+                bar_height,
+                escape_html(label),
+                shown
+            ));
+            svg.push_str(&format!("<text x=\"{}\" y=\"{}\" class=\"chart-value\">{}</text>\n", centre, y.saturating_sub(3), shown));
+            svg.push_str(&format!(
+                "<text x=\"{0}\" y=\"{1}\" class=\"chart-label\" transform=\"rotate(-60 {0} {1})\">{2}</text>\n",
+                centre,
+                baseline + 12,
+                escape_html(label)
+            ));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+// a gantt-style timeline: one band per stretch of seasons, each on its own line, against a row of years
+#[derive(Debug, Clone)]
+pub struct TimelineChart {
+    pub title: String,
+    pub bands: Vec<(String, u32, u32)>,  // label, first season, last season
+}
+
+impl TimelineChart {
+    pub fn new(title: impl Into<String>) -> TimelineChart {
+        TimelineChart {
+            title: title.into(),
+            bands: Vec::new(),
+        }
+    }
+
+    pub fn band(&mut self, label: impl Into<String>, from: u32, to: u32) {
+        self.bands.push((label.into(), from, to));
+    }
+}
+
+impl Chart for TimelineChart {
+    // each band is a bar across the seasons it covers, with the years marked underneath every five
+    fn render_svg(&self) -> String {
+        let first = self.bands.iter().map(|b| b.1).min().unwrap_or(0);
+        let last = self.bands.iter().map(|b| b.2).max().unwrap_or(first);
+        let years = (last - first + 1) as usize;
+
+        let plot_top = MARGIN_TOP;
+        let plot_bottom = plot_top + self.bands.len() * (BAND_HEIGHT + BAND_GAP);
+        let width = LABEL_WIDTH + years * YEAR_WIDTH + MARGIN_LEFT * 2;
+        let height = plot_bottom + 24;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" class=\"chart\">\n",
+            width.max(200),
+            height
+        );
+        svg.push_str(&format!("<text x=\"{}\" y=\"18\" class=\"chart-title\">{}</text>\n", MARGIN_LEFT, escape_html(&self.title)));
+
+        let x_of = |season: u32| MARGIN_LEFT + LABEL_WIDTH + (season - first) as usize * YEAR_WIDTH;
+
+        for (i, (label, from, to)) in self.bands.iter().enumerate() {
+            let y = plot_top + i * (BAND_HEIGHT + BAND_GAP);
+            let span = if from == to { from.to_string() } else { format!("{}-{}", from, to) };
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"><title>{}: {}</title></rect>\n",
+                x_of(*from),
+                y,
+                (to - from + 1) as usize * YEAR_WIDTH,
+                BAND_HEIGHT,
+                escape_html(label),
+                span
+            ));
+            svg.push_str(&format!("<text x=\"{}\" y=\"{}\" class=\"chart-band\">{}</text>\n", MARGIN_LEFT, y + BAND_HEIGHT - 5, escape_html(label)));
+        }
+
+        for season in (first..=last).filter(|s| s % 5 == 0 || *s == first) {
+            svg.push_str(&format!("<text x=\"{}\" y=\"{}\" class=\"chart-value\">{}</text>\n", x_of(season) + YEAR_WIDTH / 2, plot_bottom + 14, season));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
@@ -0,0 +1,586 @@
+// everything to do with getting season rows out of a csv file and into clean records
+
+// this one reads cvs files
+use csv::ReaderBuilder;
+
+// this one helps import cvs data
+use serde::Deserialize;
+
+// the duplicate row policy is a command line option
+use clap::ValueEnum;
+
+// joined columns are kept by name, repeated strings are shared (see Symbols), and repeated rows are found by key
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+use std::sync::Arc;
+
+// progress messages go through the log crate so they can be turned down or off
+use log::{debug, info, warn};
+
+use crate::error::{check_exists, Error, Result};
+use crate::leader_counts::assign_leaders;
+use crate::options::Options;
+use crate::progress;
+use crate::teams::{assign_team_totals, TeamTotals};
+
+// set up the framework for the data we're going to import
+#[derive(Debug, Deserialize, Clone)]
+pub struct PlayerSeason {
+    pub season: u32,
+    pub first_name: Option<String>,  // option because some are null
+    pub last_name: String,
+    pub link: String,
+    pub position: String,
+    pub team: String,
+    pub games_played: u32,
+    pub at_bats: u32,
+    pub runs: u32,
+    pub hits: u32,
+    pub doubles: u32,
+    pub triples: u32,
+    pub homeruns: u32,
+    // the rest can be missing from a file altogether (serde fills in a blank, which cleans up as missing)
+    #[serde(default)]
+    pub rbi: String,  // should be a number, but the raw data is string, we'll fix this later
+    pub walks: u32,
+    #[serde(default)]
+    pub strikeouts: String,  // some are blank, and some files write whole numbers as "123.0"
+    #[serde(default)]
+    pub stolen_bases: String,
+    #[serde(default)]
+    pub caught_stealing: String,
+    #[serde(default)]
+    pub batting_average: String,  // worked out from hits and at bats when it's not there
+    #[serde(default)]
+    pub on_base_percentage: String,
+    #[serde(default)]
+    pub slugging_percentage: String,  // worked out from the hit types when it's not there
+    #[serde(default)]
+    pub on_base_plus_slugging: String,
+    #[serde(default)]
+    pub league: String,  // AL, NL, AAA, ... for a file that mixes leagues; most files don't have it
+}
+
+// the columns a season file can do without, and what can't be worked out when one is missing
+// (batting average and slugging can always be worked out from the counts, so nothing is lost without them)
+pub const OPTIONAL_COLUMNS: &[(&str, &str)] = &[
+    ("rbi", "rbi"),
+    ("strikeouts", "strikeouts, strikeout_rate, tto and speed"),
+    ("stolen_bases", "stolen_bases and speed"),
+    ("caught_stealing", "caught_stealing and speed"),
+    ("batting_average", ""),
+    ("on_base_percentage", "on_base_percentage"),
+    ("slugging_percentage", ""),
+    ("on_base_plus_slugging", "ops"),
+];
+
+// create a new framework with the correct formats
+#[derive(Debug, Clone)]
+pub struct CleanPlayerSeason {
+    pub season: u32,
+    pub first_name: Option<String>,
+    pub last_name: String,
+    pub link: Arc<str>,      // these four repeat from row to row, so the rows share one copy of each (see Symbols)
+    pub position: Arc<str>,
+    pub team: Arc<str>,
+    pub franchise: Arc<str>,  // the franchise's current code (see franchises.rs); the same as team until assigned
+    pub league: Option<Arc<str>>,  // None without a league column (or with it blank), which is one league for everything
+    pub games_played: u32,
+    pub at_bats: u32,
+    pub runs: u32,
+    pub hits: u32,
+    pub doubles: u32,
+    pub triples: u32,
+    pub homeruns: u32,
+    pub rbi: Option<u32>,  // now a proper number (or "none" if missing)
+    pub walks: u32,
+    pub strikeouts: Option<u32>,  // now a proper whole number
+    pub stolen_bases: Option<u32>,  // now a proper number
+    pub caught_stealing: Option<u32>,  // now a proper number
+    pub batting_average: f64,
+    pub on_base_percentage: Option<f64>,  // now a proper number
+    pub slugging_percentage: f64,
+    pub on_base_plus_slugging: Option<f64>,  // now a proper number
+    pub extras: BTreeMap<String, f64>,  // columns joined on from another file (see join.rs), by name
+    pub team_totals: TeamTotals,  // his team's season totals, for the share stats (see teams.rs)
+    pub led: Vec<&'static str>,   // the stats he led the majors in that season (see leader_counts.rs)
+}
+
+// a season played outside MLB (winter ball, japan, etc) that still belongs to a player in the main file
+// these are kept apart from the MLB rows so they never end up in MLB records unless asked for
+#[derive(Debug, Clone)]
+pub struct SupplementarySeason {
+    pub league: String,
+    pub record: CleanPlayerSeason,
+}
+
+// pair of functions to convert messy string data to clean numbers
+pub fn parse_optional_number(value: &str) -> Option<u32> {
+    let value = value.trim();
+    if value == "--" || value.is_empty() {
+        return None;  // set as missing data
+    }
+
+    // try to convert to number, allowing for counts written as decimals ("123.0")
+    value.parse().ok().or_else(|| {
+        let number: f64 = value.parse().ok()?;
+        if number >= 0.0 && number.fract() == 0.0 && number <= u32::MAX as f64 {
+            Some(number as u32)
+        } else {
+            None
+        }
+    })
+}
+
+pub fn parse_optional_float(value: &str) -> Option<f64> {
+    if value == "--" || value.trim().is_empty() {
+        None  // set as missing data
+    } else {
+        value.trim().parse().ok()  // try to convert to float
+    }
+}
+
+// a rate from the counts, for when the file doesn't have it (zero at bats is .000, the way the data writes it)
+fn ratio(count: u32, at_bats: u32) -> f64 {
+    if at_bats == 0 { 0.0 } else { count as f64 / at_bats as f64 }
+}
+
+// one shared copy of each string that repeats across rows: a team code turns up thousands of times and a
+// link once a season, so the rows point at one copy each instead of every row allocating its own
+#[derive(Debug, Default)]
+pub struct Symbols {
+    strings: HashSet<Arc<str>>,
+}
+
+impl Symbols {
+    pub fn intern(&mut self, text: &str) -> Arc<str> {
+        if let Some(existing) = self.strings.get(text) {
+            return existing.clone();
+        }
+        let text: Arc<str> = Arc::from(text);
+        self.strings.insert(text.clone());
+        text
+    }
+}
+
+pub fn clean_player_data(raw: PlayerSeason) -> CleanPlayerSeason {
+    clean_player_data_with(raw, &mut Symbols::default())
+}
+
+// the same, sharing the repeated strings through a symbol table kept across the whole file
+pub fn clean_player_data_with(raw: PlayerSeason, symbols: &mut Symbols) -> CleanPlayerSeason {
+    let team = symbols.intern(&raw.team);
+    CleanPlayerSeason {
+        season: raw.season,
+        first_name: raw.first_name,
+        last_name: raw.last_name,
+        link: symbols.intern(&raw.link),
+        position: symbols.intern(&raw.position),
+        franchise: team.clone(),
+        team,
+        league: Some(raw.league.trim()).filter(|l| !l.is_empty() && *l != "--").map(|l| symbols.intern(l)),
+        games_played: raw.games_played,
+        at_bats: raw.at_bats,
+        runs: raw.runs,
+        hits: raw.hits,
+        doubles: raw.doubles,
+        triples: raw.triples,
+        homeruns: raw.homeruns,
+        rbi: parse_optional_number(&raw.rbi),
+        walks: raw.walks,
+        strikeouts: parse_optional_number(&raw.strikeouts),
+        stolen_bases: parse_optional_number(&raw.stolen_bases),
+        caught_stealing: parse_optional_number(&raw.caught_stealing),
+        batting_average: parse_optional_float(&raw.batting_average).unwrap_or_else(|| ratio(raw.hits, raw.at_bats)),
+        on_base_percentage: parse_optional_float(&raw.on_base_percentage),
+        slugging_percentage: parse_optional_float(&raw.slugging_percentage)
+            .unwrap_or_else(|| ratio(raw.hits + raw.doubles + 2 * raw.triples + 3 * raw.homeruns, raw.at_bats)),
+        on_base_plus_slugging: parse_optional_float(&raw.on_base_plus_slugging),
+        extras: BTreeMap::new(),
+        team_totals: TeamTotals::default(),
+        led: Vec::new(),
+    }
+}
+
+// a season of one league (the whole season, without a league column): what league averages and leaders are
+// worked out over
+pub type LeagueSeason = (u32, Option<Arc<str>>);
+
+pub fn league_season(season: &CleanPlayerSeason) -> LeagueSeason {
+    (season.season, season.league.clone())
+}
+
+// the leagues the rows were played in, in name order (empty without a league column)
+pub fn leagues_in(clean_records: &[CleanPlayerSeason]) -> Vec<&str> {
+    let mut leagues: Vec<&str> = clean_records.iter().filter_map(|s| s.league.as_deref()).collect();
+    leagues.sort();
+    leagues.dedup();
+    leagues
+}
+
+// is the row from one of these leagues? (AL or al, as the --league option takes them)
+pub fn in_leagues(season: &CleanPlayerSeason, leagues: &[String]) -> bool {
+    season.league.as_deref().is_some_and(|league| leagues.iter().any(|wanted| wanted.eq_ignore_ascii_case(league)))
+}
+
+// a row that didn't make it into the records, and why (the summary command lists them)
+#[derive(Debug, Clone)]
+pub struct RejectedRow {
+    pub file: String,
+    pub line: u64,
+    pub reason: String,
+}
+
+// what loading a file gave: the rows, and the ones that didn't make it in, in file order
+// skipped rows were read fine but left out (a season that can't be right, a repeated row), errors couldn't be read at all
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    pub records: Vec<CleanPlayerSeason>,
+    pub skipped: Vec<RejectedRow>,
+    pub errors: Vec<RejectedRow>,
+}
+
+impl LoadReport {
+    // every row that didn't make it in, either way, by line
+    pub fn rejected(&self) -> Vec<RejectedRow> {
+        let mut rejected: Vec<RejectedRow> = self.skipped.iter().chain(&self.errors).cloned().collect();
+        rejected.sort_by_key(|r| r.line);
+        rejected
+    }
+}
+
+// what to do with a row that has the same link, season and team as an earlier one (a scrape that
+// wrote a row twice, which would otherwise count that season twice over)
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum DuplicatePolicy {
+    /// stop, naming the first repeated row
+    Error,
+    /// keep the first copy and leave the rest out
+    #[default]
+    KeepFirst,
+    /// keep the first copy, with any value it's missing (rbi, strikeouts, ...) taken from the later ones
+    Merge,
+}
+
+// fill in the optional values a row is missing from a later copy of it
+fn merge_missing(first: &mut PlayerSeason, later: PlayerSeason) {
+    if first.first_name.is_none() {
+        first.first_name = later.first_name;
+    }
+    if first.league.trim().is_empty() {
+        first.league = later.league;
+    }
+    let fields = [
+        (&mut first.rbi, later.rbi),
+        (&mut first.strikeouts, later.strikeouts),
+        (&mut first.stolen_bases, later.stolen_bases),
+        (&mut first.caught_stealing, later.caught_stealing),
+        (&mut first.batting_average, later.batting_average),
+        (&mut first.on_base_percentage, later.on_base_percentage),
+        (&mut first.slugging_percentage, later.slugging_percentage),
+        (&mut first.on_base_plus_slugging, later.on_base_plus_slugging),
+    ];
+    for (value, later_value) in fields {
+        if parse_optional_float(value).is_none() {
+            *value = later_value;
+        }
+    }
+}
+
+// the first season of professional league play (the National Association, 1871); anything earlier is a typo
+pub const FIRST_SEASON: u32 = 1871;
+
+// this year, from the clock (a year's average length in seconds, so it can be a day out around new year)
+pub fn current_year() -> u32 {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    1970 + (seconds / 31_556_952) as u32
+}
+
+// why a season number can't be right, if it can't: before 1871, or later than next year
+// (next year is allowed, for files of projections or spring training)
+pub fn implausible_season(season: u32, this_year: u32) -> Option<String> {
+    if season < FIRST_SEASON {
+        Some(format!("season {} is before {}", season, FIRST_SEASON))
+    } else if season > this_year + 1 {
+        Some(format!("season {} is after next year ({})", season, this_year + 1))
+    } else {
+        None
+    }
+}
+
+// read a season csv file and hand back the cleaned rows (rows that couldn't be used are logged and dropped,
+// a repeated row keeps its first copy, and the season leaders are found with the default options)
+pub fn load_seasons(file_path: &str) -> Result<Vec<CleanPlayerSeason>> {
+    Ok(load_seasons_checked(file_path, DuplicatePolicy::default(), &Options::default())?.records)
+}
+
+// the same, handing back the rows that couldn't be used as well (the options decide who qualified to lead)
+pub fn load_seasons_checked(file_path: &str, duplicates: DuplicatePolicy, options: &Options) -> Result<LoadReport> {
+    check_exists("season file", file_path)?;
+
+    // the parsing bar counts bytes, since we don't know how many rows there are until we've read them
+    let file_size = std::fs::metadata(file_path)?.len();
+    debug!("Reading {} ({} bytes)", file_path, file_size);
+
+    read_seasons(std::fs::File::open(file_path)?, file_path, file_size, duplicates, options)
+}
+
+// the same from anything that reads like a season file (file_path is only for the messages, and size for
+// the parsing bar)
+pub fn read_seasons(input: impl Read, file_path: &str, size: u64, duplicates: DuplicatePolicy, options: &Options) -> Result<LoadReport> {
+    // create CSV reader
+    let mut reader = ReaderBuilder::new()
+        .has_headers(true)
+        .from_reader(input);
+
+    // a file without some of the optional columns still loads, with those values left empty
+    let headers = reader.headers()?.clone();
+    for (column, lost) in OPTIONAL_COLUMNS {
+        if headers.iter().any(|h| h.trim() == *column) {
+            continue;
+        }
+        if lost.is_empty() {
+            info!("{} has no {} column, so it's worked out from the counts", file_path, column);
+        } else {
+            warn!("{} has no {} column, so {} can't be ranked", file_path, column, lost);
+        }
+    }
+    if headers.iter().any(|h| h.trim() == "league") {
+        info!("{} has a league column, so league averages and leaders are worked out league by league", file_path);
+    }
+
+    // create a new empty list called raw_records
+    let mut raw_records = Vec::new();
+    let mut skipped = Vec::new();
+    let mut errors = Vec::new();
+    let mut future_count = 0;
+    let this_year = current_year();
+
+    // where each link, season and team first came up: its place in raw_records and its line
+    let mut first_rows: HashMap<(String, u32, String), (usize, u64)> = HashMap::new();
+    let mut duplicate_count = 0;
+
+    let parse_bar = progress::bar(size, "Parsing", true);
+
+    // read each record
+    let mut records = reader.deserialize();
+    let mut line_num = 0;
+    while let Some(result) = records.next() {
+        match result {
+            Ok(record) => {
+                let player: PlayerSeason = record;
+
+                // a season that can't be right would throw off every first and last season, so it's kept out
+                if let Some(reason) = implausible_season(player.season, this_year) {
+                    skipped.push(RejectedRow { file: file_path.to_string(), line: line_num + 2, reason });
+                } else if let Some(&(index, first_line)) = first_rows.get(&(player.link.clone(), player.season, player.team.clone())) {
+                    // the same player, season and team again: stop, or keep the first copy (filled in from this one)
+                    let line = line_num + 2;
+                    duplicate_count += 1;
+                    let reason = match duplicates {
+                        DuplicatePolicy::Error => {
+                            return Err(Error::Data(format!(
+                                "{} line {} repeats line {} ({} {} {}); --duplicates keep-first or merge loads it anyway",
+                                file_path, line, first_line, player.link, player.season, player.team
+                            )));
+                        }
+                        DuplicatePolicy::KeepFirst => format!("repeats line {} (same link, season and team)", first_line),
+                        DuplicatePolicy::Merge => {
+                            merge_missing(&mut raw_records[index], player);
+                            format!("merged into line {} (same link, season and team)", first_line)
+                        }
+                    };
+                    skipped.push(RejectedRow { file: file_path.to_string(), line, reason });
+                } else {
+                    if player.season > this_year {
+                        future_count += 1;
+                    }
+                    first_rows.insert((player.link.clone(), player.season, player.team.clone()), (raw_records.len(), line_num + 2));
+                    raw_records.push(player);
+                }
+            }
+            Err(e) => {
+                if errors.len() < 5 {
+                    warn!("Error on line {}: {}", line_num + 2, e);
+                }
+                errors.push(RejectedRow { file: file_path.to_string(), line: line_num + 2, reason: e.to_string() });
+            }
+        }
+
+        line_num += 1;
+        if line_num % 1000 == 0 {
+            parse_bar.set_position(records.reader().position().byte());
+        }
+    }
+    parse_bar.finish_and_clear();
+
+    if errors.len() > 5 {
+        warn!("{} more lines couldn't be read", errors.len() - 5);
+    }
+    if skipped.len() > duplicate_count {
+        warn!("{} rows have a season before {} or after {} and were left out (see the summary command)", skipped.len() - duplicate_count, FIRST_SEASON, this_year + 1);
+    }
+    if duplicate_count > 0 {
+        let handled = if duplicates == DuplicatePolicy::Merge { "merged into the first copy" } else { "left out, keeping the first copy" };
+        warn!("{} rows repeat an earlier row's link, season and team and were {} (see the summary command)", duplicate_count, handled);
+    }
+    if future_count > 0 {
+        warn!("{} rows are for {}, which hasn't been played yet", future_count, this_year + 1);
+    }
+    info!("Successfully loaded {} raw records", raw_records.len());
+
+    // clean the data
+    info!("Cleaning data...");
+    let clean_bar = progress::bar(raw_records.len() as u64, "Cleaning", false);
+    let mut clean_records = Vec::with_capacity(raw_records.len());
+    let mut symbols = Symbols::default();
+
+    for raw_record in raw_records {
+        let clean_record = clean_player_data_with(raw_record, &mut symbols);
+        clean_records.push(clean_record);
+        clean_bar.inc(1);
+    }
+    clean_bar.finish_and_clear();
+
+    info!("Successfully cleaned {} records", clean_records.len());
+
+    // each row gets its team's totals for the season, for the share of team offense stats,
+    // and the stats it led the majors in
+    assign_team_totals(&mut clean_records);
+    assign_leaders(&mut clean_records, options);
+
+    Ok(LoadReport { records: clean_records, skipped, errors })
+}
+
+// split a --supplementary argument into its league tag and file path
+// "NPB=japan.csv" is tagged NPB, a bare "winter_league.csv" is tagged with its file name
+pub fn parse_supplementary_arg(arg: &str) -> (String, String) {
+    match arg.split_once('=') {
+        Some((league, path)) => (league.trim().to_string(), path.trim().to_string()),
+        None => {
+            let league = std::path::Path::new(arg)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| arg.to_string());
+            (league, arg.to_string())
+        }
+    }
+}
+
+// load a supplementary (non-MLB) file and tag every row with its league
+// (the report comes back with its records taken out and tagged; a row without a league of its own takes
+// the tag, so folded in with --include-supplementary it's measured against its own league)
+pub fn load_supplementary(league: &str, file_path: &str, duplicates: DuplicatePolicy, options: &Options) -> Result<(Vec<SupplementarySeason>, LoadReport)> {
+    let mut report = load_seasons_checked(file_path, duplicates, options)?;
+
+    let tagged = std::mem::take(&mut report.records)
+        .into_iter()
+        .map(|mut record| {
+            if record.league.is_none() {
+                record.league = Some(Arc::from(league));
+            }
+            SupplementarySeason { league: league.to_string(), record }
+        })
+        .collect();
+
+    Ok((tagged, report))
+}
+
+// a season file's header, and rows under it read from csv text, for the tests in every module
+#[cfg(test)]
+pub(crate) const TEST_HEADER: &str = "season,first_name,last_name,link,position,team,games_played,at_bats,runs,hits,doubles,triples,homeruns,rbi,walks,strikeouts,stolen_bases,caught_stealing,batting_average,on_base_percentage,slugging_percentage,on_base_plus_slugging";
+
+#[cfg(test)]
+pub(crate) fn seasons_from_csv(rows: &str) -> Vec<CleanPlayerSeason> {
+    let text = format!("{}\n{}", TEST_HEADER, rows);
+    read_seasons(text.as_bytes(), "test rows", text.len() as u64, DuplicatePolicy::default(), &Options::default()).unwrap().records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bonds's 2001 twice, the first copy without his rbi, with McGwire's 1998 in between
+    const SEASONS: &str = "\
+2001,Barry,Bonds,/player/111188,LF,SF,153,476,129,156,32,2,73,--,177,93,13,3,.328,.515,.863,1.379
+1998,Mark,McGwire,/player/118743,1B,STL,155,509,130,152,21,0,70,147,162,155,1,0,.299,.470,.752,1.222
+2001,Barry,Bonds,/player/111188,LF,SF,153,476,129,156,32,2,73,137,177,93,13,3,.328,.515,.863,1.379
+";
+
+    fn load(duplicates: DuplicatePolicy) -> Result<LoadReport> {
+        let text = format!("{}\n{}", TEST_HEADER, SEASONS);
+        read_seasons(text.as_bytes(), "seasons.csv", text.len() as u64, duplicates, &Options::default())
+    }
+
+    #[test]
+    fn error_stops_at_the_repeated_row() {
+        match load(DuplicatePolicy::Error) {
+            Err(Error::Data(message)) => assert!(message.contains("seasons.csv line 4 repeats line 2"), "{}", message),
+            other => panic!("expected a data error, got {:?}", other.map(|r| r.records.len())),
+        }
+    }
+
+    #[test]
+    fn keep_first_leaves_the_repeat_out() {
+        let report = load(DuplicatePolicy::KeepFirst).unwrap();
+        assert_eq!(report.records.len(), 2);
+        assert_eq!(report.records[0].rbi, None);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].line, 4);
+        assert!(report.skipped[0].reason.starts_with("repeats line 2"), "{}", report.skipped[0].reason);
+    }
+
+    #[test]
+    fn merge_fills_in_the_first_copy() {
+        let report = load(DuplicatePolicy::Merge).unwrap();
+        assert_eq!(report.records.len(), 2);
+        assert_eq!(report.records[0].rbi, Some(137));
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].reason.starts_with("merged into line 2"), "{}", report.skipped[0].reason);
+    }
+
+    #[test]
+    fn counts_can_be_written_as_whole_decimals() {
+        assert_eq!(parse_optional_number("123"), Some(123));
+        assert_eq!(parse_optional_number(" 45 "), Some(45));
+        assert_eq!(parse_optional_number("123.0"), Some(123));
+        assert_eq!(parse_optional_number("0.0"), Some(0));
+    }
+
+    #[test]
+    fn anything_else_isnt_a_count() {
+        for value in ["123.5", "abc", "-1", "-1.0", "--", "", "  ", "5000000000"] {
+            assert_eq!(parse_optional_number(value), None, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn seasons_run_from_1871_to_next_year() {
+        assert!(implausible_season(1870, 2026).is_some_and(|reason| reason.contains("before 1871")));
+        assert_eq!(implausible_season(1871, 2026), None);
+        assert_eq!(implausible_season(2026, 2026), None);
+        assert_eq!(implausible_season(2027, 2026), None);
+        assert!(implausible_season(2028, 2026).is_some_and(|reason| reason.contains("after next year (2027)")));
+    }
+
+    #[test]
+    fn the_clock_decides_next_year() {
+        // the year from the clock can be a day out around new year, so only its edge is checked
+        let this_year = current_year();
+        assert!(this_year >= 2024, "{}", this_year);
+        assert_eq!(implausible_season(this_year + 1, current_year()), None);
+        assert!(implausible_season(this_year + 2, current_year()).is_some());
+    }
+
+    #[test]
+    fn exit_codes() {
+        // a season file that isn't there
+        let missing = load_seasons("no_such_seasons.csv").unwrap_err();
+        assert!(matches!(missing, Error::NotFound { .. }));
+        assert_eq!(missing.exit_code(), 3);
+        assert_eq!(Error::Usage("--jobs has to be at least 1".to_string()).exit_code(), 2);
+    }
+}
@@ -0,0 +1,325 @@
+// the diff command: what changed between two versions of the season file (the last scrape and a fresh
+// one), to see exactly what the provider revised: players who weren't there before, seasons that came or
+// went, and every number that changed in a season both files have
+//
+// seasons are matched on link and season; a player traded during a season has a row for each team, and
+// those are added up first, so a provider splitting or regrouping a season's rows differently isn't
+// reported as a correction unless the totals moved
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::data::{load_seasons_checked, CleanPlayerSeason, DuplicatePolicy};
+use crate::error::Result;
+use crate::options::Options;
+use crate::report::season_player_name;
+use crate::table::{Output, Table};
+
+// the counts compared, by the name the file's column has
+type RowCount = fn(&CleanPlayerSeason) -> Option<u32>;
+const COUNTS: &[(&str, RowCount)] = &[
+    ("games_played", |s| Some(s.games_played)),
+    ("at_bats", |s| Some(s.at_bats)),
+    ("runs", |s| Some(s.runs)),
+    ("hits", |s| Some(s.hits)),
+    ("doubles", |s| Some(s.doubles)),
+    ("triples", |s| Some(s.triples)),
+    ("homeruns", |s| Some(s.homeruns)),
+    ("rbi", |s| s.rbi),
+    ("walks", |s| Some(s.walks)),
+    ("strikeouts", |s| s.strikeouts),
+    ("stolen_bases", |s| s.stolen_bases),
+    ("caught_stealing", |s| s.caught_stealing),
+];
+
+// one player's season in one file: his rows for it, in file order
+type Seasons<'a> = BTreeMap<(&'a str, u32), Vec<&'a CleanPlayerSeason>>;
+
+fn by_link_and_season(records: &[CleanPlayerSeason]) -> Seasons<'_> {
+    let mut seasons: Seasons = BTreeMap::new();
+    for season in records {
+        seasons.entry((season.link.as_ref(), season.season)).or_default().push(season);
+    }
+    seasons
+}
+
+// the rates compared, each averaged over the season's rows by its weight (at bats, or plate appearances
+// for the ones with walks in)
+type RowRate = fn(&CleanPlayerSeason) -> Option<f64>;
+type RowWeight = fn(&CleanPlayerSeason) -> u32;
+const RATES: &[(&str, RowWeight, RowRate)] = &[
+    ("batting_average", |s| s.at_bats, |s| Some(s.batting_average)),
+    ("on_base_percentage", |s| s.at_bats + s.walks, |s| s.on_base_percentage),
+    ("slugging_percentage", |s| s.at_bats, |s| Some(s.slugging_percentage)),
+    ("on_base_plus_slugging", |s| s.at_bats + s.walks, |s| s.on_base_plus_slugging),
+];
+
+// the teams, as "NYY" or "BOS, NYY" for a season split between them (in code order, so the same teams
+// in a different row order aren't a correction)
+fn teams(rows: &[&CleanPlayerSeason]) -> String {
+    let mut teams: Vec<&str> = rows.iter().map(|s| s.team.as_ref()).collect();
+    teams.sort_unstable();
+    teams.dedup();
+    teams.join(", ")
+}
+
+// a count added up over the season's rows (None when a row doesn't have it)
+fn total(rows: &[&CleanPlayerSeason], count: RowCount) -> Option<u32> {
+    rows.iter().map(|s| count(s)).sum()
+}
+
+// a rate for the season's rows, weighted (None when a row doesn't have it, or there's nothing to weigh)
+fn weighted(rows: &[&CleanPlayerSeason], weight: RowWeight, rate: RowRate) -> Option<f64> {
+    let total_weight: u32 = rows.iter().map(|s| weight(s)).sum();
+    if total_weight == 0 {
+        return None;
+    }
+    let total: Option<f64> = rows.iter().map(|s| rate(s).map(|value| value * weight(s) as f64)).sum();
+    Some(total? / total_weight as f64)
+}
+
+fn optional(value: Option<u32>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "--".to_string())
+}
+
+// what changed in one season both files have: (field, old, new, change), with the change signed for counts
+fn corrections(old: &[&CleanPlayerSeason], new: &[&CleanPlayerSeason]) -> Vec<(&'static str, String, String, String)> {
+    let mut changed = Vec::new();
+
+    let (old_name, new_name) = (season_player_name(old[0]), season_player_name(new[0]));
+    if old_name != new_name {
+        changed.push(("name", old_name, new_name, String::new()));
+    }
+    let (old_teams, new_teams) = (teams(old), teams(new));
+    if old_teams != new_teams {
+        changed.push(("team", old_teams, new_teams, String::new()));
+    }
+    let (old_league, new_league) = (old[0].league.as_deref().unwrap_or("--"), new[0].league.as_deref().unwrap_or("--"));
+    if old_league != new_league {
+        changed.push(("league", old_league.to_string(), new_league.to_string(), String::new()));
+    }
+
+    for &(field, count) in COUNTS {
+        let (before, after) = (total(old, count), total(new, count));
+        if before != after {
+            let change = match (before, after) {
+                (Some(before), Some(after)) => format!("{:+}", after as i64 - before as i64),
+                _ => String::new(),
+            };
+            changed.push((field, optional(before), optional(after), change));
+        }
+    }
+
+    // rounded the way the file writes it, so a different number of decimal places isn't a correction
+    let show_rate = |value: Option<f64>| value.map(|v| format!("{:.3}", v)).unwrap_or_else(|| "--".to_string());
+    for &(field, weight, rate) in RATES {
+        let (before, after) = (show_rate(weighted(old, weight, rate)), show_rate(weighted(new, weight, rate)));
+        if before != after {
+            changed.push((field, before, after, String::new()));
+        }
+    }
+
+    changed
+}
+
+// everything that changed, a table for each kind of change
+struct Differences {
+    new_players: Table,
+    added: Table,
+    removed: Table,
+    corrected: Table,
+    corrected_seasons: usize,
+    by_field: BTreeMap<&'static str, usize>,
+}
+
+fn differences(old_records: &[CleanPlayerSeason], new_records: &[CleanPlayerSeason]) -> Differences {
+    let (old_seasons, new_seasons) = (by_link_and_season(old_records), by_link_and_season(new_records));
+
+    let old_links: BTreeSet<&str> = old_seasons.keys().map(|&(link, _)| link).collect();
+    let new_links: BTreeSet<&str> = new_seasons.keys().map(|&(link, _)| link).collect();
+
+    // players the old file didn't have at all
+    let mut new_players = Table::new("New players")
+        .column("Player", 25)
+        .column("Link", 16)
+        .right("From", 6)
+        .right("To", 6)
+        .right("Seasons", 7)
+        .right("AB", 6);
+    for &link in new_links.difference(&old_links) {
+        let seasons: Vec<(&u32, &Vec<&CleanPlayerSeason>)> = new_seasons.range((link, 0)..=(link, u32::MAX)).map(|((_, year), rows)| (year, rows)).collect();
+        let at_bats: u32 = seasons.iter().flat_map(|(_, rows)| rows.iter()).map(|s| s.at_bats).sum();
+        new_players.row(vec![
+            season_player_name(seasons[0].1[0]),
+            link.to_string(),
+            seasons[0].0.to_string(),
+            seasons[seasons.len() - 1].0.to_string(),
+            seasons.len().to_string(),
+            at_bats.to_string(),
+        ]);
+    }
+
+    // seasons only one of the files has (for players in both; a new player's seasons are all new)
+    let season_table = |title: &str| {
+        Table::new(title)
+            .column("Player", 25)
+            .column("Link", 16)
+            .right("Season", 6)
+            .column("Team", 10)
+            .right("AB", 5)
+    };
+    let season_row = |rows: &[&CleanPlayerSeason]| {
+        vec![
+            season_player_name(rows[0]),
+            rows[0].link.to_string(),
+            rows[0].season.to_string(),
+            teams(rows),
+            rows.iter().map(|s| s.at_bats).sum::<u32>().to_string(),
+        ]
+    };
+
+    let mut added = season_table("New seasons for players already there");
+    for (key, rows) in &new_seasons {
+        if old_links.contains(key.0) && !old_seasons.contains_key(key) {
+            added.row(season_row(rows));
+        }
+    }
+    let mut removed = season_table("Removed seasons");
+    for (key, rows) in &old_seasons {
+        if !new_seasons.contains_key(key) {
+            removed.row(season_row(rows));
+        }
+    }
+
+    // every number that changed in a season both files have
+    let mut corrected = Table::new("Corrections")
+        .column("Player", 25)
+        .column("Link", 16)
+        .right("Season", 6)
+        .column("Field", 18)
+        .right("Old", 10)
+        .right("New", 10)
+        .right("Change", 7);
+    let mut by_field: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut corrected_seasons = 0;
+    for (key, old_rows) in &old_seasons {
+        let Some(new_rows) = new_seasons.get(key) else {
+            continue;
+        };
+        let changed = corrections(old_rows, new_rows);
+        if !changed.is_empty() {
+            corrected_seasons += 1;
+        }
+        for (field, before, after, change) in changed {
+            *by_field.entry(field).or_default() += 1;
+            corrected.row(vec![season_player_name(new_rows[0]), key.0.to_string(), key.1.to_string(), field.to_string(), before, after, change]);
+        }
+    }
+
+    Differences { new_players, added, removed, corrected, corrected_seasons, by_field }
+}
+
+// the diff command: the old file against the new one (duplicate rows in either are handled as --duplicates says)
+pub fn show_diff(out: &mut Output, old_path: &str, new_path: &str, duplicates: DuplicatePolicy, options: &Options) -> Result<()> {
+    let old_records = load_seasons_checked(old_path, duplicates, options)?.records;
+    let new_records = load_seasons_checked(new_path, duplicates, options)?.records;
+
+    out.line(&format!("Comparing {} ({} rows) with {} ({} rows)", old_path, old_records.len(), new_path, new_records.len()));
+
+    let Differences { new_players, added, removed, corrected, corrected_seasons, by_field } = differences(&old_records, &new_records);
+    let counts = (new_players.rows.len(), added.rows.len(), removed.rows.len(), corrected.rows.len());
+    for table in [new_players, added, removed, corrected] {
+        if !table.rows.is_empty() {
+            out.table(&table);
+        }
+    }
+
+    out.line("");
+    if counts == (0, 0, 0, 0) {
+        out.line("No differences: every season has the same numbers in both files");
+        return Ok(());
+    }
+    let fields: Vec<String> = by_field.iter().map(|(field, count)| format!("{} {}", field, count)).collect();
+    out.line("Summary:");
+    out.line(&format!("  new players:        {}", counts.0));
+    out.line(&format!("  new seasons:        {} (for players already there)", counts.1));
+    out.line(&format!("  removed seasons:    {}", counts.2));
+    out.line(&format!("  corrected seasons:  {}", corrected_seasons));
+    if !fields.is_empty() {
+        out.line(&format!("  corrections:        {} ({})", counts.3, fields.join(", ")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::seasons_from_csv;
+    use crate::table::Row;
+
+    // Bonds's 2001, McGwire's 1998, and Rickey Henderson's 1989 split between the Yankees and the A's
+    const OLD: &str = "\
+2001,Barry,Bonds,/player/111188,LF,SF,153,476,129,156,32,2,73,137,177,93,13,3,.328,.515,.863,1.379
+1998,Mark,McGwire,/player/118743,1B,STL,155,509,130,152,21,0,70,147,162,155,1,0,.299,.470,.752,1.222
+1989,Rickey,Henderson,/player/116435,LF,NYY,65,235,41,58,13,1,3,22,56,29,25,8,.247,.392,.349,.741
+1989,Rickey,Henderson,/player/116435,LF,OAK,85,306,72,90,13,2,9,35,70,39,52,6,.294,.425,.438,.863
+";
+
+    fn cells(table: &Table) -> Vec<Vec<String>> {
+        table
+            .rows
+            .iter()
+            .filter_map(|row| match row {
+                Row::Cells(cells) => Some(cells.clone()),
+                Row::Separator => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_same_file_has_no_differences() {
+        let records = seasons_from_csv(OLD);
+        let found = differences(&records, &records);
+        assert!([found.new_players, found.added, found.removed, found.corrected].iter().all(|t| t.rows.is_empty()));
+    }
+
+    #[test]
+    fn an_added_row_is_a_new_season() {
+        let new = format!("{}2002,Barry,Bonds,/player/111188,LF,SF,143,403,117,149,31,2,46,110,198,47,9,2,.370,.582,.799,1.381\n", OLD);
+        let found = differences(&seasons_from_csv(OLD), &seasons_from_csv(&new));
+        assert_eq!(cells(&found.added), vec![vec!["Barry Bonds", "/player/111188", "2002", "SF", "403"]]);
+        assert!(found.removed.rows.is_empty() && found.corrected.rows.is_empty() && found.new_players.rows.is_empty());
+    }
+
+    #[test]
+    fn a_missing_row_is_a_removed_season() {
+        let new: String = OLD.lines().filter(|line| !line.contains("McGwire")).map(|line| format!("{}\n", line)).collect();
+        let found = differences(&seasons_from_csv(OLD), &seasons_from_csv(&new));
+        assert_eq!(cells(&found.removed), vec![vec!["Mark McGwire", "/player/118743", "1998", "STL", "509"]]);
+        assert!(found.added.rows.is_empty() && found.corrected.rows.is_empty());
+    }
+
+    #[test]
+    fn a_changed_value_is_a_correction() {
+        let new = OLD.replace("129,156,32,2,73,137", "129,156,32,2,73,138");
+        let found = differences(&seasons_from_csv(OLD), &seasons_from_csv(&new));
+        assert_eq!(cells(&found.corrected), vec![vec!["Barry Bonds", "/player/111188", "2001", "rbi", "137", "138", "+1"]]);
+        assert_eq!(found.corrected_seasons, 1);
+        assert_eq!(found.by_field.get("rbi"), Some(&1));
+    }
+
+    #[test]
+    fn a_changed_rate_is_a_correction() {
+        let new = OLD.replace(".299,.470,.752,1.222", ".301,.470,.752,1.222");
+        let found = differences(&seasons_from_csv(OLD), &seasons_from_csv(&new));
+        assert_eq!(cells(&found.corrected), vec![vec!["Mark McGwire", "/player/118743", "1998", "batting_average", "0.299", "0.301", ""]]);
+    }
+
+    #[test]
+    fn reordered_teams_are_not_a_correction() {
+        let mut lines: Vec<&str> = OLD.lines().collect();
+        lines.swap(2, 3);
+        let new = lines.join("\n");
+        let found = differences(&seasons_from_csv(OLD), &seasons_from_csv(&new));
+        assert!(found.corrected.rows.is_empty(), "{:?}", cells(&found.corrected));
+    }
+}
@@ -0,0 +1,75 @@
+// the errors the library hands back instead of printing and carrying on, and the exit code the
+// command line tool turns each one into, so a script can tell a missing file from a bad one
+//
+// exit codes: 0 worked, 1 anything else, 2 a bad option (or stat), 3 an input file (or the player,
+// team or season asked for) that isn't there, 4 an input file that's there but can't be read, 5 output
+// that couldn't be written
+
+use std::io;
+use std::path::Path;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    // an input file that isn't there ("splits file", "halves.csv"), or a player, team or season asked for
+    #[error("{what} {path} not found")]
+    NotFound { what: &'static str, path: String },
+
+    // an option that doesn't make sense (a bad --join-key, xlsx to the screen)
+    #[error("{0}")]
+    Usage(String),
+
+    // a file that's there but isn't what it should be (no link column, say)
+    #[error("{0}")]
+    Data(String),
+
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    // reports in a batch that didn't work, by title (each was logged as it failed)
+    #[error("{} of {total} reports failed: {}", failed.len(), failed.join(", "))]
+    Batch { failed: Vec<String>, total: usize, code: u8 },
+
+    // the name index for search
+    #[error(transparent)]
+    Index(#[from] fst::Error),
+
+    // watching the files for --watch
+    #[error(transparent)]
+    Watch(#[from] notify::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::NotFound { .. } => 3,
+            // a csv error can be the disk rather than the file (writing export-careers, say)
+            Error::Csv(e) if e.is_io_error() => 5,
+            Error::Data(_) | Error::Csv(_) => 4,
+            // json is only ever written
+            Error::Json(_) | Error::Io(_) => 5,
+            Error::Index(_) | Error::Watch(_) => 1,
+            // the first failed report's
+            Error::Batch { code, .. } => *code,
+        }
+    }
+}
+
+// NotFound if the file isn't there, before trying to read it
+pub fn check_exists(what: &'static str, path: &str) -> Result<()> {
+    if Path::new(path).exists() {
+        Ok(())
+    } else {
+        Err(Error::NotFound { what, path: path.to_string() })
+    }
+}
@@ -0,0 +1,178 @@
+// everything in one go for people who want to work with the numbers themselves:
+// every season row, every career, and a leaderboard for every stat
+// (best sent to a file: --out stats.xlsx gives one worksheet per table)
+
+use crate::careers::AggregatedPlayer;
+use crate::data::{leagues_in, CleanPlayerSeason};
+use crate::join::joined_columns;
+use crate::leaders::{leader_table, LeaderArgs, Scope};
+use crate::options::Options;
+use crate::stats::{all_stats, format_rate, has_career_value};
+use crate::table::{Output, Table};
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "--".to_string())
+}
+
+// every season row, as loaded
+fn seasons_table(clean_records: &[CleanPlayerSeason], joined: &[String]) -> Table {
+    let mut table = Table::new("Seasons")
+        .right("Season", 6)
+        .column("First Name", 15)
+        .column("Last Name", 15)
+        .column("Link", 16)
+        .column("Pos", 4)
+        .column("Team", 5)
+        .right("G", 4)
+        .right("AB", 5)
+        .right("R", 4)
+        .right("H", 4)
+        .right("2B", 4)
+        .right("3B", 4)
+        .right("HR", 4)
+        .right("RBI", 4)
+        .right("BB", 4)
+        .right("SO", 4)
+        .right("SB", 4)
+        .right("CS", 4)
+        .right("AVG", 5)
+        .right("OBP", 5)
+        .right("SLG", 5)
+        .right("OPS", 5);
+    // the league, after the numbers, for data that has one
+    let by_league = !leagues_in(clean_records).is_empty();
+    if by_league {
+        table = table.column("League", 6);
+    }
+    for name in joined {
+        table = table.right(name, name.len().max(5));
+    }
+
+    for s in clean_records {
+        let mut row = vec![
+            s.season.to_string(),
+            s.first_name.clone().unwrap_or_default(),
+            s.last_name.clone(),
+            s.link.to_string(),
+            s.position.to_string(),
+            s.team.to_string(),
+            s.games_played.to_string(),
+            s.at_bats.to_string(),
+            s.runs.to_string(),
+            s.hits.to_string(),
+            s.doubles.to_string(),
+            s.triples.to_string(),
+            s.homeruns.to_string(),
+            optional(s.rbi),
+            s.walks.to_string(),
+            optional(s.strikeouts),
+            optional(s.stolen_bases),
+            optional(s.caught_stealing),
+            format_rate(s.batting_average),
+            optional(s.on_base_percentage.map(format_rate)),
+            format_rate(s.slugging_percentage),
+            optional(s.on_base_plus_slugging.map(format_rate)),
+        ];
+        if by_league {
+            row.push(optional(s.league.as_deref()));
+        }
+        row.extend(joined.iter().map(|name| optional(s.extras.get(name).map(|v| format!("{:.1}", v)))));
+        table.row(row);
+    }
+
+    table
+}
+
+// every career, oldest first
+fn careers_table(aggregated_players: &[AggregatedPlayer], joined: &[String]) -> Table {
+    let mut players: Vec<&AggregatedPlayer> = aggregated_players.iter().collect();
+    players.sort_by_key(|p| (p.first_season, p.last_name.clone(), p.first_name.clone()));
+
+    let mut table = Table::new("Careers")
+        .column("First Name", 15)
+        .column("Last Name", 15)
+        .column("Link", 16)
+        .right("From", 6)
+        .right("To", 6)
+        .right("Seasons", 7)
+        .column("Positions", 12)
+        .column("Primary", 7)
+        .column("Teams", 20)
+        .right("G", 5)
+        .right("AB", 6)
+        .right("R", 5)
+        .right("H", 5)
+        .right("2B", 4)
+        .right("3B", 4)
+        .right("HR", 4)
+        .right("RBI", 5)
+        .right("BB", 5)
+        .right("SO", 5)
+        .right("SB", 4)
+        .right("CS", 4);
+    let by_league = players.iter().any(|p| !p.leagues.is_empty());
+    if by_league {
+        table = table.column("Leagues", 12);
+    }
+    for name in joined {
+        table = table.right(name, name.len().max(5));
+    }
+
+    for p in players {
+        let mut row = vec![
+            p.first_name.clone(),
+            p.last_name.clone(),
+            p.link.clone(),
+            p.first_season.to_string(),
+            p.last_season.to_string(),
+            p.seasons_played.to_string(),
+            p.positions.clone(),
+            p.primary_position.clone(),
+            p.teams.clone(),
+            p.total_games_played.to_string(),
+            p.total_at_bats.to_string(),
+            p.total_runs.to_string(),
+            p.total_hits.to_string(),
+            p.total_doubles.to_string(),
+            p.total_triples.to_string(),
+            p.total_homeruns.to_string(),
+            p.total_rbi.to_string(),
+            p.total_walks.to_string(),
+            p.total_strikeouts.to_string(),
+            p.total_stolen_bases.to_string(),
+            p.total_caught_stealing.to_string(),
+        ];
+        if by_league {
+            row.push(p.leagues.clone());
+        }
+        row.extend(joined.iter().map(|name| optional(p.extras.get(name).map(|v| format!("{:.1}", v)))));
+        table.row(row);
+    }
+
+    table
+}
+
+// the export command
+pub fn show_export(out: &mut Output, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], top: usize, options: &Options) {
+    // columns joined on with --join go on the end of both
+    let joined = joined_columns(clean_records, aggregated_players);
+    out.table(&seasons_table(clean_records, &joined));
+    out.table(&careers_table(aggregated_players, &joined));
+
+    // a leaderboard per stat, season and career, through the same code as top
+    // (short titles, since they end up as worksheet names)
+    for stat in all_stats() {
+        for scope in [Scope::Season, Scope::Career] {
+            if scope == Scope::Career && !has_career_value(stat.name()) {
+                continue;
+            }
+
+            let args = LeaderArgs { stat: stat.name().to_string(), scope, min_ab: None, min_attempts: None, ascending: false, position: None, adjusted: false };
+            if let Ok(mut table) = leader_table(&args, false, top, clean_records, aggregated_players, options) {
+                let scope_name = if scope == Scope::Season { "Season" } else { "Career" };
+                table.title = format!("{} {}", scope_name, stat.name());
+                out.table(&table);
+            }
+        }
+    }
+}
@@ -0,0 +1,185 @@
+// team codes change when clubs move or rename (MON -> WSH, FLA -> MIA, CAL/ANA -> LAA), so every season
+// row also gets the code of the franchise it belongs to; career team lists, team filters and team records
+// go by franchise, and --raw-teams turns that off
+
+use std::collections::{BTreeMap, HashSet};
+
+use csv::ReaderBuilder;
+use serde::Deserialize;
+
+use crate::data::{CleanPlayerSeason, Symbols};
+use crate::error::{check_exists, Result};
+use crate::table::{Output, Table};
+
+// one code used by a franchise, over a span of seasons (codes get reused, e.g. MIL for the Braves and the Brewers)
+#[derive(Debug, Clone, Deserialize)]
+pub struct FranchiseCode {
+    pub team: String,
+    pub franchise: String,
+    pub first_season: Option<u32>,
+    pub last_season: Option<u32>,
+}
+
+// the moves and renames since 1901, mapped to the code each franchise uses now
+const BUILTIN: &[(&str, &str, u32, u32)] = &[
+    ("NYH", "NYY", 1903, 1912),
+    ("CHI", "CHC", 1876, 1902),
+    ("BSN", "ATL", 1876, 1952),
+    ("MIL", "ATL", 1953, 1965),
+    ("BRO", "LAD", 1884, 1957),
+    ("LA", "LAD", 1958, 2004),
+    ("NYG", "SF", 1883, 1957),
+    ("PHA", "OAK", 1901, 1954),
+    ("KCA", "OAK", 1955, 1967),
+    ("SLB", "BAL", 1902, 1953),
+    ("WAS", "MIN", 1901, 1960),
+    ("WAS", "TEX", 1961, 1971),
+    ("SEA", "MIL", 1969, 1969),
+    ("CAL", "LAA", 1966, 1996),
+    ("ANA", "LAA", 1997, 2004),
+    ("MON", "WSH", 1969, 2004),
+    ("FLA", "MIA", 1993, 2011),
+];
+
+pub struct FranchiseMap {
+    codes: Vec<FranchiseCode>,
+}
+
+impl FranchiseMap {
+    pub fn builtin() -> FranchiseMap {
+        let codes = BUILTIN
+            .iter()
+            .map(|&(team, franchise, first, last)| FranchiseCode {
+                team: team.to_string(),
+                franchise: franchise.to_string(),
+                first_season: Some(first),
+                last_season: Some(last),
+            })
+            .collect();
+
+        FranchiseMap { codes }
+    }
+
+    // add entries from a csv file (team,franchise,first_season,last_season; the seasons can be left blank)
+    // these are checked before the built-in ones, so they can correct or replace them
+    pub fn load_overrides(&mut self, file_path: &str) -> Result<usize> {
+        check_exists("franchise map", file_path)?;
+        let mut reader = ReaderBuilder::new().has_headers(true).trim(csv::Trim::All).from_path(file_path)?;
+
+        let mut overrides = Vec::new();
+        for result in reader.deserialize() {
+            let code: FranchiseCode = result?;
+            overrides.push(code);
+        }
+
+        let count = overrides.len();
+        overrides.append(&mut self.codes);
+        self.codes = overrides;
+        Ok(count)
+    }
+
+    // the franchise a team code belonged to in a season (the code itself if it never changed)
+    pub fn franchise_for(&self, team: &str, season: u32) -> String {
+        self.codes
+            .iter()
+            .find(|c| {
+                c.team.eq_ignore_ascii_case(team)
+                    && c.first_season.is_none_or(|first| season >= first)
+                    && c.last_season.is_none_or(|last| season <= last)
+            })
+            .map(|c| c.franchise.clone())
+            .unwrap_or_else(|| team.to_string())
+    }
+
+    // fill in the franchise on every row (sharing one copy of each code, like the loader does)
+    pub fn assign(&self, records: &mut [CleanPlayerSeason]) {
+        let mut symbols = Symbols::default();
+        for record in records {
+            let franchise = self.franchise_for(&record.team, record.season);
+            record.franchise = if franchise == *record.team { record.team.clone() } else { symbols.intern(&franchise) };
+        }
+    }
+}
+
+// what we add up for each franchise
+#[derive(Default)]
+struct FranchiseTotals<'a> {
+    codes: Vec<&'a str>,
+    first_season: u32,
+    last_season: u32,
+    players: HashSet<&'a str>,
+    hits: u32,
+    homeruns: u32,
+}
+
+// the franchises command: every franchise, the codes it has gone by, and its all-time totals
+pub fn show_franchises(out: &mut Output, clean_records: &[CleanPlayerSeason]) {
+    let mut by_franchise: BTreeMap<&str, FranchiseTotals> = BTreeMap::new();
+
+    for season in clean_records {
+        let totals = by_franchise.entry(season.franchise.as_ref()).or_insert_with(|| FranchiseTotals {
+            first_season: season.season,
+            last_season: season.season,
+            ..Default::default()
+        });
+
+        if !totals.codes.contains(&season.team.as_ref()) {
+            totals.codes.push(season.team.as_ref());
+        }
+        totals.first_season = totals.first_season.min(season.season);
+        totals.last_season = totals.last_season.max(season.season);
+        totals.players.insert(season.link.as_ref());
+        totals.hits += season.hits;
+        totals.homeruns += season.homeruns;
+    }
+
+    let mut table = Table::new("Franchises")
+        .column("Franchise", 9)
+        .column("Codes", 16)
+        .right("From", 6)
+        .right("To", 6)
+        .right("Players", 7)
+        .right("H", 7)
+        .right("HR", 6);
+
+    for (franchise, totals) in &by_franchise {
+        table.row(vec![
+            franchise.to_string(),
+            totals.codes.join(", "),
+            totals.first_season.to_string(),
+            totals.last_season.to_string(),
+            totals.players.len().to_string(),
+            totals.hits.to_string(),
+            totals.homeruns.to_string(),
+        ]);
+    }
+
+    out.table(&table);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::Scratch;
+
+    #[test]
+    fn old_codes_go_to_the_franchise_in_their_years() {
+        let map = FranchiseMap::builtin();
+        assert_eq!(map.franchise_for("MON", 2004), "WSH");
+        assert_eq!(map.franchise_for("mon", 1969), "WSH");
+        assert_eq!(map.franchise_for("FLA", 2011), "MIA");
+        // a code that never changed is its own franchise
+        assert_eq!(map.franchise_for("BOS", 1912), "BOS");
+    }
+
+    #[test]
+    fn overrides_come_before_the_built_in_codes() {
+        let scratch = Scratch::new("franchise_map");
+        let path = scratch.write("map.csv", "team,franchise,first_season,last_season\nMON,EXPOS,2000,2004\nBRO,LAD,,\n");
+        let mut map = FranchiseMap::builtin();
+        assert_eq!(map.load_overrides(&path).unwrap(), 2);
+        assert_eq!(map.franchise_for("MON", 2003), "EXPOS");
+        assert_eq!(map.franchise_for("MON", 1999), "WSH");
+        assert_eq!(map.franchise_for("BRO", 1955), "LAD");
+    }
+}
@@ -0,0 +1,128 @@
+// how a stat is spread across players: counts in bins of equal width, drawn as a row of #s per bin,
+// so a 73 home run season can be seen against the body of the league
+
+use crate::chart::BarChart;
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::leaders::SEASON_QUALIFYING_AT_BATS;
+use crate::options::Options;
+use crate::ranking::{rank_with_ties, SortOrder, TieBreak};
+use crate::schedule::prorated_note;
+use crate::stats::{format_stat_value, has_season_value, is_rate_stat, normalize_stat_name, season_value, unavailable_reason, unknown_stat};
+use crate::table::{Output, Table};
+
+const BAR_WIDTH: usize = 40;      // characters in the longest bar
+const TARGET_BINS: f64 = 20.0;    // roughly how many bins to aim for when no width is given
+
+// a round bin width giving about TARGET_BINS bins (1, 2, 5, 10, 20, 50, ... times a power of ten)
+fn default_bin_width(min: f64, max: f64, rate: bool) -> f64 {
+    let span = (max - min).max(if rate { 0.001 } else { 1.0 });
+    let rough = span / TARGET_BINS;
+    let magnitude = 10f64.powf(rough.log10().floor());
+
+    let width = [1.0, 2.0, 5.0, 10.0].iter().map(|step| step * magnitude).find(|&w| w >= rough).unwrap_or(10.0 * magnitude);
+
+    // counting stats come in whole numbers, so a bin is at least one wide
+    if rate { width } else { width.max(1.0).round() }
+}
+
+// the hist command
+pub fn show_histogram(out: &mut Output, stat: &str, season: Option<u32>, bin_width: Option<f64>, min_ab: Option<u32>, clean_records: &[CleanPlayerSeason], options: &Options) -> Result<()> {
+    let stat = normalize_stat_name(stat).ok_or_else(|| unknown_stat(stat))?;
+    if !has_season_value(stat) {
+        return Err(Error::Usage(format!("{} is only worked out for careers", stat)));
+    }
+    if let Some(reason) = unavailable_reason(stat, clean_records) {
+        return Err(Error::Data(format!("{} can't be shown: {}", stat, reason)));
+    }
+    if bin_width.is_some_and(|w| w <= 0.0) {
+        return Err(Error::Usage("--bin-width has to be more than 0".to_string()));
+    }
+
+    // rates need the usual qualifier, or a 1 for 1 season sits at 1.000; counting stats take everyone
+    let rate = is_rate_stat(stat);
+    let min_at_bats = min_ab.unwrap_or(if rate { SEASON_QUALIFYING_AT_BATS } else { 0 });
+    let season_min = |year: u32| if min_ab.is_some() { min_at_bats } else { options.prorated(min_at_bats, year) };
+
+    let value_of = |s: &CleanPlayerSeason| {
+        if season.is_some_and(|year| s.season != year) || s.at_bats < season_min(s.season) { None } else { season_value(stat, s) }
+    };
+    let values: Vec<f64> = clean_records.iter().filter_map(value_of).collect();
+
+    if values.is_empty() {
+        out.line("No seasons to show");
+        return Ok(());
+    }
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let width = bin_width.unwrap_or_else(|| default_bin_width(min, max, rate));
+
+    // bins start at a multiple of the width, so they read 0-4, 5-9, ... rather than starting at the minimum
+    // (the nudge stops .300 / .020 = 14.999... putting a .300 season in the .280 bin)
+    let bin_of = |value: f64| (value / width + 1e-9).floor() as i64;
+    let first_bin = bin_of(min);
+    let last_bin = bin_of(max);
+    let mut counts = vec![0usize; (last_bin - first_bin + 1) as usize];
+    for &value in &values {
+        counts[(bin_of(value) - first_bin) as usize] += 1;
+    }
+
+    let highest_count = counts.iter().copied().max().unwrap_or(1);
+
+    let scope = match season {
+        Some(year) => format!("in {}", year),
+        None => "in every season".to_string(),
+    };
+    let prorated_qualifier = if min_ab.is_none() { prorated_note(options.prorate) } else { "" };
+    let qualifier = if min_at_bats > 0 { format!(", min {} AB{}", min_at_bats, prorated_qualifier) } else { String::new() };
+
+    let mut table = Table::new(format!("{} {} ({} player seasons{})", stat, scope, values.len(), qualifier))
+        .column("From", 8)
+        .column("To", 8)
+        .right("Seasons", 7)
+        .column("", BAR_WIDTH);
+
+    let mut chart = BarChart::new(format!("{} {}, seasons per bin", stat, scope));
+
+    // a bin's upper end is just under the next bin's start: 5-9 for counting stats, .300-.309 for rates
+    let step = match stat {
+        "speed" => 0.1,
+        _ if rate => 0.001,
+        _ => 1.0,
+    };
+
+    for (i, &count) in counts.iter().enumerate() {
+        let from = (first_bin + i as i64) as f64 * width;
+        let to = from + width - step;
+
+        // anything at all gets a mark, so a single 70 home run season doesn't vanish
+        let mut bar_length = (count as f64 / highest_count as f64 * BAR_WIDTH as f64).round() as usize;
+        if count > 0 {
+            bar_length = bar_length.max(1);
+        }
+
+        table.row(vec![
+            format_stat_value(stat, from),
+            format_stat_value(stat, to.max(from)),
+            count.to_string(),
+            "#".repeat(bar_length),
+        ]);
+        chart.bar(format_stat_value(stat, from), count as f64);
+    }
+
+    out.table(&table);
+    out.chart(&chart);
+
+    // and who's out at the far end
+    let best = rank_with_ties(clean_records, 1, SortOrder::Descending, TieBreak::Shared, value_of);
+    if let Some(top) = best.first() {
+        let names: Vec<String> = best
+            .iter()
+            .map(|row| format!("{} {} ({}, {})", row.item.first_name.as_deref().unwrap_or(""), row.item.last_name, row.item.team, row.item.season))
+            .collect();
+        out.line("");
+        out.line(&format!("Highest: {} by {}", format_stat_value(stat, top.value), names.join(", ")));
+    }
+    Ok(())
+}
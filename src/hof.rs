@@ -0,0 +1,216 @@
+// Bill James' hall of fame yardsticks, as far as the data goes:
+//   - Black Ink: points for every season a player led the majors in a stat (without a league column in
+//     the data it's leading both leagues together, which is harder than the original; with one, it's
+//     leading his league, as the original counts it)
+//   - Hall of Fame Monitor: points for big seasons, for leading, and for career milestones, where
+//     about 100 makes a likely hall of famer (the parts for awards, post-season and fielding
+//     aren't in the data, so catchers and shortstops come out low)
+
+use std::collections::{BTreeMap, HashMap};
+
+use clap::ValueEnum;
+
+use crate::careers::{AggregatedPlayer, CareerBuilder};
+use crate::data::CleanPlayerSeason;
+use crate::leaders::CAREER_QUALIFYING_AT_BATS;
+use crate::options::Options;
+use crate::ranking::{rank_by, SortOrder};
+use crate::table::{Output, Table};
+
+// black ink points for leading the majors in each stat
+const BLACK_INK_POINTS: &[(&str, u32)] = &[
+    ("homeruns", 4),
+    ("rbi", 4),
+    ("batting_average", 4),
+    ("runs", 3),
+    ("hits", 3),
+    ("slugging_percentage", 3),
+    ("doubles", 2),
+    ("walks", 2),
+    ("stolen_bases", 2),
+    ("games", 1),
+    ("at_bats", 1),
+    ("triples", 1),
+];
+
+// monitor points for leading the majors
+const MONITOR_LEADER_POINTS: &[(&str, f64)] = &[
+    ("batting_average", 6.0),
+    ("homeruns", 4.0),
+    ("rbi", 4.0),
+    ("runs", 3.0),
+    ("hits", 2.0),
+    ("stolen_bases", 2.0),
+    ("doubles", 1.0),
+    ("triples", 1.0),
+];
+
+// which score the hof command ranks by
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum HofScore {
+    Monitor,
+    BlackInk,
+}
+
+#[derive(Default)]
+struct Scores {
+    monitor: f64,
+    black_ink: u32,
+}
+
+// monitor points for one season's line (a season split between teams is added up first)
+fn season_monitor_points(line: &AggregatedPlayer) -> f64 {
+    let mut points = 0.0;
+
+    // batting average only counts in a full season (100 games)
+    if line.total_games_played >= 100 && line.total_at_bats > 0 {
+        let average = line.total_hits as f64 / line.total_at_bats as f64;
+        points += if average >= 0.400 {
+            15.0
+        } else if average >= 0.350 {
+            5.0
+        } else if average >= 0.300 {
+            2.5
+        } else {
+            0.0
+        };
+    }
+
+    if line.total_hits >= 200 {
+        points += 5.0;
+    }
+    if line.total_rbi >= 100 {
+        points += 3.0;
+    }
+    if line.total_runs >= 100 {
+        points += 3.0;
+    }
+
+    // only the highest home run mark counts
+    points += match line.total_homeruns {
+        50.. => 10.0,
+        40..=49 => 4.0,
+        30..=39 => 2.0,
+        _ => 0.0,
+    };
+    points += match line.total_doubles {
+        45.. => 2.0,
+        35..=44 => 1.0,
+        _ => 0.0,
+    };
+
+    points
+}
+
+// monitor points for career totals
+fn career_monitor_points(player: &AggregatedPlayer) -> f64 {
+    let mut points = match player.total_hits {
+        3000.. => 40.0,
+        2500..=2999 => 8.0,
+        2000..=2499 => 4.0,
+        _ => 0.0,
+    };
+    points += match player.total_homeruns {
+        500.. => 20.0,
+        400..=499 => 10.0,
+        300..=399 => 3.0,
+        _ => 0.0,
+    };
+
+    // a career average only counts over a real career
+    if player.total_at_bats >= CAREER_QUALIFYING_AT_BATS {
+        let average = player.total_hits as f64 / player.total_at_bats as f64;
+        points += if average >= 0.330 {
+            24.0
+        } else if average >= 0.315 {
+            16.0
+        } else if average >= 0.300 {
+            8.0
+        } else {
+            0.0
+        };
+    }
+
+    points
+}
+
+// both scores for every player who has any points
+fn all_scores<'a>(clean_records: &'a [CleanPlayerSeason], aggregated_players: &'a [AggregatedPlayer], options: &Options) -> HashMap<&'a str, Scores> {
+    let mut scores: HashMap<&str, Scores> = HashMap::new();
+
+    // leading the majors, from what the loader already marked on each row (see leader_counts.rs)
+    for season in clean_records {
+        for &stat in &season.led {
+            if let Some(&(_, points)) = BLACK_INK_POINTS.iter().find(|(s, _)| *s == stat) {
+                scores.entry(season.link.as_ref()).or_default().black_ink += points;
+            }
+            if let Some(&(_, points)) = MONITOR_LEADER_POINTS.iter().find(|(s, _)| *s == stat) {
+                scores.entry(season.link.as_ref()).or_default().monitor += points;
+            }
+        }
+    }
+
+    // big seasons, with a season split between teams added up into one line
+    let mut player_seasons: BTreeMap<(&str, u32), Vec<&CleanPlayerSeason>> = BTreeMap::new();
+    for season in clean_records {
+        player_seasons.entry((season.link.as_ref(), season.season)).or_default().push(season);
+    }
+    let builder = CareerBuilder::new(clean_records, options);
+    for ((link, _), rows) in &player_seasons {
+        let points = season_monitor_points(&builder.career(link, rows));
+        if points > 0.0 {
+            scores.entry(link).or_default().monitor += points;
+        }
+    }
+
+    for player in aggregated_players {
+        let points = career_monitor_points(player);
+        if points > 0.0 {
+            scores.entry(player.link.as_str()).or_default().monitor += points;
+        }
+    }
+
+    scores
+}
+
+// the hof command
+pub fn show_hof(out: &mut Output, by: HofScore, top: usize, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], options: &Options) {
+    let scores = all_scores(clean_records, aggregated_players, options);
+
+    let ranked = rank_by(aggregated_players, top, SortOrder::Descending, options, |p| {
+        let score = scores.get(p.link.as_str())?;
+        let value = match by {
+            HofScore::Monitor => score.monitor,
+            HofScore::BlackInk => score.black_ink as f64,
+        };
+        (value > 0.0).then_some(value)
+    });
+
+    let title = match by {
+        HofScore::Monitor => format!("Top {} Hall of Fame Monitor scores (100 = likely hall of famer)", top),
+        HofScore::BlackInk => format!("Top {} Black Ink scores (points for leading the majors)", top),
+    };
+
+    let mut table = Table::new(title)
+        .column("Rank", 4)
+        .column("Player", 25)
+        .column("From", 6)
+        .column("To", 6)
+        .right("Monitor", 7)
+        .right("Black Ink", 9);
+
+    for row in &ranked {
+        let player = row.item;
+        let score = &scores[player.link.as_str()];
+        table.row(vec![
+            row.rank.to_string(),
+            format!("{} {}", player.first_name, player.last_name),
+            player.first_season.to_string(),
+            player.last_season.to_string(),
+            format!("{:.1}", score.monitor),
+            score.black_ink.to_string(),
+        ]);
+    }
+
+    out.table(&table);
+}
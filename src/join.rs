@@ -0,0 +1,244 @@
+// extra numeric columns from another file (WAR, fielding runs, whatever the season file doesn't have),
+// joined on the player's link, and on the season too unless the file has one row per player
+//
+// joined on link and season, a value goes on that player's season and adds up into his career like any
+// other stat (a season split between teams gets it on its first row, so it's only counted once)
+// joined on link alone, a value is a career number and goes straight on the career
+// either way the columns can then be ranked with top --stat, and show up on player pages and in export
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use csv::ReaderBuilder;
+use log::{info, warn};
+
+use crate::careers::AggregatedPlayer;
+use crate::data::{parse_optional_float, CleanPlayerSeason};
+use crate::error::{check_exists, Error, Result};
+use crate::stats::normalize_stat_name;
+
+// which columns rows are matched on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinKey {
+    Link,
+    LinkSeason,
+}
+
+impl JoinKey {
+    // from --join-key: "link,season" or "link"
+    pub fn parse(text: &str) -> Result<JoinKey> {
+        let parts: Vec<String> = text.split(',').map(|p| p.trim().to_lowercase()).collect();
+        match parts.iter().map(|p| p.as_str()).collect::<Vec<&str>>().as_slice() {
+            ["link"] => Ok(JoinKey::Link),
+            ["link", "season"] | ["season", "link"] => Ok(JoinKey::LinkSeason),
+            _ => Err(Error::Usage(format!("--join-key has to be link,season or link, not \"{}\"", text))),
+        }
+    }
+}
+
+// a loaded file of extra columns
+#[derive(Clone)]
+pub struct JoinFile {
+    key: JoinKey,
+    columns: Vec<String>,
+    rows: HashMap<(String, Option<u32>), Vec<Option<f64>>>,
+}
+
+impl JoinFile {
+    // read the file: the key columns, and every other column that's all numbers (or blank)
+    pub fn load(file_path: &str, key: JoinKey) -> Result<JoinFile> {
+        check_exists("join file", file_path)?;
+        let mut reader = ReaderBuilder::new().has_headers(true).trim(csv::Trim::All).from_path(file_path)?;
+        let headers: Vec<String> = reader.headers()?.iter().map(|h| h.to_lowercase()).collect();
+
+        let position = |name: &str| headers.iter().position(|h| h == name);
+        let link_column = position("link").ok_or_else(|| Error::Data(format!("{} has no link column", file_path)))?;
+        let season_column = match key {
+            JoinKey::LinkSeason => Some(position("season").ok_or_else(|| Error::Data(format!("{} has no season column (or use --join-key link)", file_path)))?),
+            JoinKey::Link => None,
+        };
+
+        let mut raw_rows = Vec::new();
+        for result in reader.records() {
+            raw_rows.push(result?);
+        }
+
+        // the value columns: not a key, not one of our own stats, and numbers all the way down
+        let mut value_columns = Vec::new();
+        for (i, name) in headers.iter().enumerate() {
+            if i == link_column || Some(i) == season_column {
+                continue;
+            }
+            if normalize_stat_name(name).is_some() {
+                warn!("{}: the {} column has the same name as one of our stats and was left out", file_path, name);
+                continue;
+            }
+            let numeric = raw_rows.iter().all(|row| {
+                let cell = row.get(i).unwrap_or("");
+                parse_optional_float(cell).is_some() || cell.is_empty() || cell == "--"
+            });
+            if numeric {
+                value_columns.push(i);
+            } else {
+                info!("{}: the {} column isn't numbers and was left out", file_path, name);
+            }
+        }
+
+        let mut rows = HashMap::new();
+        for row in &raw_rows {
+            let link = row.get(link_column).unwrap_or("").to_string();
+            let season = match season_column {
+                Some(column) => match row.get(column).and_then(|s| s.parse().ok()) {
+                    Some(season) => Some(season),
+                    None => continue,
+                },
+                None => None,
+            };
+            let values = value_columns.iter().map(|&i| parse_optional_float(row.get(i).unwrap_or(""))).collect();
+            rows.insert((link, season), values);
+        }
+
+        let columns = value_columns.iter().map(|&i| headers[i].clone()).collect();
+        Ok(JoinFile { key, columns, rows })
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    // put season values on the seasons (does nothing for a file joined on link alone)
+    pub fn apply_to_seasons(&self, records: &mut [CleanPlayerSeason]) {
+        if self.key != JoinKey::LinkSeason {
+            return;
+        }
+
+        let mut seen: HashSet<(String, u32)> = HashSet::new();
+        for record in records.iter_mut() {
+            let Some(values) = self.rows.get(&(record.link.to_string(), Some(record.season))) else {
+                continue;
+            };
+
+            // only the first row of a season split between teams gets it
+            if !seen.insert((record.link.to_string(), record.season)) {
+                continue;
+            }
+
+            for (column, value) in self.columns.iter().zip(values) {
+                if let Some(value) = value {
+                    record.extras.insert(column.clone(), *value);
+                }
+            }
+        }
+
+        self.warn_unmatched(seen.len());
+    }
+
+    // put career values on the careers (does nothing for a file joined on link and season)
+    pub fn apply_to_players(&self, players: &mut [AggregatedPlayer]) {
+        if self.key != JoinKey::Link {
+            return;
+        }
+
+        let mut matched = 0;
+        for player in players.iter_mut() {
+            let Some(values) = self.rows.get(&(player.link.clone(), None)) else {
+                continue;
+            };
+            matched += 1;
+
+            for (column, value) in self.columns.iter().zip(values) {
+                if let Some(value) = value {
+                    player.extras.insert(column.clone(), *value);
+                }
+            }
+        }
+
+        self.warn_unmatched(matched);
+    }
+
+    fn warn_unmatched(&self, matched: usize) {
+        let unmatched = self.rows.len() - matched;
+        if unmatched > 0 {
+            warn!("{} joined rows don't match any player in the data", unmatched);
+        }
+    }
+}
+
+// every joined column name, in order, across the seasons and careers
+pub fn joined_columns(clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer]) -> Vec<String> {
+    let mut columns: BTreeSet<&str> = BTreeSet::new();
+    for extras in clean_records.iter().map(|s| &s.extras).chain(aggregated_players.iter().map(|p| &p.extras)) {
+        columns.extend(extras.keys().map(|name| name.as_str()));
+    }
+    columns.into_iter().map(|name| name.to_string()).collect()
+}
+
+// a joined column's name as the data has it, from what the user typed (any case)
+pub fn joined_stat_name(name: &str, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer]) -> Option<String> {
+    let name = name.trim().to_lowercase();
+    joined_columns(clean_records, aggregated_players).into_iter().find(|column| *column == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::careers::aggregate_players;
+    use crate::data::seasons_from_csv;
+    use crate::options::Options;
+    use crate::scratch::Scratch;
+
+    // Henderson's 1989, split between the Yankees and the A's
+    const SEASONS: &str = "\
+1989,Rickey,Henderson,/player/116435,LF,NYY,65,235,41,58,13,1,3,22,56,29,25,8,.247,.392,.349,.741
+1989,Rickey,Henderson,/player/116435,LF,OAK,85,306,72,90,13,2,9,35,70,39,52,6,.294,.425,.438,.863
+1990,Rickey,Henderson,/player/116435,LF,OAK,136,489,119,159,33,3,28,61,97,60,65,10,.325,.439,.577,1.016
+";
+
+    #[test]
+    fn join_key_takes_link_and_season_either_way_round() {
+        assert_eq!(JoinKey::parse("link,season").unwrap(), JoinKey::LinkSeason);
+        assert_eq!(JoinKey::parse(" Season , Link ").unwrap(), JoinKey::LinkSeason);
+        assert_eq!(JoinKey::parse("link").unwrap(), JoinKey::Link);
+        assert!(matches!(JoinKey::parse("season"), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn a_season_value_goes_on_the_first_row_once() {
+        let scratch = Scratch::new("join_seasons");
+        // hr is one of our stats and team isn't a number, so neither is joined
+        let path = scratch.write("war.csv", "link,season,WAR,hr,team\n/player/116435,1989,8.1,99,NYY\n/player/116435,1990,9.9,99,OAK\n");
+        let join_file = JoinFile::load(&path, JoinKey::LinkSeason).unwrap();
+        assert_eq!(join_file.columns(), ["war"]);
+
+        let mut records = seasons_from_csv(SEASONS);
+        join_file.apply_to_seasons(&mut records);
+        let war: Vec<Option<f64>> = records.iter().map(|s| s.extras.get("war").copied()).collect();
+        assert_eq!(war, vec![Some(8.1), None, Some(9.9)]);
+
+        // and adds up into the career
+        let careers = aggregate_players(&records, &Options::default());
+        assert!((careers[0].extras["war"] - 18.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_link_value_goes_on_the_career() {
+        let scratch = Scratch::new("join_careers");
+        let path = scratch.write("hof.csv", "link,hof_votes\n/player/116435,505\n/player/nobody,1\n");
+        let join_file = JoinFile::load(&path, JoinKey::Link).unwrap();
+
+        let mut records = seasons_from_csv(SEASONS);
+        join_file.apply_to_seasons(&mut records);
+        assert!(records.iter().all(|s| s.extras.is_empty()));
+
+        let mut careers = aggregate_players(&records, &Options::default());
+        join_file.apply_to_players(&mut careers);
+        assert_eq!(careers[0].extras.get("hof_votes"), Some(&505.0));
+        assert_eq!(joined_stat_name("HOF_Votes", &records, &careers), Some("hof_votes".to_string()));
+    }
+
+    #[test]
+    fn a_season_join_needs_a_season_column() {
+        let scratch = Scratch::new("join_no_season");
+        let path = scratch.write("war.csv", "link,war\n/player/116435,100\n");
+        assert!(matches!(JoinFile::load(&path, JoinKey::LinkSeason), Err(Error::Data(_))));
+    }
+}
@@ -0,0 +1,113 @@
+// leading the majors: which stats each season row led in, worked out once when the rows are loaded
+// (the same leaders as the almanac's season by season lists) and kept on the rows, so the player page,
+// the times_led stat and the hall of fame scores all read them instead of finding the leaders again
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::data::{league_season, CleanPlayerSeason, LeagueSeason};
+use crate::leaders::SEASON_QUALIFYING_AT_BATS;
+use crate::options::Options;
+use crate::ranking::{rank_with_ties, SortOrder, TieBreak};
+use crate::stats::{higher_is_better, is_rate_stat, season_value};
+
+// the stats leading counts for, with the short name the player page uses (the black ink categories)
+pub const LEADER_STATS: &[(&str, &str)] = &[
+    ("homeruns", "HR"),
+    ("rbi", "RBI"),
+    ("batting_average", "AVG"),
+    ("runs", "R"),
+    ("hits", "H"),
+    ("slugging_percentage", "SLG"),
+    ("doubles", "2B"),
+    ("walks", "BB"),
+    ("stolen_bases", "SB"),
+    ("games", "G"),
+    ("at_bats", "AB"),
+    ("triples", "3B"),
+];
+
+// the almanac's idea of a leader: the most of a counting stat, the best of a rate
+pub fn leader_order(stat: &str) -> SortOrder {
+    if is_rate_stat(stat) {
+        SortOrder::for_stat(higher_is_better(stat), false)
+    } else {
+        SortOrder::Descending
+    }
+}
+
+// a season's value, leaving out seasons too short to count for a rate
+// (and zeros, so a team that never hit a home run doesn't get a dozen players tied on a record of 0)
+pub fn qualified_season_value(stat: &str, season: &CleanPlayerSeason, options: &Options) -> Option<f64> {
+    if is_rate_stat(stat) && season.at_bats < options.prorated(SEASON_QUALIFYING_AT_BATS, season.season) {
+        return None;
+    }
+    season_value(stat, season).filter(|&value| value > 0.0)
+}
+
+// one season's leader in a stat (more than one when they tied)
+pub struct SeasonLeader<'a> {
+    pub season: u32,
+    pub league: Option<Arc<str>>,  // with a league column each league has its own leader
+    pub value: f64,
+    pub players: Vec<&'a CleanPlayerSeason>,
+}
+
+// who led the majors in a stat, season by season, the way the almanac counts it
+// (the almanac's season by season lists and the quiz use it as it is; assign_leaders below keeps it on the rows)
+// data with a league column gets a leader for each league every season, the way the leagues count it
+pub fn season_leaders_for<'a>(stat: &str, clean_records: &'a [CleanPlayerSeason], options: &Options) -> Vec<SeasonLeader<'a>> {
+    leader_rows(stat, clean_records, options)
+        .into_iter()
+        .map(|((season, league), value, rows)| SeasonLeader { season, league, value, players: rows.into_iter().map(|i| &clean_records[i]).collect() })
+        .collect()
+}
+
+// the same, with the leaders as their places in the records
+fn leader_rows(stat: &str, clean_records: &[CleanPlayerSeason], options: &Options) -> Vec<(LeagueSeason, f64, Vec<usize>)> {
+    let mut by_season: BTreeMap<LeagueSeason, Vec<(usize, &CleanPlayerSeason)>> = BTreeMap::new();
+    for (index, season) in clean_records.iter().enumerate() {
+        by_season.entry(league_season(season)).or_default().push((index, season));
+    }
+
+    by_season
+        .into_iter()
+        .filter_map(|(league_season, seasons)| {
+            // co-leaders all led, whatever --ties says
+            let leaders = rank_with_ties(&seasons, 1, leader_order(stat), TieBreak::Shared, |&(_, s)| qualified_season_value(stat, s, options));
+            // nobody qualified (or nobody has the stat recorded) that year
+            let value = leaders.first()?.value;
+            Some((league_season, value, leaders.iter().map(|row| row.item.0).collect()))
+        })
+        .collect()
+}
+
+// fill in what every row led the majors in (co-leaders all led), or its league with a league column
+// the loader does this once a file's rows are in, so a supplementary league's leaders are its own
+pub fn assign_leaders(records: &mut [CleanPlayerSeason], options: &Options) {
+    let mut led: HashMap<usize, Vec<&'static str>> = HashMap::new();
+    for &(stat, _) in LEADER_STATS {
+        for (_, _, rows) in leader_rows(stat, records, options) {
+            for index in rows {
+                led.entry(index).or_default().push(stat);
+            }
+        }
+    }
+
+    for (index, stats) in led {
+        records[index].led = stats;
+    }
+}
+
+// "HR 4x, RBI 2x, AVG" for the stats in a count, in LEADER_STATS order
+pub fn describe_times_led(times_led: &BTreeMap<&'static str, u32>) -> String {
+    LEADER_STATS
+        .iter()
+        .filter_map(|(stat, short)| match times_led.get(stat) {
+            Some(1) => Some(short.to_string()),
+            Some(&count) => Some(format!("{} {}x", short, count)),
+            None => None,
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,159 @@
+// the original fixed set of leaderboards (home runs, single seasons and careers)
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::leaders::CAREER_QUALIFYING_AT_BATS;
+use crate::options::Options;
+use crate::ranking::{rank_by, RankedRow, SortOrder};
+use crate::stats::format_rate;
+use crate::table::{truncate, Output, Table};
+
+// a leaderboard of single seasons
+fn season_table(title: String, stat_header: &str, ranked: &[RankedRow<CleanPlayerSeason>], stat: impl Fn(&CleanPlayerSeason) -> u32) -> Table {
+    let mut table = Table::new(title)
+        .column("Rank", 4)
+        .column("First Name", 15)
+        .column("Last Name", 15)
+        .column("Team", 6)
+        .column("Season", 8)
+        .column(stat_header, 5);
+
+    for row in ranked {
+        let player = row.item;
+        table.row(vec![
+            row.rank.to_string(),
+            player.first_name.as_deref().unwrap_or("N/A").to_string(),
+            player.last_name.clone(),
+            player.team.to_string(),
+            player.season.to_string(),
+            stat(player).to_string(),
+        ]);
+    }
+
+    table
+}
+
+// a leaderboard of careers, with the teams they played for
+fn career_table(title: String, stat_header: &str, ranked: &[RankedRow<AggregatedPlayer>], stat: impl Fn(&AggregatedPlayer) -> u32) -> Table {
+    let mut table = Table::new(title)
+        .column("Rank", 4)
+        .column("First Name", 15)
+        .column("Last Name", 15)
+        .column("Teams", 20)
+        .column("From", 6)
+        .column("To", 6)
+        .column(stat_header, 5);
+
+    for row in ranked {
+        let player = row.item;
+        table.row(vec![
+            row.rank.to_string(),
+            player.first_name.clone(),
+            player.last_name.clone(),
+            format!("{} ({})", truncate(&player.teams, 16), player.team_count),
+            player.first_season.to_string(),
+            player.last_season.to_string(),
+            stat(player).to_string(),
+        ]);
+    }
+
+    table
+}
+
+// home run records, single season and career
+pub fn show_homeruns(out: &mut Output, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], top: usize, options: &Options) {
+    // rank players by home runs (highest first), keeping the top n plus anyone tied with the last one
+    let top_homeruns = rank_by(clean_records, top, SortOrder::Descending, options, |p| Some(p.homeruns as f64));
+    out.table(&season_table(format!("Top {} home runs in a season", top), "HR", &top_homeruns, |p| p.homeruns));
+
+    // same again for careers, also showing how many seasons it took
+    let top_career_homeruns = rank_by(aggregated_players, top, SortOrder::Descending, options, |p| Some(p.total_homeruns as f64));
+
+    let mut table = Table::new(format!("Top {} homeruns in a career", top))
+        .column("Rank", 4)
+        .column("First Name", 15)
+        .column("Last Name", 15)
+        .column("From", 6)
+        .column("To", 6)
+        .column("Total", 6)
+        .column("Home runs", 9);
+
+    for row in &top_career_homeruns {
+        let player = row.item;
+        table.row(vec![
+            row.rank.to_string(),
+            player.first_name.clone(),
+            player.last_name.clone(),
+            player.first_season.to_string(),
+            player.last_season.to_string(),
+            player.seasons_played.to_string(),
+            player.total_homeruns.to_string(),
+        ]);
+    }
+
+    out.table(&table);
+}
+
+// single season records
+pub fn show_seasons(out: &mut Output, clean_records: &[CleanPlayerSeason], top: usize, options: &Options) {
+    // hits
+    let top_hits = rank_by(clean_records, top, SortOrder::Descending, options, |p| Some(p.hits as f64));
+    out.table(&season_table(format!("Top {} most hits in a season", top), "Hits", &top_hits, |p| p.hits));
+
+    // homeruns
+    let top_homeruns = rank_by(clean_records, top, SortOrder::Descending, options, |p| Some(p.homeruns as f64));
+    out.table(&season_table(format!("Top {} most homeruns in a season", top), "HRs", &top_homeruns, |p| p.homeruns));
+
+    // walks
+    let top_walks = rank_by(clean_records, top, SortOrder::Descending, options, |p| Some(p.walks as f64));
+    out.table(&season_table(format!("Top {} most walks in a season", top), "Walks", &top_walks, |p| p.walks));
+}
+
+// career records
+pub fn show_careers(out: &mut Output, aggregated_players: &[AggregatedPlayer], top: usize, options: &Options) {
+    // games played
+    let top_career_games = rank_by(aggregated_players, top, SortOrder::Descending, options, |p| Some(p.total_games_played as f64));
+    out.table(&career_table(format!("Top {} games played in a career", top), "Games", &top_career_games, |p| p.total_games_played));
+
+    // hits
+    let top_career_hits = rank_by(aggregated_players, top, SortOrder::Descending, options, |p| Some(p.total_hits as f64));
+    out.table(&career_table(format!("Top {} most hits in a career", top), "Hits", &top_career_hits, |p| p.total_hits));
+
+    // homeruns
+    let top_career_homeruns = rank_by(aggregated_players, top, SortOrder::Descending, options, |p| Some(p.total_homeruns as f64));
+    out.table(&career_table(format!("Top {} most homeruns in a career", top), "HRs", &top_career_homeruns, |p| p.total_homeruns));
+
+    // the slash line, by OPS, for careers long enough to mean something
+    let top_career_ops = rank_by(aggregated_players, top, SortOrder::Descending, options, |p| {
+        if p.total_at_bats < CAREER_QUALIFYING_AT_BATS { None } else { p.ops }
+    });
+    let mut table = Table::new(format!("Top {} career OPS (min {} AB)", top, CAREER_QUALIFYING_AT_BATS))
+        .column("Rank", 4)
+        .column("First Name", 15)
+        .column("Last Name", 15)
+        .column("From", 6)
+        .column("To", 6)
+        .right("AB", 6)
+        .right("AVG", 5)
+        .right("OBP", 5)
+        .right("SLG", 5)
+        .right("OPS", 5);
+
+    let rate = |value: Option<f64>| value.map(format_rate).unwrap_or_else(|| "--".to_string());
+    for row in &top_career_ops {
+        let player = row.item;
+        table.row(vec![
+            row.rank.to_string(),
+            player.first_name.clone(),
+            player.last_name.clone(),
+            player.first_season.to_string(),
+            player.last_season.to_string(),
+            player.total_at_bats.to_string(),
+            rate(player.batting_average),
+            rate(player.on_base_percentage),
+            rate(player.slugging_percentage),
+            rate(player.ops),
+        ]);
+    }
+    out.table(&table);
+}
@@ -0,0 +1,312 @@
+// the generic leaderboard: any stat, for single seasons or whole careers, best or worst
+
+use std::collections::HashMap;
+
+use clap::{Args, ValueEnum};
+
+use crate::adjusted::{can_adjust, LeagueBaselines};
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::join::joined_stat_name;
+use crate::options::Options;
+use crate::positions::position_matches;
+use crate::ranking::{rank_by, SortOrder};
+use crate::schedule::prorated_note;
+use crate::stats::{career_value, has_career_value, has_season_value, format_stat_value, higher_is_better, is_rate_stat, normalize_stat_name, season_value, unavailable_reason, unknown_stat};
+use crate::table::{Output, Table};
+
+// we don't have plate appearances, so the qualifiers are in at-bats
+pub const SEASON_QUALIFYING_AT_BATS: u32 = 502;   // 3.1 per game over a 162 game season
+pub const CAREER_QUALIFYING_AT_BATS: u32 = 3000;
+pub const HALF_QUALIFYING_AT_BATS: u32 = 251;     // half a season's worth, for first/second half splits
+
+// stolen base attempts (SB + CS), for the stats built on them, so two steals in two tries isn't a leader
+pub const SEASON_QUALIFYING_ATTEMPTS: u32 = 10;
+pub const CAREER_QUALIFYING_ATTEMPTS: u32 = 50;
+
+// rank single seasons or whole careers, or only each player's first or last season
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Scope {
+    Season,
+    Career,
+    Rookie,
+    Final,
+}
+
+// the options shared by top and bottom
+#[derive(Debug, Clone, Args)]
+pub struct LeaderArgs {
+    /// the stat to rank by, e.g. homeruns, hr, avg, ops (--help lists them all)
+    #[arg(long)]
+    pub stat: String,
+
+    /// rank single seasons or whole careers, or only rookie (first) or final seasons
+    #[arg(long, value_enum, default_value_t = Scope::Season)]
+    pub scope: Scope,
+
+    /// minimum at-bats to qualify (defaults to 502 a season / 3000 a career for rate stats and lowest-first lists)
+    #[arg(long)]
+    pub min_ab: Option<u32>,
+
+    /// minimum stolen base attempts (SB + CS) to qualify (defaults to 10 a season / 50 a career for baserunning_runs)
+    #[arg(long)]
+    pub min_attempts: Option<u32>,
+
+    /// list the lowest values first, whichever end is better for the stat
+    #[arg(long)]
+    pub ascending: bool,
+
+    /// only players at this position: their primary position for careers, that season's for seasons (OF takes in LF, CF and RF)
+    #[arg(long, value_name = "POS")]
+    pub position: Option<String>,
+
+    /// rank against each season's league average instead (an index, 100 = average; counting stats per at bat)
+    #[arg(long)]
+    pub adjusted: bool,
+}
+
+// the options for the shortcut commands (hr, avg, ...), which fill in --stat themselves
+#[derive(Debug, Clone, Args)]
+pub struct ShortcutArgs {
+    /// rank single seasons or whole careers, or only rookie (first) or final seasons
+    #[arg(long, value_enum, default_value_t = Scope::Season)]
+    pub scope: Scope,
+
+    /// minimum at-bats to qualify (defaults to 502 a season / 3000 a career for rate stats and lowest-first lists)
+    #[arg(long)]
+    pub min_ab: Option<u32>,
+
+    /// list the lowest values first, whichever end is better for the stat
+    #[arg(long)]
+    pub ascending: bool,
+
+    /// only players at this position: their primary position for careers, that season's for seasons (OF takes in LF, CF and RF)
+    #[arg(long, value_name = "POS")]
+    pub position: Option<String>,
+
+    /// rank against each season's league average instead (an index, 100 = average; counting stats per at bat)
+    #[arg(long)]
+    pub adjusted: bool,
+}
+
+impl ShortcutArgs {
+    // the full set of leaderboard options for a given stat
+    pub fn for_stat(&self, stat: &str) -> LeaderArgs {
+        LeaderArgs {
+            stat: stat.to_string(),
+            scope: self.scope,
+            min_ab: self.min_ab,
+            min_attempts: None,
+            ascending: self.ascending,
+            position: self.position.clone(),
+            adjusted: self.adjusted,
+        }
+    }
+}
+
+// the stolen base attempts a stat needs without --min-attempts (only the ones built on steal attempts need any)
+pub fn default_min_attempts(stat: &str, scope: Scope) -> u32 {
+    match (stat, scope) {
+        ("baserunning_runs", Scope::Career) => CAREER_QUALIFYING_ATTEMPTS,
+        ("baserunning_runs", _) => SEASON_QUALIFYING_ATTEMPTS,
+        _ => 0,
+    }
+}
+
+// build a leaderboard: best values first, or worst values first for `bottom`
+// (an unknown stat or a scope it can't be ranked in is a usage error, a stat the data can't give a data error)
+pub fn leader_table(args: &LeaderArgs, worst: bool, top: usize, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], options: &Options) -> Result<Table> {
+    // one of our stats, or a column joined on from another file
+    let joined_name;
+    let stat: &str = match normalize_stat_name(&args.stat) {
+        Some(stat) => {
+            if let Some(reason) = unavailable_reason(stat, clean_records) {
+                return Err(Error::Data(format!("{} can't be ranked: {}", stat, reason)));
+            }
+            stat
+        }
+        None => {
+            joined_name = joined_stat_name(&args.stat, clean_records, aggregated_players).ok_or_else(|| unknown_stat(&args.stat))?;
+            &joined_name
+        }
+    };
+
+    // which way round, and which end is "worst", both depend on the stat
+    let order = if args.ascending {
+        SortOrder::Ascending
+    } else {
+        SortOrder::for_stat(higher_is_better(stat), worst)
+    };
+
+    // rates and lowest-first lists are meaningless without a minimum playing time
+    let default_min = if is_rate_stat(stat) || args.adjusted || order == SortOrder::Ascending {
+        match args.scope {
+            Scope::Career => CAREER_QUALIFYING_AT_BATS,
+            _ => SEASON_QUALIFYING_AT_BATS,
+        }
+    } else {
+        0
+    };
+    let min_at_bats = args.min_ab.unwrap_or(default_min);
+
+    // a season's own minimum: the default shrinks with a short season when --prorate-short-seasons is on
+    // (a --min-ab is taken as it is)
+    let season_min = |season: u32| if args.min_ab.is_some() { min_at_bats } else { options.prorated(min_at_bats, season) };
+    let prorated_qualifier = if args.min_ab.is_none() && args.scope != Scope::Career { prorated_note(options.prorate) } else { "" };
+
+    // and the same for steal attempts
+    let min_attempts = args.min_attempts.unwrap_or_else(|| default_min_attempts(stat, args.scope));
+    let season_min_attempts = |season: u32| if args.min_attempts.is_some() { min_attempts } else { options.prorated(min_attempts, season) };
+
+    // with --adjusted every value is an index against its season's league average, worked out here
+    let baselines = if args.adjusted {
+        if !can_adjust(stat) {
+            return Err(Error::Usage(format!("{} can't be adjusted for era", stat)));
+        }
+        Some(LeagueBaselines::build(stat, clean_records))
+    } else {
+        None
+    };
+
+    let label = if worst { "Bottom" } else { "Top" };
+
+    // note the qualifier and direction in the title so nobody misreads the list
+    let mut notes = Vec::new();
+    if min_at_bats > 0 {
+        notes.push(format!("min {} AB{}", min_at_bats, prorated_qualifier));
+    }
+    if min_attempts > 0 {
+        notes.push(format!("min {} SB attempts", min_attempts));
+    }
+    if let Some(position) = &args.position {
+        notes.push(format!("{} only", position.to_uppercase()));
+    }
+    if args.adjusted {
+        notes.push("era adjusted, 100 = league average".to_string());
+    }
+    if order == SortOrder::Ascending {
+        notes.push("lowest first".to_string());
+    }
+    let qualifier = if notes.is_empty() { String::new() } else { format!(" ({})", notes.join(", ")) };
+
+    let at_position = |position: &str| args.position.as_deref().is_none_or(|wanted| position_matches(wanted, position));
+
+    let stat_header = if args.adjusted { format!("{}+", stat) } else { stat.to_string() };
+    let stat_width = stat_header.len().max(8);
+    let show_value = |value: f64| if args.adjusted { format!("{:.0}", value) } else { format_stat_value(stat, value) };
+
+    match args.scope {
+        Scope::Season | Scope::Rookie | Scope::Final => {
+            if !has_season_value(stat) {
+                return Err(Error::Usage(format!("{} is only worked out for careers (try --scope career)", stat)));
+            }
+
+            // rookie and final seasons are each player's first and last in the data, leaving out anyone who
+            // was already playing when the data starts or still is when it ends (those aren't really either)
+            let (data_first, data_last) = (
+                clean_records.iter().map(|s| s.season).min().unwrap_or(0),
+                clean_records.iter().map(|s| s.season).max().unwrap_or(0),
+            );
+            let career_ends: HashMap<&str, (u32, u32)> = aggregated_players
+                .iter()
+                .map(|p| (p.link.as_str(), (p.first_season, p.last_season)))
+                .collect();
+            let in_scope = |s: &CleanPlayerSeason| match (args.scope, career_ends.get(s.link.as_ref())) {
+                (Scope::Rookie, Some(&(first, _))) => s.season == first && first != data_first,
+                (Scope::Final, Some(&(_, last))) => s.season == last && last != data_last,
+                (Scope::Rookie | Scope::Final, None) => false,
+                _ => true,
+            };
+
+            let ranked = rank_by(clean_records, top, order, options, |s| {
+                let attempts = s.stolen_bases.unwrap_or(0) + s.caught_stealing.unwrap_or(0);
+                if s.at_bats < season_min(s.season) || attempts < season_min_attempts(s.season) || !at_position(&s.position) || !in_scope(s) {
+                    return None;
+                }
+                match &baselines {
+                    Some(baselines) => baselines.season_index(s),
+                    None => season_value(stat, s),
+                }
+            });
+
+            let season_kind = match args.scope {
+                Scope::Rookie => "rookie season",
+                Scope::Final => "final season",
+                _ => "season",
+            };
+            let mut table = Table::new(format!("{} {} {} in a {}{}", label, top, stat, season_kind, qualifier))
+                .column("Rank", 4)
+                .column("First Name", 15)
+                .column("Last Name", 15)
+                .column("Team", 6)
+                .column("Season", 8)
+                .right("AB", 5)
+                .right(&stat_header, stat_width);
+
+            for row in &ranked {
+                let player = row.item;
+                table.row(vec![
+                    row.rank.to_string(),
+                    player.first_name.as_deref().unwrap_or("N/A").to_string(),
+                    player.last_name.clone(),
+                    player.team.to_string(),
+                    player.season.to_string(),
+                    player.at_bats.to_string(),
+                    show_value(row.value),
+                ]);
+            }
+
+            Ok(table)
+        }
+        Scope::Career => {
+            // adjusted careers come from their seasons, so they work for the rates too
+            if !has_career_value(stat) && baselines.is_none() {
+                let hint = if can_adjust(stat) { " (try --adjusted)" } else { "" };
+                return Err(Error::Usage(format!("{} isn't available for careers yet{}", stat, hint)));
+            }
+            let career_indexes = baselines.as_ref().map(|b| b.career_indexes(clean_records));
+
+            let ranked = rank_by(aggregated_players, top, order, options, |p| {
+                let attempts = p.total_stolen_bases + p.total_caught_stealing;
+                if p.total_at_bats < min_at_bats || attempts < min_attempts || !at_position(&p.primary_position) {
+                    return None;
+                }
+                match &career_indexes {
+                    Some(indexes) => indexes.get(&p.link).copied(),
+                    None => career_value(stat, p),
+                }
+            });
+
+            let mut table = Table::new(format!("{} {} {} in a career{}", label, top, stat, qualifier))
+                .column("Rank", 4)
+                .column("First Name", 15)
+                .column("Last Name", 15)
+                .column("From", 6)
+                .column("To", 6)
+                .right("AB", 6)
+                .right(&stat_header, stat_width);
+
+            for row in &ranked {
+                let player = row.item;
+                table.row(vec![
+                    row.rank.to_string(),
+                    player.first_name.clone(),
+                    player.last_name.clone(),
+                    player.first_season.to_string(),
+                    player.last_season.to_string(),
+                    player.total_at_bats.to_string(),
+                    show_value(row.value),
+                ]);
+            }
+
+            Ok(table)
+        }
+    }
+}
+
+// print a leaderboard (the top and bottom commands, and the shortcuts)
+pub fn show_leaders(out: &mut Output, args: &LeaderArgs, worst: bool, top: usize, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], options: &Options) -> Result<()> {
+    out.table(&leader_table(args, worst, top, clean_records, aggregated_players, options)?);
+    Ok(())
+}
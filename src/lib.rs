@@ -0,0 +1,88 @@
+//! Baseball statistics from a csv of player seasons: loading and cleaning the rows, rolling them up
+//! into careers, ranking any stat, and writing the results as text, csv, json, markdown, html or xlsx.
+//!
+//! The `baseball-stats` command line tool is built on this, and the same pieces can be used from
+//! other programs. The examples folder has complete ones (`cargo run --example custom_stat`).
+//!
+//! ```no_run
+//! use rust_baseball_stats::careers::aggregate_players;
+//! use rust_baseball_stats::data::load_seasons;
+//! use rust_baseball_stats::leaders::{leader_table, LeaderArgs, Scope};
+//! use rust_baseball_stats::options::Options;
+//! use rust_baseball_stats::table::{Format, Output};
+//!
+//! // the tool's defaults: shared ties, missing counts as zero, no pro-rating for short seasons
+//! let options = Options::default();
+//! let seasons = load_seasons("mlb_season_data.csv")?;
+//! let careers = aggregate_players(&seasons, &options);
+//!
+//! // the ten best career home run totals, the same list as `baseball-stats hr --scope career`
+//! let args = LeaderArgs {
+//!     stat: "homeruns".to_string(),
+//!     scope: Scope::Career,
+//!     min_ab: None,
+//!     min_attempts: None,
+//!     ascending: false,
+//!     position: None,
+//!     adjusted: false,
+//! };
+//! let table = leader_table(&args, false, 10, &seasons, &careers, &options)?;
+//!
+//! let mut out = Output::stdout(Format::Text, false);
+//! out.table(&table);
+//! out.finish()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub mod adjusted;
+pub mod aging;
+pub mod asof;
+pub mod batch;
+pub mod career_export;
+pub mod careers;
+pub mod chart;
+pub mod data;
+pub mod diff;
+pub mod error;
+pub mod export;
+pub mod franchises;
+pub mod hist;
+pub mod hof;
+pub mod join;
+pub mod leader_counts;
+pub mod leaderboards;
+pub mod leaders;
+pub mod logging;
+pub mod lookup;
+pub mod matrix;
+pub mod missing;
+pub mod options;
+pub mod percentile;
+pub mod player;
+pub mod positions;
+pub mod progress;
+pub mod quiz;
+pub mod random;
+pub mod ranking;
+pub mod report;
+pub mod roster;
+pub mod schedule;
+pub mod schema;
+pub mod search;
+pub mod splits;
+pub mod stats;
+pub mod summary;
+pub mod table;
+pub mod teams;
+pub mod timeline;
+pub mod tto;
+pub mod usage;
+pub mod verify;
+pub mod versus;
+pub mod watch;
+pub mod xlsx;
+
+// the scratch folder the integration tests write their files to, for the unit tests too
+#[cfg(test)]
+#[path = "../tests/common/mod.rs"]
+mod scratch;
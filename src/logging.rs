@@ -0,0 +1,52 @@
+// the "Loading baseball data..." chatter goes through the log crate rather than println!,
+// so anything embedding the loading code can turn it off or send it somewhere else
+// this is the plain logger the command line tool uses: messages as-is, on stderr
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+struct PlainLogger;
+
+impl Log for PlainLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        // keep the everyday messages looking the way they always have, and label the rest
+        match record.level() {
+            Level::Info => eprintln!("{}", record.args()),
+            Level::Error => eprintln!("Error: {}", record.args()),
+            Level::Warn => eprintln!("Warning: {}", record.args()),
+            Level::Debug | Level::Trace => eprintln!("[{}] {}", record.level().as_str().to_lowercase(), record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: PlainLogger = PlainLogger;
+
+// turn -v/-q counts into a log level: the default shows the usual progress messages,
+// -q hides them (warnings and errors still show), -qq hides everything but errors,
+// -v adds debug detail and -vv adds everything
+pub fn level_for(verbose: u8, quiet: u8) -> LevelFilter {
+    match (verbose as i8) - (quiet as i8) {
+        i8::MIN..=-3 => LevelFilter::Off,
+        -2 => LevelFilter::Error,
+        -1 => LevelFilter::Warn,
+        0 => LevelFilter::Info,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+// install the logger (only the command line tool calls this)
+pub fn init(level: LevelFilter) {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
@@ -0,0 +1,139 @@
+// looking players up without going through every career each time: the career, the season rows (all of
+// them, and the MLB ones alone) and the supplementary (non-MLB) rows for each link, and the links for each name, built once after the careers
+// are added up and shared by every command that's asked about a player
+
+use std::collections::HashMap;
+
+use crate::careers::{group_by_player, AggregatedPlayer};
+use crate::data::{CleanPlayerSeason, SupplementarySeason};
+use crate::error::Error;
+use crate::search::normalize_name;
+
+pub struct PlayerIndex<'a> {
+    by_name: HashMap<String, Vec<&'a AggregatedPlayer>>,  // "first last", last name and link, normalized
+    by_link: HashMap<&'a str, &'a AggregatedPlayer>,
+    seasons: HashMap<&'a str, Vec<&'a CleanPlayerSeason>>,
+    mlb_seasons: HashMap<&'a str, Vec<&'a CleanPlayerSeason>>,  // the same as seasons, unless supplementary rows were folded in
+    supplementary: HashMap<&'a str, Vec<&'a SupplementarySeason>>,
+}
+
+impl<'a> PlayerIndex<'a> {
+    // the records are the MLB rows, or with --include-supplementary those and the supplementary ones together
+    pub fn build(aggregated_players: &'a [AggregatedPlayer], clean_records: &'a [CleanPlayerSeason], mlb_records: &'a [CleanPlayerSeason], supplementary: &'a [SupplementarySeason]) -> PlayerIndex<'a> {
+        let mut by_name: HashMap<String, Vec<&AggregatedPlayer>> = HashMap::new();
+        let mut by_link = HashMap::with_capacity(aggregated_players.len());
+
+        for player in aggregated_players {
+            let mut keys = vec![
+                normalize_name(&format!("{} {}", player.first_name, player.last_name)),
+                normalize_name(&player.last_name),
+                normalize_name(&player.link),
+            ];
+            keys.dedup();
+            for key in keys {
+                by_name.entry(key).or_default().push(player);
+            }
+            by_link.insert(player.link.as_str(), player);
+        }
+
+        // oldest first, so father/son pairs read in order
+        for players in by_name.values_mut() {
+            players.sort_by_key(|p| (p.first_season, p.link.as_str()));
+        }
+
+        let mut supplementary_seasons: HashMap<&str, Vec<&SupplementarySeason>> = HashMap::new();
+        for season in supplementary {
+            supplementary_seasons.entry(season.record.link.as_ref()).or_default().push(season);
+        }
+
+        PlayerIndex {
+            by_name,
+            by_link,
+            seasons: group_by_player(clean_records).into_iter().collect(),
+            mlb_seasons: group_by_player(mlb_records).into_iter().collect(),
+            supplementary: supplementary_seasons,
+        }
+    }
+
+    // everyone whose full name, last name or link is the query (in any case), oldest first
+    pub fn find(&self, query: &str) -> Vec<&'a AggregatedPlayer> {
+        self.by_name.get(&normalize_name(query)).cloned().unwrap_or_default()
+    }
+
+    pub fn player(&self, link: &str) -> Option<&'a AggregatedPlayer> {
+        self.by_link.get(link).copied()
+    }
+
+    // a player's season rows, in the order the file has them
+    pub fn seasons(&self, link: &str) -> &[&'a CleanPlayerSeason] {
+        self.seasons.get(link).map(|s| s.as_slice()).unwrap_or_default()
+    }
+
+    // just his MLB rows, in file order (leaving out any supplementary ones folded into the records)
+    pub fn mlb_seasons(&self, link: &str) -> &[&'a CleanPlayerSeason] {
+        self.mlb_seasons.get(link).map(|s| s.as_slice()).unwrap_or_default()
+    }
+
+    // his rows from the supplementary files, in the order they were loaded
+    pub fn supplementary(&self, link: &str) -> &[&'a SupplementarySeason] {
+        self.supplementary.get(link).map(|s| s.as_slice()).unwrap_or_default()
+    }
+
+    // one season's rows (more than one if he was traded during it)
+    pub fn season(&self, link: &str, season: u32) -> Vec<&'a CleanPlayerSeason> {
+        self.seasons(link).iter().copied().filter(|s| s.season == season).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_link.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_link.is_empty()
+    }
+}
+
+// the error for a name (or link) that matches nobody
+pub fn player_not_found(query: &str) -> Error {
+    Error::NotFound { what: "player", path: format!("\"{}\"", query) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::careers::aggregate_players;
+    use crate::data::seasons_from_csv;
+    use crate::options::Options;
+
+    // two of Clemente's MLB seasons, and a winter in Puerto Rico
+    const MLB: &str = "\
+1960,Roberto,Clemente,/player/112391,RF,PIT,144,570,89,179,22,6,16,94,39,72,4,5,.314,.357,.458,.815
+1961,Roberto,Clemente,/player/112391,RF,PIT,146,572,100,201,30,10,23,89,35,59,4,1,.351,.390,.559,.949
+";
+    const WINTER: &str = "\
+1961,Roberto,Clemente,/player/112391,RF,SJ,60,240,40,82,12,3,8,40,20,25,6,2,.342,.398,.517,.915
+";
+
+    #[test]
+    fn mlb_seasons_leave_out_folded_in_winter_ball() {
+        let mlb_records = seasons_from_csv(MLB);
+        let supplementary: Vec<SupplementarySeason> = seasons_from_csv(WINTER)
+            .into_iter()
+            .map(|record| SupplementarySeason { league: "PRWL".to_string(), record })
+            .collect();
+
+        // folded in the way --include-supplementary does, but winter ball first, so it can't be told apart by where it is
+        let mut combined: Vec<CleanPlayerSeason> = supplementary.iter().map(|s| s.record.clone()).collect();
+        combined.extend(mlb_records.iter().cloned());
+        let aggregated_players = aggregate_players(&combined, &Options::default());
+
+        let index = PlayerIndex::build(&aggregated_players, &combined, &mlb_records, &supplementary);
+        let link = "/player/112391";
+        assert_eq!(index.seasons(link).len(), 3);
+        let mlb: Vec<(u32, &str)> = index.mlb_seasons(link).iter().map(|s| (s.season, s.team.as_ref())).collect();
+        assert_eq!(mlb, vec![(1960, "PIT"), (1961, "PIT")]);
+        assert_eq!(index.supplementary(link).len(), 1);
+        assert_eq!(index.supplementary(link)[0].league, "PRWL");
+        assert!(index.mlb_seasons("/player/nobody").is_empty());
+    }
+}
@@ -1,543 +1,993 @@
 // install tools
 
-// this one reads cvs files
-use csv::ReaderBuilder;
+// the exit code a failed run ends with (see error.rs)
+use std::process::ExitCode;
 
-// this one helps import cvs data
-use serde::Deserialize;
+// the log crate carries the loading messages (see logging.rs)
+use log::{error, info, warn};
 
-// this one is a standard error handler
-use std::error::Error;
+// tool for checking which players we know about
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-// this one helps us work with different file paths
-use std::path::Path;
-
-// tool for looking up data
-use std::collections::HashMap;
+// times the run, for the usage journal
+use std::time::Instant;
 
 // clap is what reads command line arguments, it also needs adding as a dependency to cargo.toml
-use clap::{Parser, Subcommand};
-
-// set up the framework for the data we're going to import
-#[derive(Debug, Deserialize, Clone)]
-struct PlayerSeason {
-    season: u32,
-    first_name: Option<String>,  // option because some are null
-    last_name: String,
-    link: String,
-    position: String,
-    team: String,
-    games_played: u32,
-    at_bats: u32,
-    runs: u32,
-    hits: u32,
-    doubles: u32,
-    triples: u32,
-    homeruns: u32,
-    rbi: String,  // should be a number, but the raw data is string, we'll fix this later
-    walks: u32,
-    strikeouts: Option<f64>,  // some are null
-    stolen_bases: String,
-    caught_stealing: String,
-    batting_average: f64,
-    on_base_percentage: String,
-    slugging_percentage: f64,
-    on_base_plus_slugging: String,
-}
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 
-// create a new framework with the correct formats
-#[derive(Debug, Clone)]
-struct CleanPlayerSeason {
-    season: u32,
-    first_name: Option<String>,
-    last_name: String,
-    link: String,
-    position: String,
-    team: String,
-    games_played: u32,
-    at_bats: u32,
-    runs: u32,
-    hits: u32,
-    doubles: u32,
-    triples: u32,
-    homeruns: u32,
-    rbi: Option<u32>,  // now a proper number (or "none" if missing)
-    walks: u32,
-    strikeouts: Option<f64>,
-    stolen_bases: Option<u32>,  // now a proper number
-    caught_stealing: Option<u32>,  // now a proper number
-    batting_average: f64,
-    on_base_percentage: Option<f64>,  // now a proper number
-    slugging_percentage: f64,
-    on_base_plus_slugging: Option<f64>,  // now a proper number
-}
+// our own modules, from the library half of the crate (see lib.rs)
+use rust_baseball_stats::{aging, asof, batch, career_export, careers, data, diff, error, export, franchises, hist, hof, join, leaderboards, leaders, logging, lookup, matrix, missing, options, percentile, player, positions, progress, quiz, random, ranking, report, roster, schedule, schema, search, splits, stats, summary, table, teams, timeline, tto, usage, verify, versus, watch};
 
-// pair of functions to convert messy string data to clean numbers
-fn parse_optional_number(value: &str) -> Option<u32> {
-    if value == "--" || value.trim().is_empty() {
-        None  // set as missing data
-    } else {
-        value.trim().parse().ok()  // try to convert to number
-    }
+// reading the command line arguments
+#[derive(Parser)]
+#[command(name = "baseball-stats")]
+#[command(about = "A CLI tool for analyzing baseball statistics")]
+#[command(long_about = "A CLI tool for analyzing baseball statistics.\n\nLoads every player season from mlb_season_data.csv, rolls them up into careers, and answers questions about records, leaders and individual players.")]
+#[command(after_help = "Examples:\n  baseball-stats careers\n  baseball-stats top --stat hr --scope career --top 25\n  baseball-stats player \"ken griffey\"\n  baseball-stats hr --scope career --out hr_leaders.csv\n  baseball-stats seasons --out-dir reports/ --format markdown\n  baseball-stats report --out almanac.html --watch\n  baseball-stats <command> --help    (every command has its own examples)\n\nExit codes:\n  0  it worked\n  1  anything else went wrong\n  2  a bad command line option (or an unknown stat)\n  3  an input file, player, team or season isn't there\n  4  an input file can't be read\n  5  output couldn't be written")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    /// how many rows to show in each leaderboard (ties at the cutoff are all shown)
+    #[arg(long, global = true, default_value_t = 10)]
+    top: usize,
+
+    /// extra non-MLB season file (winter league, foreign league), as LEAGUE=path.csv or just path.csv; can be repeated
+    #[arg(long, global = true, value_name = "LEAGUE=PATH")]
+    supplementary: Vec<String>,
+
+    /// count supplementary seasons in records and career totals (they're left out by default)
+    #[arg(long, global = true)]
+    include_supplementary: bool,
+
+    /// only rows from this league (AL, NL, AAA, ...), for data with a league column (a supplementary file's rows are in their tag's league); can be repeated
+    #[arg(long, global = true, value_name = "LEAGUE")]
+    league: Vec<String>,
+
+    /// csv of extra team code mappings (team,franchise,first_season,last_season), checked before the built-in ones
+    #[arg(long, global = true, value_name = "PATH")]
+    franchise_map: Option<String>,
+
+    /// keep team codes as the data has them (MON and WSH stay separate) instead of grouping them by franchise
+    #[arg(long, global = true)]
+    raw_teams: bool,
+
+    /// csv of first and second half lines (season,link,half,games_played,at_bats,runs,hits,doubles,triples,homeruns,walks,strikeouts), for halves and surges
+    #[arg(long, global = true, value_name = "PATH")]
+    splits: Option<String>,
+
+    /// csv of extra numeric columns (war, fielding runs, ...) to join on by link, usable as stats in top, player and export
+    #[arg(long, global = true, value_name = "PATH")]
+    join: Vec<String>,
+
+    /// what --join files are matched on: link,season (a value per season, added up for careers) or link (a career value)
+    #[arg(long, global = true, value_name = "COLUMNS", default_value = "link,season")]
+    join_key: String,
+
+    /// what to do with a row repeating an earlier one's link, season and team: stop, keep the first copy, or fill in the first copy's missing values from it
+    #[arg(long, global = true, value_enum, default_value = "keep-first")]
+    duplicates: data::DuplicatePolicy,
+
+    /// what a career total does with seasons missing a count (rbi, strikeouts, stolen bases, caught stealing): count 0, leave the career out of lists built on it, or estimate it from the league's rate that season
+    #[arg(long, global = true, value_enum, default_value = "zero")]
+    missing: missing::MissingPolicy,
+
+    /// shrink the season qualifier for rate stats (502 at bats) with a short season's schedule: 1981, 1994, 1995 and 2020, and any --season-lengths adds
+    #[arg(long, global = true)]
+    prorate_short_seasons: bool,
+
+    /// csv of season,games for seasons scheduled for fewer than 162 games, added to (or correcting) the built-in short seasons
+    #[arg(long, global = true, value_name = "PATH")]
+    season_lengths: Option<String>,
+
+    /// how leaderboards handle players with the same value: share the rank (1, 2, 2, 4), or break the tie
+    #[arg(long, global = true, value_enum, default_value = "shared")]
+    ties: ranking::TieBreak,
+
+    /// keep a journal of this run (command, time taken, data size) on this machine, for stats-usage; BASEBALL_STATS_USAGE_LOG=1 keeps it for every run
+    #[arg(long, global = true)]
+    usage_log: bool,
+
+    /// after running, wait for the data file (or any other file it read) to change and run again, reading only the changed file again, until ctrl-c
+    #[arg(long, global = true)]
+    watch: bool,
+
+    /// wrap long table cells onto extra lines instead of shortening them with "..."
+    #[arg(long, global = true)]
+    wrap: bool,
+
+    /// how to write the report (defaults to text, or whatever the --out file's extension suggests); html is a standalone page with sortable tables and charts
+    #[arg(long, global = true, value_enum)]
+    format: Option<table::Format>,
+
+    /// write the report to this file instead of the screen
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "out_dir")]
+    out: Option<String>,
+
+    /// write each table to its own file in this folder, named after the table
+    #[arg(long, global = true, value_name = "DIR")]
+    out_dir: Option<String>,
+
+    /// how many --out-dir files to write at once (defaults to one per core)
+    #[arg(long, global = true, value_name = "N", requires = "out_dir")]
+    jobs: Option<usize>,
+
+    /// more detail while loading (-vv for everything)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// fewer messages while loading: -q hides progress, -qq hides warnings too
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "verbose")]
+    quiet: u8,
 }
 
-fn parse_optional_float(value: &str) -> Option<f64> {
-    if value == "--" || value.trim().is_empty() {
-        None  // set as missing data
-    } else {
-        value.trim().parse().ok()  // try to convert to float
-    }
+// examples for the commands that take --stat; the list of stats gets added underneath when the cli is built
+const TOP_EXAMPLES: &str = "Examples:\n  baseball-stats top --stat homeruns\n  baseball-stats top --stat avg --scope career --top 20\n  baseball-stats top --stat hr --min-ab 300 --ascending\n  baseball-stats top --stat homeruns --scope career --position C\n  baseball-stats top --stat ops --adjusted\n  baseball-stats top --stat ops --adjusted --league AL\n  baseball-stats top --stat war --scope career --join war.csv\n  baseball-stats top --stat homeruns --scope rookie\n  baseball-stats top --stat avg --prorate-short-seasons";
+const BOTTOM_EXAMPLES: &str = "Examples:\n  baseball-stats bottom --stat avg\n  baseball-stats bottom --stat strikeouts --top 25\n  baseball-stats bottom --stat obp --scope career --min-ab 5000";
+
+// define the available commands
+#[derive(Subcommand)]
+enum Commands {
+    /// show home run records
+    #[command(long_about = "Show home run records: the top single seasons and the top careers, with how many seasons each career took.")]
+    #[command(after_help = "Examples:\n  baseball-stats homeruns\n  baseball-stats homeruns --top 25")]
+    Homeruns,
+    /// show season records
+    #[command(long_about = "Show single season records for hits, home runs and walks.")]
+    #[command(after_help = "Examples:\n  baseball-stats seasons\n  baseball-stats seasons --top 5")]
+    Seasons,
+    /// show career records
+    #[command(long_about = "Show career records for games played, hits and home runs, with the teams each player played for, and the best career OPS with the rest of the slash line.\n\nCareer rates come from the career totals (hits over at bats, not the average of each season's average), and only careers of 3000 at bats or more are ranked.")]
+    #[command(after_help = "Examples:\n  baseball-stats careers\n  baseball-stats careers --top 50 --wrap")]
+    Careers,
+    /// rank any stat, best first (for strikeouts and caught stealing, best means fewest)
+    #[command(long_about = "Rank single seasons or whole careers by any stat, best first.\n\nFor most stats best means highest; for strikeouts and caught stealing it means fewest. Rate stats (and any lowest-first list) only include qualified players: 502 at bats for a season, 3000 for a career, unless --min-ab says otherwise. Players tied on the stat share a rank.\n\n--scope rookie and --scope final rank only each player's first or last season in the data (leaving out anyone already playing when the data starts, or still playing when it ends).")]
+    #[command(after_help = TOP_EXAMPLES)]
+    Top {
+        #[command(flatten)]
+        args: leaders::LeaderArgs,
+    },
+    /// rank any stat, worst first (e.g. worst qualified batting averages, most strikeouts)
+    #[command(long_about = "Rank single seasons or whole careers by any stat, worst first.\n\nThe same as top, turned around: the lowest batting averages, or the most strikeouts. The same qualifying rules apply.")]
+    #[command(after_help = BOTTOM_EXAMPLES)]
+    Bottom {
+        #[command(flatten)]
+        args: leaders::LeaderArgs,
+    },
+    /// home run leaders (shortcut for top --stat homeruns)
+    #[command(long_about = "Home run leaders, single season by default. A shortcut for top --stat homeruns; it takes the same --scope, --min-ab and --ascending options.")]
+    #[command(after_help = "Examples:\n  baseball-stats hr\n  baseball-stats hr --scope career --top 25")]
+    Hr {
+        #[command(flatten)]
+        args: leaders::ShortcutArgs,
+    },
+    /// batting average leaders among qualified players (shortcut for top --stat avg)
+    #[command(long_about = "Batting average leaders, single season by default, among players with 502 at bats in a season or 3000 in a career unless --min-ab says otherwise. A shortcut for top --stat avg.")]
+    #[command(after_help = "Examples:\n  baseball-stats avg\n  baseball-stats avg --scope career\n  baseball-stats avg --min-ab 300")]
+    Avg {
+        #[command(flatten)]
+        args: leaders::ShortcutArgs,
+    },
+    /// runs batted in leaders (shortcut for top --stat rbi)
+    #[command(long_about = "Runs batted in leaders, single season by default. A shortcut for top --stat rbi.")]
+    #[command(after_help = "Examples:\n  baseball-stats rbi\n  baseball-stats rbi --scope career")]
+    Rbi {
+        #[command(flatten)]
+        args: leaders::ShortcutArgs,
+    },
+    /// stolen base leaders (shortcut for top --stat sb)
+    #[command(long_about = "Stolen base leaders, single season by default. A shortcut for top --stat stolen_bases.")]
+    #[command(after_help = "Examples:\n  baseball-stats sb\n  baseball-stats sb --scope career --top 20")]
+    Sb {
+        #[command(flatten)]
+        args: leaders::ShortcutArgs,
+    },
+    /// hits leaders (shortcut for top --stat hits)
+    #[command(long_about = "Hits leaders, single season by default. A shortcut for top --stat hits.")]
+    #[command(after_help = "Examples:\n  baseball-stats hits\n  baseball-stats hits --scope career")]
+    Hits {
+        #[command(flatten)]
+        args: leaders::ShortcutArgs,
+    },
+    /// speed score leaders among qualified players (shortcut for top --stat speed)
+    #[command(long_about = "Speed score leaders, single season by default. This is Bill James' speed score, built from four things the data has: how often a player's steals succeed, how often he tries to steal, how many triples he hits, and how often he scores once he's on base. Each part is scored 0 to 10, and the speed score is their average.\n\nSeasons without caught stealing or strikeout data don't get a score. Career scores use career totals, and careers with no caught stealing on record at all don't get one. Only qualified players are listed: 502 at bats for a season, 3000 for a career, unless --min-ab says otherwise. A shortcut for top --stat speed.")]
+    #[command(after_help = "Examples:\n  baseball-stats speed\n  baseball-stats speed --scope career --top 25")]
+    Speed {
+        #[command(flatten)]
+        args: leaders::ShortcutArgs,
+    },
+    /// three true outcomes (home runs, walks, strikeouts) league-wide by season and decade, plus leaders
+    #[command(long_about = "Three true outcomes: the share of plate appearances that end in a home run, a walk or a strikeout, with no fielder involved.\n\nShows the league-wide rate for every season (with a bar so the trend is visible), the rate by decade and how much it changed, then the highest single seasons and careers among qualified players. Plate appearances are at bats plus walks, since the data has no hit by pitch or sacrifices, and seasons without strikeout data are left out. With --format html the trend is drawn as a chart.")]
+    #[command(after_help = "Examples:\n  baseball-stats tto\n  baseball-stats tto --top 20\n  baseball-stats top --stat tto --scope career\n  baseball-stats tto --out tto.html")]
+    Tto,
+    /// players grouped by primary position, with what each position hit and its home run leader
+    #[command(long_about = "Group every player by primary position (the one he played the most games at) and show how many players each position has, what they hit between them, and the career home run leader at each.\n\nTo rank a single position, use --position with top, bottom or the shortcuts (hr, avg, ...). OF takes in LF, CF and RF.")]
+    #[command(after_help = "Examples:\n  baseball-stats by-position\n  baseball-stats hr --scope career --position C\n  baseball-stats top --stat avg --scope career --position ss --top 20")]
+    ByPosition,
+    /// a player's season split into first half and second half (needs --splits)
+    #[command(long_about = "Show a player's season split into its first and second halves, with the whole season underneath and how much each rate went up or down after the break.\n\nThe season data only has whole seasons, so the halves come from a separate file given with --splits: a csv with season,link,half,games_played,at_bats,runs,hits,doubles,triples,homeruns,walks,strikeouts columns, where half is 1 or 2 and link matches the season data.")]
+    #[command(after_help = "Examples:\n  baseball-stats halves \"mookie betts\" 2018 --splits halves.csv\n\nSplits file:\n  season,link,half,games_played,at_bats,runs,hits,doubles,triples,homeruns,walks,strikeouts\n  2018,/player/605141,1,85,340,82,114,29,4,22,45,55\n  2018,/player/605141,2,51,180,47,66,18,1,10,36,36")]
+    Halves {
+        /// last name, full name or player link
+        name: String,
+        /// the season to split
+        season: u32,
+    },
+    /// the biggest jumps in OPS from the first half of a season to the second (needs --splits)
+    #[command(long_about = "The biggest second half surges: the seasons where a player's OPS went up the most from the first half to the second. --fades turns it round to the biggest drops.\n\nBoth halves need 251 at bats (half a qualifying season) unless --min-ab says otherwise. The halves come from the file given with --splits; see halves --help for its columns.")]
+    #[command(after_help = "Examples:\n  baseball-stats surges --splits halves.csv\n  baseball-stats surges --splits halves.csv --fades --min-ab 200 --top 20")]
+    Surges {
+        /// minimum at-bats in each half (default 251)
+        #[arg(long)]
+        min_ab: Option<u32>,
+        /// the biggest drops instead of the biggest jumps
+        #[arg(long)]
+        fades: bool,
+    },
+    /// where each of a player's stats ranked among qualified hitters in one season (percentile and z-score)
+    #[command(long_about = "Show where a player's season stood: for every stat, his rank among that season's qualified hitters (502 at bats unless --min-ab says otherwise), his percentile, his z-score (standard deviations from the average, with plus always better) and the average itself.\n\nA season split between teams is shown one team at a time, the same way the leaderboards count it.")]
+    #[command(after_help = "Examples:\n  baseball-stats percentile \"barry bonds\" --season 2001\n  baseball-stats percentile \"ichiro suzuki\" --season 2004 --min-ab 400")]
+    Percentile {
+        /// last name, full name or player link
+        name: String,
+        /// the season to look at
+        #[arg(long)]
+        season: u32,
+        /// minimum at-bats for the hitters he's compared against (default 502)
+        #[arg(long)]
+        min_ab: Option<u32>,
+    },
+    /// a histogram of a stat across player seasons, one season or all of them
+    #[command(long_about = "Show how a stat is spread across player seasons: the number of seasons in each bin, with a bar of #s for each, and who has the highest value. One season with --season, every season without it.\n\nBins are picked to give about 20 unless --bin-width says otherwise. Rate stats only count seasons with 502 at bats unless --min-ab says otherwise; counting stats count everyone. With --format html the histogram is drawn as a chart too.")]
+    #[command(after_help = "Examples:\n  baseball-stats hist --stat homeruns --season 1998\n  baseball-stats hist --stat hr --season 1998 --min-ab 300 --bin-width 5\n  baseball-stats hist --stat avg --bin-width 0.010")]
+    Hist {
+        /// the stat to show, e.g. homeruns, hr, avg (see top --help for the list)
+        #[arg(long)]
+        stat: String,
+        /// only this season (every season by default)
+        #[arg(long)]
+        season: Option<u32>,
+        /// how wide each bin is, e.g. 5 for home runs or 0.010 for batting average
+        #[arg(long)]
+        bin_width: Option<f64>,
+        /// minimum at-bats for a season to count (defaults to 502 for rate stats, 0 otherwise)
+        #[arg(long)]
+        min_ab: Option<u32>,
+    },
+    /// a stat by player age across the league, from a file of birthdates
+    #[command(long_about = "Bucket every qualified season by how old the player was and show the average and median of a stat at each age, to see where hitters peak and how they age. This is the whole league at once; player shows one career.\n\nThe data has no birthdates, so they come from --birthdates: a csv with a link column and a birth_date column (1934-02-05, or just 1934). A season's age is the player's age on June 30th of it. Seasons need 502 at bats, for counting stats too, unless --min-ab says otherwise, and ages with fewer than --min-seasons seasons are left out. --chart adds a bar of #s for the average at each age; with --format html it's drawn as a chart too.")]
+    #[command(after_help = "Examples:\n  baseball-stats aging-curve --stat ops --birthdates birthdates.csv\n  baseball-stats aging-curve --stat hr --birthdates birthdates.csv --chart\n  baseball-stats aging-curve --stat strikeout_rate --birthdates birthdates.csv --min-ab 300 --min-seasons 20\n\nBirthdates file:\n  link,birth_date\n  /player/110002,1934-02-05")]
+    AgingCurve {
+        /// the stat to follow, e.g. ops, hr, avg (see top --help for the list)
+        #[arg(long)]
+        stat: String,
+        /// csv with link and birth_date columns
+        #[arg(long, value_name = "FILE")]
+        birthdates: String,
+        /// minimum at-bats for a season to count (default 502)
+        #[arg(long)]
+        min_ab: Option<u32>,
+        /// leave out ages with fewer qualified seasons than this
+        #[arg(long, default_value_t = 5)]
+        min_seasons: usize,
+        /// draw the averages as a bar of #s
+        #[arg(long)]
+        chart: bool,
+    },
+    /// career leaders among active players as they stood after a season
+    #[command(long_about = "Rank career totals as they stood at the end of a season, among the players who played in it: --stat hits --season 1974 answers who led active players in career hits going into 1975. Only seasons up to and including that one are counted.\n\nRate stats need 3000 career at bats by then unless --min-ab says otherwise.")]
+    #[command(after_help = "Examples:\n  baseball-stats leaders-asof 1974 --stat hits\n  baseball-stats leaders-asof 1998 --stat homeruns --top 20\n  baseball-stats leaders-asof 1985 --stat strikeout_rate --min-ab 2000")]
+    LeadersAsof {
+        /// the season to stop at
+        season: u32,
+        /// the stat to rank by, e.g. hits, hr, avg (see top --help for the list)
+        #[arg(long, default_value = "hits")]
+        stat: String,
+        /// minimum career at-bats by then (defaults to 3000 for rate stats, 0 otherwise)
+        #[arg(long)]
+        min_ab: Option<u32>,
+    },
+    /// the most hall-worthy careers, by Bill James' Hall of Fame Monitor or Black Ink
+    #[command(long_about = "Rank careers by Bill James' Hall of Fame Monitor (points for big seasons, for leading the majors and for career milestones, where about 100 makes a likely hall of famer) or by Black Ink (points for every season a player led the majors in a stat), with --by black-ink.\n\nOnly the parts the data has are counted: nothing for awards, all-star games, the post-season or fielding, so catchers and shortstops score lower than they should. Unless the data has a league column, leading means leading both leagues together.")]
+    #[command(after_help = "Examples:\n  baseball-stats hof\n  baseball-stats hof --by black-ink --top 25")]
+    Hof {
+        /// which score to rank by
+        #[arg(long, value_enum, default_value_t = hof::HofScore::Monitor)]
+        by: hof::HofScore,
+    },
+    /// a player's career team by team: each stint, in order, and how it started
+    #[command(long_about = "List each stint of a player's career in order: the team (as the data has it, so the Expos stay the Expos), the seasons and games, and how the stint started, going by where the team changed: mid-season, between seasons, or coming back after missing seasons. The data has no transactions, so trades, sales and free agent signings all look alike.\n\nThe last column draws each stint across the years of the career; with --format html the stints are drawn as a timeline chart too.")]
+    #[command(after_help = "Examples:\n  baseball-stats timeline \"rickey henderson\"\n  baseball-stats timeline \"ted williams\"\n  baseball-stats timeline \"rickey henderson\" --out rickey.html")]
+    Timeline {
+        /// last name, full name or player link
+        name: String,
+    },
+    /// show a player's page (season by season, career totals, non-MLB seasons)
+    #[command(long_about = "Show a player's page: every MLB season, their MLB career totals, and any supplementary (winter or foreign league) seasons in a separate section. With --format html (or an --out file ending .html) the page also gets bar charts of hits, home runs and batting average by season.\n\nEvery player matching the name is shown, so \"griffey\" shows both Ken Griffeys.")]
+    #[command(after_help = "Examples:\n  baseball-stats player bonds\n  baseball-stats player \"barry bonds\"\n  baseball-stats player /player/111188\n  baseball-stats player \"barry bonds\" --out bonds.html\n  baseball-stats player \"barry bonds\" --supplementary LVBP=winter.csv")]
+    Player {
+        /// last name, full name or player link
+        name: String,
+    },
+    /// find players by name (prefix match, or fuzzy with --fuzzy)
+    #[command(long_about = "Find players by name. Matches the start of a first-and-last name or a last name; if nothing matches it falls back to close spellings.")]
+    #[command(after_help = "Examples:\n  baseball-stats search griff\n  baseball-stats search \"mickey mantel\"\n  baseball-stats search bnods --fuzzy 2")]
+    Search {
+        /// the start of a name, e.g. "griff" or "ken griffey"
+        query: String,
+        /// allow this many typos instead of matching the start of the name
+        #[arg(long)]
+        fuzzy: Option<u32>,
+        /// most players to list
+        #[arg(long, default_value_t = 25)]
+        limit: usize,
+    },
+    /// describe the loaded dataset: size, coverage, missing data and totals
+    #[command(long_about = "Describe the loaded dataset: how many rows and players, which seasons and teams it covers, how many rows are missing each optional stat, and totals across every row.")]
+    #[command(after_help = "Examples:\n  baseball-stats summary")]
+    Summary,
+    /// write the whole almanac: career leaders, season leaders, milestone clubs and team records
+    #[command(long_about = "Write the whole almanac as one document: career leaders in every stat, the leader of every season, the big milestone clubs (3000 hits, 500 home runs, ...) and every team's single season records.\n\nIt comes out as markdown unless --format or the --out file's extension says otherwise; --format html makes a standalone web page. --top sets how many players each career list shows.")]
+    #[command(after_help = "Examples:\n  baseball-stats report --out almanac.md\n  baseball-stats report --out almanac.html\n  baseball-stats report --section milestones --section teams\n  baseball-stats report --all --out-dir reports/")]
+    Report {
+        #[command(flatten)]
+        args: report::ReportArgs,
+    },
+    /// every hitter on a team in one season, with their stat lines
+    #[command(long_about = "List every player row the data has for a team in one season, with each full stat line and the team's totals underneath, sorted by at bats unless --by names another stat (best first; anyone without the stat goes at the end).\n\nTeam codes are the ones in the data (NYY, BOS, LAD, ...) or a franchise's current code (WSH finds the Expos), and can be typed in any case.")]
+    #[command(after_help = "Examples:\n  baseball-stats roster NYY 1927\n  baseball-stats roster sea 2001 --by avg\n  baseball-stats roster MON 1994 --by hr --out expos.csv")]
+    Roster {
+        /// the team's code, e.g. NYY
+        team: String,
+        /// the season
+        season: u32,
+        /// the stat to sort by
+        #[arg(long, default_value = "at_bats")]
+        by: String,
+    },
+    /// the seasons where one player hit the biggest share of his team's home runs (or scored its runs, or got its hits)
+    #[command(long_about = "The most extreme one-man offenses: the seasons where one player accounted for the biggest share of his team's home runs, with --stat runs or --stat hits for those instead. Shows the player's count, the team's and the share.\n\nA team's totals are all of its players' rows that season added up, with the team as the data has it, so a player traded mid-season is measured against each team on its own. Only seasons with 502 at bats count unless --min-ab says otherwise. The shares are stats of their own too (homerun_share, run_share, hit_share), so top and the other commands can use them.")]
+    #[command(after_help = "Examples:\n  baseball-stats one-man\n  baseball-stats one-man --stat runs --top 20\n  baseball-stats top --stat hr_share --min-ab 300")]
+    OneMan {
+        /// homeruns, runs or hits
+        #[arg(long, default_value = "homeruns")]
+        stat: String,
+        /// minimum at-bats to qualify (default 502)
+        #[arg(long)]
+        min_ab: Option<u32>,
+    },
+    /// compare two teams' offense in one season, with each side's top contributors
+    #[command(long_about = "Compare two teams' offense in one season: every player's line added up into a team line, side by side, with which team has the edge in each stat, then each team's top contributors.\n\nContributors are ranked by hits unless --by names another stat. Team codes are the ones in the data (NYY, BOS, LAD, ...) or a franchise's current code (WSH finds the Expos), and can be typed in any case.")]
+    #[command(after_help = "Examples:\n  baseball-stats versus-teams NYY BOS 2004\n  baseball-stats versus-teams lad nyy 2024 --by hr --top 5")]
+    VersusTeams {
+        /// the first team's code, e.g. NYY
+        team_a: String,
+        /// the second team's code, e.g. BOS
+        team_b: String,
+        /// the season to compare
+        season: u32,
+        /// the stat to rank each team's contributors by
+        #[arg(long, default_value = "hits")]
+        by: String,
+    },
+    /// list every franchise with the team codes it has used and its all-time totals
+    #[command(long_about = "List every franchise with the team codes it has gone by, the seasons it covers, how many players it has had, and its all-time hits and home runs.\n\nClubs that moved or were renamed are one franchise under the code they use now (the Expos are WSH, the Angels are LAA). Extra or corrected mappings can be given with --franchise-map, and --raw-teams keeps every code separate.")]
+    #[command(after_help = "Examples:\n  baseball-stats franchises\n  baseball-stats franchises --raw-teams\n  baseball-stats franchises --franchise-map my_teams.csv\n\nFranchise map file:\n  team,franchise,first_season,last_season\n  MON,WSH,1969,2004\n  TBD,TB,,")]
+    Franchises,
+    /// what's been run and how long it took, from the local usage journal (see --usage-log)
+    #[command(long_about = "Show the usage journal: how many times each command has been run, how long it took on average and at worst, when it was last run, and the most recent runs with the size of the data they loaded.\n\nThe journal is only kept when asked for, with --usage-log on a run or BASEBALL_STATS_USAGE_LOG=1 in the environment, and only on this machine (in the user's cache folder). Nothing is sent anywhere. --clear deletes it.")]
+    #[command(after_help = "Examples:\n  BASEBALL_STATS_USAGE_LOG=1 baseball-stats hr\n  baseball-stats stats-usage\n  baseball-stats stats-usage --top 50\n  baseball-stats stats-usage --clear")]
+    StatsUsage {
+        /// delete the journal
+        #[arg(long)]
+        clear: bool,
+    },
+    /// export every season, every career and a leaderboard for every stat (e.g. to an excel workbook)
+    #[command(long_about = "Export everything: every season row, every career, and a top --top leaderboard for every stat, season and career.\n\nMeant for --out. With an .xlsx file (or --format xlsx) each table becomes its own worksheet with the header row frozen; csv, json, markdown and html work too, and --out-dir writes one file per table.")]
+    #[command(after_help = "Examples:\n  baseball-stats export --out baseball.xlsx\n  baseball-stats export --format xlsx --out stats.xlsx --top 25\n  baseball-stats export --out-dir export/ --format csv")]
+    Export,
+    /// write every career to a csv or json file, in a fixed set of columns with a schema version
+    #[command(long_about = "Write every career (totals, the career rates that can be worked out from them, positions and teams) to a csv or json file, going by the path's extension, one row per player sorted by link.\n\nThe columns are fixed for each schema version, and every row carries the version (the json has it once at the top, with the column descriptions), so a pipeline reading the file can check it's getting what it expects. The columns are listed at the bottom of --help.")]
+    #[command(after_help = "Examples:\n  baseball-stats export-careers careers.csv\n  baseball-stats export-careers careers.json --raw-teams")]
+    ExportCareers {
+        /// the file to write, ending .csv or .json
+        path: String,
+    },
+    /// run a file of reports, each with its own options and output, on one load of the data
+    #[command(long_about = "Run every report in a toml file one after another, loading the data once, instead of starting the tool for each.\n\nEach [[report]] table has a command and that command's options, with the option names as keys (min_ab or min-ab for --min-ab, true for a flag, name = \"ken griffey\" for player's argument), plus top, format, out, out_dir, jobs and wrap to say how much and where it goes. Settings at the top of the file go to every report that doesn't set its own. Anything that changes the data itself (--join, --supplementary, --missing and the rest) goes on the batch command line, since every report shares it.\n\nEvery report is checked before any runs. A report that fails is logged and the rest still run; the batch then exits with the first failure's exit code.")]
+    #[command(after_help = "Examples:\n  baseball-stats batch reports.toml\n  baseball-stats batch reports.toml --join war.csv --prorate-short-seasons\n\nReports file:\n  top = 20\n  format = \"csv\"\n\n  [[report]]\n  title = \"career home runs\"\n  command = \"top\"\n  stat = \"homeruns\"\n  scope = \"career\"\n  out = \"reports/career_hr.csv\"\n\n  [[report]]\n  command = \"player\"\n  name = \"ken griffey\"\n  format = \"markdown\"\n  out = \"reports/griffey.md\"")]
+    Batch {
+        /// toml file of [[report]] tables
+        path: String,
+    },
+    /// describe every stat, scope and command option as json, for front ends that build their own query screens
+    #[command(long_about = "Write a json description of what can be asked for: every stat (built in, and any --join columns) with its type, whether higher is better, the scopes it can be ranked in, its qualifying at bats and whether the data has it; the scopes; and every command with its options, their types, choices and defaults.\n\nA web page, a TUI or a script can build its query screens from this and then run the commands with --format json, without keeping its own list of stat names. The document has a schema_version, which goes up when a field is renamed or taken away. It's json whatever --format says, and goes to the screen unless a path is given. Without a season file in the folder it still lists the stats and options, with the data description and each stat's availability left null.")]
+    #[command(after_help = "Examples:\n  baseball-stats schema\n  baseball-stats schema schema.json\n  baseball-stats schema --join war.csv")]
+    Schema {
+        /// write it to this file instead of the screen
+        path: Option<String>,
+    },
+    /// a few players' careers against each other in one stat: who leads whom, and by how much
+    #[command(long_about = "Compare a few players' careers in one stat: each player's career value and how many of the others he's ahead of, then a grid with every pair, row against column, showing how far the row's player is ahead (+) or behind (-). Ahead means better, so fewer for strikeouts and caught stealing.\n\nPlayers are a comma separated list of names or links. A name more than one player had goes to the one with the most at bats, with the others' links listed to pick one instead. The grid is a table like any other, so --format csv or --out grid.csv writes it for a spreadsheet.")]
+    #[command(after_help = "Examples:\n  baseball-stats matrix --stat ops --players \"babe ruth,ted williams,lou gehrig,barry bonds\"\n  baseball-stats matrix --stat hits --players \"pete rose,ty cobb,hank aaron\" --out matrix.csv\n  baseball-stats matrix --stat war --players \"mays,mantle,snider\" --join war.csv")]
+    Matrix {
+        /// the stat to compare, e.g. ops, hr, hits (top --help lists them all)
+        #[arg(long)]
+        stat: String,
+        /// comma separated names or links
+        #[arg(long, value_name = "NAMES")]
+        players: String,
+    },
+    /// a multiple choice quiz made up from the data, with a running score
+    #[command(long_about = "A multiple choice quiz made up from the data: who led the majors in a stat in some season (against the next best hitters that year), or which of two players had more of a stat in his career (among careers of 3000 at bats or more).\n\nQuestions are asked one at a time on the screen. Answer with the letter (or the number) of a choice, or q to stop; the score so far is shown after each answer. --seed makes the same quiz again.")]
+    #[command(after_help = "Examples:\n  baseball-stats quiz\n  baseball-stats quiz --questions 20\n  baseball-stats quiz --seed 1987")]
+    Quiz {
+        /// how many questions to ask
+        #[arg(long, default_value_t = 10)]
+        questions: usize,
+        /// the same seed gives the same questions in the same order
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// a random player's career card, or with --fact a random record
+    #[command(long_about = "Show a random player's career card: his seasons, positions and teams, career totals and slash line, and what he led the majors in. Only players with --min-ab career at bats are picked (1000 unless it says otherwise).\n\nWith --fact it shows a random record instead: a season or a career from the top ten in a random stat, with the same qualifying minimums as top (502 at bats a season and 3000 a career for rate stats).\n\n--seed picks the same player or fact every time (a date makes a stat of the day).")]
+    #[command(after_help = "Examples:\n  baseball-stats random\n  baseball-stats random --min-ab 5000\n  baseball-stats random --fact -q\n  baseball-stats random --fact --seed $(date +%Y%m%d)")]
+    Random {
+        /// a random record instead of a random player
+        #[arg(long)]
+        fact: bool,
+        /// fewest career at bats a random player can have
+        #[arg(long, default_value_t = 1000)]
+        min_ab: u32,
+        /// the same seed picks the same player or fact
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// compare the computed leaders against a reference file of known records
+    #[command(long_about = "Compare the leaders worked out from the dataset against a reference file of known records, and report every value, rank or player that doesn't match.\n\nThe reference is a csv with scope,stat,rank,first_name,last_name,season,value columns. Scope is season or career, and season is left blank for careers.")]
+    #[command(after_help = "Examples:\n  baseball-stats verify --reference known_records.csv\n\nReference file:\n  scope,stat,rank,first_name,last_name,season,value\n  season,homeruns,1,Barry,Bonds,2001,73\n  career,hits,1,Pete,Rose,,4256")]
+    Verify {
+        /// csv with scope,stat,rank,first_name,last_name,season,value columns
+        #[arg(long)]
+        reference: String,
+    },
+    /// what changed between two versions of the season file: new players, new and removed seasons, corrections
+    #[command(long_about = "Compare two versions of the season file (the last scrape and a fresh one, say) and report what the provider revised: players the old file didn't have, seasons added for players it did, seasons that are gone, and every number that changed in a season both files have, with the old and new values.\n\nSeasons are matched on link and season. A player's rows for one season (one per team when he was traded) are added up before they're compared, so only a change in the totals, the teams or the name counts as a correction. Both files are read the way the data file is, with --duplicates deciding what happens to a repeated row.")]
+    #[command(after_help = "Examples:\n  baseball-stats diff last_month.csv mlb_season_data.csv\n  baseball-stats diff old.csv new.csv --out changes.csv\n  baseball-stats diff old.csv new.csv --out-dir changes/")]
+    Diff {
+        /// the earlier version of the file
+        old: String,
+        /// the later one
+        new: String,
+    },
 }
 
-// function to convert raw data to clean data
-fn clean_player_data(raw: PlayerSeason) -> CleanPlayerSeason {
-    CleanPlayerSeason {
-        season: raw.season,
-        first_name: raw.first_name,
-        last_name: raw.last_name,
-        link: raw.link,
-        position: raw.position,
-        team: raw.team,
-        games_played: raw.games_played,
-        at_bats: raw.at_bats,
-        runs: raw.runs,
-        hits: raw.hits,
-        doubles: raw.doubles,
-        triples: raw.triples,
-        homeruns: raw.homeruns,
-        rbi: parse_optional_number(&raw.rbi),
-        walks: raw.walks,
-        strikeouts: raw.strikeouts,
-        stolen_bases: parse_optional_number(&raw.stolen_bases),
-        caught_stealing: parse_optional_number(&raw.caught_stealing),
-        batting_average: raw.batting_average,
-        on_base_percentage: parse_optional_float(&raw.on_base_percentage),
-        slugging_percentage: raw.slugging_percentage,
-        on_base_plus_slugging: parse_optional_float(&raw.on_base_plus_slugging),
-    }
+// clap only knows about fixed text, so the list of stats gets added to the long help of the
+// commands that take --stat (and verify, which reads stat names from its file) here
+fn build_cli() -> clap::Command {
+    let stat_list = stats::stat_help();
+
+    Cli::command()
+        .mut_subcommand("top", |c| c.after_long_help(format!("{}\n\n{}", TOP_EXAMPLES, stat_list)))
+        .mut_subcommand("bottom", |c| c.after_long_help(format!("{}\n\n{}", BOTTOM_EXAMPLES, stat_list)))
+        .mut_subcommand("export-careers", |c| {
+            let examples = c.get_after_help().map(|h| h.to_string()).unwrap_or_default();
+            c.after_long_help(format!("{}\n\n{}", examples, career_export::career_schema_help()))
+        })
+        .mut_subcommand("verify", |c| {
+            let examples = c.get_after_help().map(|h| h.to_string()).unwrap_or_default();
+            c.after_long_help(format!("{}\n\n{}", examples, stat_list))
+        })
 }
 
-// the main function
-fn main() -> Result<(), Box<dyn Error>> {
+// the main function: run, and if it didn't work say why and exit with the code for what went wrong
+fn main() -> ExitCode {
 
     // read and parse command line arguments
-    let cli = Cli::parse();
-
-    println!();
-    println!("Loading baseball data...");
-    
-    // tell it where the data is
-    let file_path = "mlb_season_data.csv";
-    
-    // check if it exists
-    if !Path::new(file_path).exists() {
-        println!("Error: {} not found. Please put your CSV file in the project root folder.", file_path);
-        return Ok(());
+    let matches = build_cli().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    // loading messages and progress bars go to stderr, at the level asked for
+    let log_level = logging::level_for(cli.verbose, cli.quiet);
+    logging::init(log_level);
+    progress::set_enabled(log_level >= log::LevelFilter::Info);
+
+    if !cli.watch {
+        return match run(&matches, &mut Inputs::default()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                error!("{}", e);
+                ExitCode::from(e.exit_code())
+            }
+        };
     }
-    
-    // create CSV reader
-    let mut reader = ReaderBuilder::new()
-        .has_headers(true)
-        .from_path(file_path)?;
-    
-    // create a new empty list called raw_records
-    let mut raw_records = Vec::new();
-    let mut error_count = 0;
-    
-    // read each record
-    for (line_num, result) in reader.deserialize().enumerate() {
-        match result {
-            Ok(record) => {
-                let player: PlayerSeason = record;
-                raw_records.push(player);
+
+    // --watch: a run that fails (a file caught half written, say) just waits for the next change, and only
+    // the file that changed is read again
+    let mut inputs = Inputs { keep: true, ..Inputs::default() };
+    loop {
+        if let Err(e) = run(&matches, &mut inputs) {
+            error!("{}", e);
+        }
+        let files = input_files(&cli);
+        info!("Watching {} for changes (ctrl-c to stop)", files.join(", "));
+        match watch::wait_for_change(&files) {
+            Ok(path) => {
+                info!("{} changed, running again", path);
+                inputs.changed(&path, &cli);
             }
             Err(e) => {
-                error_count += 1;
-                if error_count <= 5 { 
-                    println!("Error on line {}: {}", line_num + 2, e);
-                }
+                error!("{}", e);
+                return ExitCode::from(e.exit_code());
             }
         }
     }
+}
 
-    println!("Successfully loaded {} raw records", raw_records.len());
-    
-    // clean the data
-    println!("Cleaning data...");
-    let mut clean_records = Vec::new();
-    
-    for raw_record in &raw_records {
-        let clean_record = clean_player_data(raw_record.clone());
-        clean_records.push(clean_record);
-    }
+// tell it where the data is (it has to be in the folder the tool is run from)
+const DATA_FILE: &str = "mlb_season_data.csv";
 
-    println!("Successfully cleaned {} records", clean_records.len());
-
-#[derive(Debug, Clone)]
-struct AggregatedPlayer {
-    first_name: String,
-    last_name: String,
-    first_season: u32,      // lowest season number
-    last_season: u32,       // highest season number  
-    link: String,
-    seasons_played: u32,    // count of seasons
-    positions: String, // all unique positions
-    teams: String,     // all unique teams
-    team_count: u32,
-    total_games_played: u32,
-    total_at_bats: u32,
-    total_runs: u32,
-    total_hits: u32,
-    total_doubles: u32,
-    total_triples: u32,
-    total_homeruns: u32,
-    total_rbi: u32,
-    total_walks: u32,
-    total_strikeouts: f64,
-    total_stolen_bases: u32,
-    total_caught_stealing: u32,
+// every file a run reads, for --watch
+fn input_files(cli: &Cli) -> Vec<String> {
+    let mut files = Vec::new();
+    if !needs_no_data(cli) {
+        files.push(DATA_FILE.to_string());
+    }
+    files.extend(cli.supplementary.iter().map(|arg| data::parse_supplementary_arg(arg).1));
+    files.extend(cli.franchise_map.iter().cloned());
+    files.extend(cli.splits.iter().cloned());
+    files.extend(cli.season_lengths.iter().cloned());
+    files.extend(cli.join.iter().cloned());
+    match &cli.command {
+        Some(Commands::Verify { reference }) => files.push(reference.clone()),
+        Some(Commands::AgingCurve { birthdates, .. }) => files.push(birthdates.clone()),
+        Some(Commands::Batch { path }) => files.push(path.clone()),
+        Some(Commands::Diff { old, new }) => files.extend([old.clone(), new.clone()]),
+        _ => {}
+    }
+    files
 }
 
-// group players by their unique link
-println!("Identifying individual players...");
-// create a new data set, using strings (vecs) from the cleanplayerseason dataset as the identifiers, but for now it's blank
-let mut player_groups: HashMap<String, Vec<CleanPlayerSeason>> = HashMap::new();
+// what's been read from the season, supplementary, join and splits files, by path, kept from one --watch
+// run to the next so a change only reads that file again (a one-off run doesn't keep anything)
+#[derive(Default)]
+struct Inputs {
+    keep: bool,
+    seasons: HashMap<String, data::LoadReport>,
+    supplementary: HashMap<String, (Vec<data::SupplementarySeason>, data::LoadReport)>,
+    joins: HashMap<String, join::JoinFile>,
+    splits: HashMap<String, Vec<splits::HalfSplit>>,
+}
 
-// for every row in the clean_players dataset
-for player in &clean_records {
-    let link = player.link.clone();
-    // either add it to an existing record in the player_groups dataset (where it matches the link column) or create a new record
-    player_groups.entry(link).or_insert(Vec::new()).push(player.clone());
+impl Inputs {
+    // a file changed, so it's read again next run (the season lengths decide who led each season, so a change
+    // to them reads the season files again too)
+    fn changed(&mut self, path: &str, cli: &Cli) {
+        if cli.season_lengths.as_deref() == Some(path) {
+            self.seasons.clear();
+            self.supplementary.clear();
+        }
+        self.seasons.remove(path);
+        self.supplementary.remove(path);
+        self.joins.remove(path);
+        self.splits.remove(path);
+    }
 }
 
-println!("Found {} unique players", player_groups.len());
+// a file's contents: the copy from the last run if it's still good, otherwise read now (and kept, when watching)
+fn cached<T: Clone>(cache: &mut HashMap<String, T>, keep: bool, path: &str, load: impl FnOnce() -> error::Result<T>) -> error::Result<T> {
+    if let Some(loaded) = cache.get(path) {
+        info!("{} hasn't changed, using what was read last time", path);
+        return Ok(loaded.clone());
+    }
+    let loaded = load()?;
+    if keep {
+        cache.insert(path.to_string(), loaded.clone());
+    }
+    Ok(loaded)
+}
 
+// everything the commands work from, loaded once a run (a batch runs all its reports on the one load)
+struct Dataset {
+    mlb_records: Vec<data::CleanPlayerSeason>,
+    supplementary: Vec<data::SupplementarySeason>,
+    // MLB and supplementary rows together, with --include-supplementary (otherwise the records are the
+    // MLB rows themselves, not a copy)
+    combined: Option<Vec<data::CleanPlayerSeason>>,
+    rejected: Vec<data::RejectedRow>,
+    half_splits: Vec<splits::HalfSplit>,
+    aggregated_players: Vec<careers::AggregatedPlayer>,
+    // --ties, --missing and --prorate-short-seasons, which the data was loaded with and every report uses
+    options: options::Options,
+}
 
-// populate aggregated player records
-println!("Creating aggregated player records...");
-let mut aggregated_players = Vec::new();
+impl Dataset {
+    // nothing loaded, for a command that doesn't look at the season data
+    fn empty(cli: &Cli) -> error::Result<Dataset> {
+        Ok(Dataset {
+            mlb_records: Vec::new(),
+            supplementary: Vec::new(),
+            combined: None,
+            rejected: Vec::new(),
+            half_splits: Vec::new(),
+            aggregated_players: Vec::new(),
+            options: options_for(cli)?,
+        })
+    }
 
-for (link, seasons) in &player_groups {
-    // get basic info from first season
-    let first_season_record = &seasons[0];
-    
-    // find min and max seasons
-    let first_season = seasons.iter().map(|s| s.season).min().unwrap();
-    let last_season = seasons.iter().map(|s| s.season).max().unwrap();
-    
-    // collect unique positions and teams
-    let mut unique_positions = Vec::new();
-    let mut unique_teams = Vec::new();
-    
-    for season in seasons {
-        if !unique_positions.contains(&season.position) {
-            unique_positions.push(season.position.clone());
-        }
-        if !unique_teams.contains(&season.team) {
-            unique_teams.push(season.team.clone());
-        }
+    fn clean_records(&self) -> &[data::CleanPlayerSeason] {
+        self.combined.as_deref().unwrap_or(&self.mlb_records)
     }
+}
 
-    // calculate career totals - iterate through each line and add them up
-    let total_games_played: u32 = seasons.iter().map(|s| s.games_played).sum();
-    let total_at_bats: u32 = seasons.iter().map(|s| s.at_bats).sum();
-    let total_runs: u32 = seasons.iter().map(|s| s.runs).sum();
-    let total_hits: u32 = seasons.iter().map(|s| s.hits).sum();
-    let total_doubles: u32 = seasons.iter().map(|s| s.doubles).sum();
-    let total_triples: u32 = seasons.iter().map(|s| s.triples).sum();
-    let total_homeruns: u32 = seasons.iter().map(|s| s.homeruns).sum();
-    let total_walks: u32 = seasons.iter().map(|s| s.walks).sum();
-    
-    // handle the optional fields (treat None as 0)
-    let total_rbi: u32 = seasons.iter().map(|s| s.rbi.unwrap_or(0)).sum();
-    let total_strikeouts: f64 = seasons.iter().map(|s| s.strikeouts.unwrap_or(0.0)).sum();
-    let total_stolen_bases: u32 = seasons.iter().map(|s| s.stolen_bases.unwrap_or(0)).sum();
-    let total_caught_stealing: u32 = seasons.iter().map(|s| s.caught_stealing.unwrap_or(0)).sum();
-
-    // populate the aggregated player record
-    let aggregated_player = AggregatedPlayer {
-        link: link.clone(),
-        first_name: first_season_record.first_name.as_deref().unwrap_or("N/A").to_string(),
-        last_name: first_season_record.last_name.clone(),
-        first_season,
-        last_season,
-        seasons_played: seasons.len() as u32,
-        positions: unique_positions.join(", "),
-        teams: unique_teams.join(", "),
-        team_count: unique_teams.len() as u32,
-        total_games_played,
-        total_at_bats,
-        total_runs,
-        total_hits,
-        total_doubles,
-        total_triples,
-        total_homeruns,
-        total_rbi,
-        total_walks,
-        total_strikeouts,
-        total_stolen_bases,
-        total_caught_stealing,
-    };
-    
-    aggregated_players.push(aggregated_player);
+// diff reads the two files it's given and stats-usage only the journal, so there's no point loading the
+// season data; schema describes the data when there is some, but without a season file it still lists
+// every stat and option
+fn needs_no_data(cli: &Cli) -> bool {
+    match cli.command {
+        Some(Commands::Diff { .. }) | Some(Commands::StatsUsage { .. }) => true,
+        Some(Commands::Schema { .. }) => !std::path::Path::new(DATA_FILE).exists(),
+        _ => false,
+    }
 }
 
-    println!("Successfully created {} player records", aggregated_players.len());
-    println!();
+// one run of the command
+fn run(matches: &ArgMatches, inputs: &mut Inputs) -> error::Result<()> {
+    let started = Instant::now();
+    let command_name = matches.subcommand_name().unwrap_or("none").to_string();
+    let cli = Cli::from_arg_matches(matches).map_err(|e| error::Error::Usage(e.to_string()))?;
+    let usage_log = cli.usage_log;
 
-// reading the command line arguments  
-#[derive(Parser)]
-#[command(name = "baseball-stats")]
-#[command(about = "A CLI tool for analyzing baseball statistics")]
-struct Cli {
-    #[command(subcommand)]
-    command: Option<Commands>,
-}
+    let dataset = if needs_no_data(&cli) { Dataset::empty(&cli)? } else { load_dataset(&cli, inputs)? };
 
-// function for truncating text
-fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+    // every command that's asked about a player by name looks him up here
+    let player_index = lookup::PlayerIndex::build(&dataset.aggregated_players, dataset.clean_records(), &dataset.mlb_records, &dataset.supplementary);
+
+    run_command(cli, &dataset, &player_index)?;
+
+    // the usage journal, if it's on (looking at the journal doesn't count as a run)
+    if usage::enabled(usage_log) && command_name != "stats-usage" {
+        let entry = usage::UsageEntry::new(&command_name, started.elapsed(), dataset.clean_records().len(), dataset.aggregated_players.len());
+        if let Err(e) = usage::record(&entry) {
+            warn!("couldn't write the usage journal: {}", e);
+        }
     }
-}
 
+    Ok(())
+}
 
-// define the available commands
-#[derive(Subcommand)]
-enum Commands {
-    /// show home run records
-    Homeruns,
-    /// show season records 
-    Seasons,
-    /// show career records
-    Careers,
+// --ties, --missing and --prorate-short-seasons, for the loader and every report (and the season lengths
+// first, since the loader works out each season's leaders with them)
+fn options_for(cli: &Cli) -> error::Result<options::Options> {
+    let mut season_lengths = schedule::SeasonLengths::default();
+    if let Some(path) = &cli.season_lengths {
+        season_lengths = schedule::SeasonLengths::load(path)?;
+        info!("Loaded {} season lengths from {}", season_lengths.len(), path);
+    }
+    Ok(options::Options { ties: cli.ties, missing: cli.missing, prorate: cli.prorate_short_seasons, season_lengths })
 }
 
+// read the data and everything that goes with it, the way the command line asks
+fn load_dataset(cli: &Cli, inputs: &mut Inputs) -> error::Result<Dataset> {
+    let options = options_for(cli)?;
 
-    // handle the command line argument
-    match cli.command {
-        Some(Commands::Homeruns) => {
+    let file_path = DATA_FILE;
 
-            // create top 10 home run seasons
-            println!();
+    let report = cached(&mut inputs.seasons, inputs.keep, file_path, || {
+        info!("Loading baseball data...");
+        data::load_seasons_checked(file_path, cli.duplicates, &options)
+    })?;
+    let mut rejected = report.rejected();
+    let mut mlb_records = report.records;
 
-            // sort players by home runs (highest first)
-            let mut sorted_by_homeruns = clean_records.clone();
-            sorted_by_homeruns.sort_by(|a, b| b.homeruns.cmp(&a.homeruns));
-
-            // take the top 10
-            let top_10_homeruns = &sorted_by_homeruns[0..10];
-
-            // display the results
-            println!("\nTop 10 home runs in a season:");
-            println!("{:<4} {:<15} {:<15} {:<6} {:<8} {:<3}", "Rank", "First Name", "Last Name", "Team", "Season", "HR");
-            println!("{}", "-".repeat(60));
-
-            for (i, player) in top_10_homeruns.iter().enumerate() {
-                let first_name = player.first_name.as_deref().unwrap_or("N/A");
-                println!("{:<4} {:<15} {:<15} {:<6} {:<8} {:<3}", 
-                        i + 1, 
-                        first_name, 
-                        player.last_name, 
-                        player.team, 
-                        player.season, 
-                        player.homeruns);
-            }
-            
-            
-            
-            // create top 10 homerun career
-            println!();
+    // load any supplementary (non-MLB) files, tagged with their league
+    let mut supplementary = Vec::new();
 
-            // sort players by homeruns (highest first)
-            let mut sorted_career_by_homeruns = aggregated_players.clone();
-            sorted_career_by_homeruns.sort_by(|a, b| b.total_homeruns.cmp(&a.total_homeruns));
+    for arg in &cli.supplementary {
+        let (league, path) = data::parse_supplementary_arg(arg);
 
-            // take the top 10
-            let top_10_career_homeruns = &sorted_career_by_homeruns[0..10];
+        let (rows, rows_report) = cached(&mut inputs.supplementary, inputs.keep, &path, || {
+            error::check_exists("supplementary file", &path)?;
+            info!("Loading supplementary {} data from {}...", league, path);
+            data::load_supplementary(&league, &path, cli.duplicates, &options)
+        })?;
+        supplementary.extend(rows);
+        rejected.extend(rows_report.rejected());
+    }
 
-            // display the results
-            println!("\nTop 10 homeruns in a career:");
-            println!();
-            println!("{:<4} {:<15} {:<15} {:<6} {:<6} {:<6} {:<3}", "Rank", "First Name", "Last Name", "From", "To", "Total", "Home runs");
-            println!("{}", "-".repeat(67));
-
-            for (i, player) in top_10_career_homeruns.iter().enumerate() {
-                println!("{:<4} {:<15} {:<15} {:<6} {:<6} {:<6} {:<3}", 
-                        i + 1, 
-                        player.first_name, 
-                        player.last_name, 
-                        player.first_season, 
-                        player.last_season,
-                        player.seasons_played,
-                        player.total_homeruns);
+    // only the leagues asked for (each row's league leaders and team totals are already worked out, so
+    // leaving the rest out doesn't change them)
+    if !cli.league.is_empty() {
+        let leagues: BTreeSet<&str> = mlb_records.iter().chain(supplementary.iter().map(|s| &s.record)).filter_map(|s| s.league.as_deref()).collect();
+        if let Some(unknown) = cli.league.iter().find(|wanted| !leagues.iter().any(|league| league.eq_ignore_ascii_case(wanted))) {
+            let known = if leagues.is_empty() {
+                format!("{} has no league column", file_path)
+            } else {
+                format!("the data has {}", leagues.into_iter().collect::<Vec<&str>>().join(", "))
+            };
+            return Err(error::Error::Usage(format!("--league {}: no rows are from that league ({})", unknown, known)));
+        }
+        mlb_records.retain(|s| data::in_leagues(s, &cli.league));
+        supplementary.retain(|s| data::in_leagues(&s.record, &cli.league));
+        info!("Kept {} rows from {}", mlb_records.len() + supplementary.len(), cli.league.join(", "));
     }
-            
 
+    // old team codes get the franchise they belong to now (MON -> WSH), unless asked not to
+    if !cli.raw_teams {
+        let mut franchise_map = franchises::FranchiseMap::builtin();
 
+        if let Some(path) = &cli.franchise_map {
+            let count = franchise_map.load_overrides(path)?;
+            info!("Loaded {} franchise mappings from {}", count, path);
         }
-        Some(Commands::Seasons) => {
-            
-            // create top 10 hit seasons
-            println!();
 
-            // sort players by hits (highest first)
-            let mut sorted_by_hits = clean_records.clone();
-            sorted_by_hits.sort_by(|a, b| b.hits.cmp(&a.hits));
-
-            // take the top 10
-            let top_10_hits = &sorted_by_hits[0..10];
-
-            // display the results
-            println!("\nTop 10 most hits in a season:");
-            println!("{:<4} {:<15} {:<15} {:<6} {:<8} {:<3}", "Rank", "First Name", "Last Name", "Team", "Season", "Hits");
-            println!("{}", "-".repeat(60));
-
-            for (i, player) in top_10_hits.iter().enumerate() {
-                let first_name = player.first_name.as_deref().unwrap_or("N/A");
-                println!("{:<4} {:<15} {:<15} {:<6} {:<8} {:<3}", 
-                        i + 1, 
-                        first_name, 
-                        player.last_name, 
-                        player.team, 
-                        player.season, 
-                        player.hits);
-            }
+        franchise_map.assign(&mut mlb_records);
+        for s in &mut supplementary {
+            franchise_map.assign(std::slice::from_mut(&mut s.record));
+        }
+    }
 
-            // create top 10 homerun seasons
-            println!();
+    // extra columns joined on from other files, on the seasons now and on the careers once they're added up
+    let join_key = join::JoinKey::parse(&cli.join_key)?;
+    let mut join_files = Vec::new();
+    for path in &cli.join {
+        let join_file = cached(&mut inputs.joins, inputs.keep, path, || join::JoinFile::load(path, join_key))?;
+        info!("Joined {} from {}", join_file.columns().join(", "), path);
+        join_file.apply_to_seasons(&mut mlb_records);
+        join_files.push(join_file);
+    }
 
-            // sort players by homeruns (highest first)
-            let mut sorted_by_homeruns = clean_records.clone();
-            sorted_by_homeruns.sort_by(|a, b| b.homeruns.cmp(&a.homeruns));
-
-            // take the top 10
-            let top_10_homeruns = &sorted_by_homeruns[0..10];
-
-            // display the results
-            println!("\nTop 10 most homeruns in a season:");
-            println!("{:<4} {:<15} {:<15} {:<6} {:<8} {:<3}", "Rank", "First Name", "Last Name", "Team", "Season", "HRs");
-            println!("{}", "-".repeat(60));
-
-            for (i, player) in top_10_homeruns.iter().enumerate() {
-                let first_name = player.first_name.as_deref().unwrap_or("N/A");
-                println!("{:<4} {:<15} {:<15} {:<6} {:<8} {:<3}", 
-                        i + 1, 
-                        first_name, 
-                        player.last_name, 
-                        player.team, 
-                        player.season, 
-                        player.homeruns);
-            }
+    // first and second half lines, if there's a file of them
+    let mut half_splits = Vec::new();
+    if let Some(path) = &cli.splits {
+        half_splits = cached(&mut inputs.splits, inputs.keep, path, || splits::load_splits(path))?;
+        info!("Loaded {} half season splits from {}", half_splits.len(), path);
+    }
 
-            // create top 10 walk seasons
-            println!();
+    // supplementary rows have to hang off a player we already know about
+    if !supplementary.is_empty() {
+        let known_links: HashSet<&str> = mlb_records.iter().map(|s| s.link.as_ref()).collect();
+        let unlinked = supplementary
+            .iter()
+            .filter(|s| !known_links.contains(s.record.link.as_ref()))
+            .count();
 
-            // sort players by hits (highest first)
-            let mut sorted_by_walks = clean_records.clone();
-            sorted_by_walks.sort_by(|a, b| b.walks.cmp(&a.walks));
-
-            // take the top 10
-            let top_10_walks = &sorted_by_walks[0..10];
-
-            // display the results
-            println!("\nTop 10 most walks in a season:");
-            println!("{:<4} {:<15} {:<15} {:<6} {:<8} {:<3}", "Rank", "First Name", "Last Name", "Team", "Season", "Walks");
-            println!("{}", "-".repeat(60));
-
-            for (i, player) in top_10_walks.iter().enumerate() {
-                let first_name = player.first_name.as_deref().unwrap_or("N/A");
-                println!("{:<4} {:<15} {:<15} {:<6} {:<8} {:<3}", 
-                        i + 1, 
-                        first_name, 
-                        player.last_name, 
-                        player.team, 
-                        player.season, 
-                        player.walks);
-            }
+        if unlinked > 0 {
+            warn!("{} supplementary rows don't match any player in the MLB data", unlinked);
         }
-        Some(Commands::Careers) => {
-            
-            // create top 10 games played
-            println!();
+    }
 
-            // sort players by homeruns (highest first)
-            let mut sorted_career_by_games = aggregated_players.clone();
-            sorted_career_by_games.sort_by(|a, b| b.total_games_played.cmp(&a.total_games_played));
-
-            // take the top 10
-            let top_10_career_games = &sorted_career_by_games[0..10];
-
-            // display the results
-            println!("\nTop 10 games played in a career:");
-            println!("{:<4} {:<15} {:<15} {:<20} {:<6} {:<6} {:<3}", "Rank", "First Name", "Last Name", "Teams", "From", "To", "Games");
-            println!("{}", "-".repeat(77));
-
-            for (i, player) in top_10_career_games.iter().enumerate() {
-                println!("{:<4} {:<15} {:<15} {:<20} {:<6} {:<6} {:<3}", 
-                        i + 1, 
-                        player.first_name, 
-                        player.last_name, 
-                        format!("{} ({})", truncate_string(&player.teams, 16), player.team_count),
-                        player.first_season, 
-                        player.last_season,
-                        player.total_games_played);
-            }
+    // MLB records only, unless asked to fold the supplementary seasons in
+    let combined = if cli.include_supplementary && !supplementary.is_empty() {
+        info!("Including {} supplementary rows in records", supplementary.len());
+        let mut combined = mlb_records.clone();
+        combined.extend(supplementary.iter().map(|s| s.record.clone()));
+        Some(combined)
+    } else {
+        None
+    };
 
+    let mut aggregated_players = careers::aggregate_players(combined.as_deref().unwrap_or(&mlb_records), &options);
+    for join_file in &join_files {
+        join_file.apply_to_players(&mut aggregated_players);
+    }
 
-            // create top 10 hits
-            println!();
+    Ok(Dataset { mlb_records, supplementary, combined, rejected, half_splits, aggregated_players, options })
+}
+
+// run one command on the loaded data, writing where its options say
+fn run_command(cli: Cli, dataset: &Dataset, player_index: &lookup::PlayerIndex) -> error::Result<()> {
+    let clean_records = dataset.clean_records();
+    let aggregated_players = &dataset.aggregated_players;
+    let options = &dataset.options;
+
+    // everything from here on prints through the table renderer, to the screen or to files
+    let format = cli
+        .format
+        .or_else(|| cli.out.as_deref().and_then(table::Format::from_path))
+        .unwrap_or(if matches!(cli.command, Some(Commands::Report { .. })) {
+            table::Format::Markdown  // the almanac is meant to be shared, so it's a document by default
+        } else {
+            table::Format::Text
+        });
+
+    // the quiz asks and answers on the screen, so there's nothing to write to a file
+    if matches!(cli.command, Some(Commands::Quiz { .. })) && (cli.out.is_some() || cli.out_dir.is_some()) {
+        return Err(error::Error::Usage("the quiz is played on the screen, so it can't go to --out or --out-dir".to_string()));
+    }
+    // a batch's reports each say where they go
+    if matches!(cli.command, Some(Commands::Batch { .. })) && (cli.out.is_some() || cli.out_dir.is_some()) {
+        return Err(error::Error::Usage("each report in a batch says where it goes (out = \"...\"), so the batch itself can't take --out or --out-dir".to_string()));
+    }
+    // the schema is its own json document, not a table
+    if matches!(cli.command, Some(Commands::Schema { .. })) && (cli.out.is_some() || cli.out_dir.is_some()) {
+        return Err(error::Error::Usage("the schema goes to the screen, or to a file with schema <path>, not --out or --out-dir".to_string()));
+    }
+
+    if cli.jobs == Some(0) {
+        return Err(error::Error::Usage("--jobs needs at least one".to_string()));
+    }
 
-            // sort players by homeruns (highest first)
-            let mut sorted_career_by_hits = aggregated_players.clone();
-            sorted_career_by_hits.sort_by(|a, b| b.total_hits.cmp(&a.total_hits));
-
-            // take the top 10
-            let top_10_career_hits = &sorted_career_by_hits[0..10];
-
-            // display the results
-            println!("\nTop 10 most hits in a career:");
-            println!("{:<4} {:<15} {:<15} {:<20} {:<6} {:<6} {:<3}", "Rank", "First Name", "Teams", "Last Name", "From", "To", "Hits");
-            println!("{}", "-".repeat(76));
-
-            for (i, player) in top_10_career_hits.iter().enumerate() {
-                println!("{:<4} {:<15} {:<15} {:<20} {:<6} {:<6} {:<3}", 
-                        i + 1, 
-                        player.first_name, 
-                        player.last_name, 
-                        format!("{} ({})", truncate_string(&player.teams, 16), player.team_count),
-                        player.first_season, 
-                        player.last_season,
-                        player.total_hits);
+    // a workbook can't go to the screen
+    if format == table::Format::Xlsx && cli.out.is_none() && cli.out_dir.is_none() {
+        return Err(error::Error::Usage("xlsx output needs a file: add --out <file>.xlsx or --out-dir <folder>".to_string()));
+    }
+
+    let mut out = match (&cli.out, &cli.out_dir) {
+        (Some(path), _) => table::Output::to_file(path, format, cli.wrap)?,
+        (None, Some(dir)) => table::Output::to_dir(dir, format, cli.wrap, cli.jobs.unwrap_or_else(table::default_jobs))?,
+        (None, None) => table::Output::stdout(format, cli.wrap),
+    };
+
+    // handle the command line argument
+    match cli.command {
+        Some(Commands::Homeruns) => {
+            leaderboards::show_homeruns(&mut out, clean_records, aggregated_players, cli.top, options);
+        }
+        Some(Commands::Seasons) => {
+            leaderboards::show_seasons(&mut out, clean_records, cli.top, options);
+        }
+        Some(Commands::Careers) => {
+            leaderboards::show_careers(&mut out, aggregated_players, cli.top, options);
+        }
+        Some(Commands::Top { args }) => {
+            leaders::show_leaders(&mut out, &args, false, cli.top, clean_records, aggregated_players, options)?;
+        }
+        Some(Commands::Bottom { args }) => {
+            leaders::show_leaders(&mut out, &args, true, cli.top, clean_records, aggregated_players, options)?;
+        }
+        // the shortcuts all go through the same leaderboard as top
+        Some(Commands::Hr { args }) => {
+            leaders::show_leaders(&mut out, &args.for_stat("homeruns"), false, cli.top, clean_records, aggregated_players, options)?;
+        }
+        Some(Commands::Avg { args }) => {
+            leaders::show_leaders(&mut out, &args.for_stat("batting_average"), false, cli.top, clean_records, aggregated_players, options)?;
+        }
+        Some(Commands::Rbi { args }) => {
+            leaders::show_leaders(&mut out, &args.for_stat("rbi"), false, cli.top, clean_records, aggregated_players, options)?;
+        }
+        Some(Commands::Sb { args }) => {
+            leaders::show_leaders(&mut out, &args.for_stat("stolen_bases"), false, cli.top, clean_records, aggregated_players, options)?;
+        }
+        Some(Commands::Hits { args }) => {
+            leaders::show_leaders(&mut out, &args.for_stat("hits"), false, cli.top, clean_records, aggregated_players, options)?;
+        }
+        Some(Commands::Speed { args }) => {
+            leaders::show_leaders(&mut out, &args.for_stat("speed"), false, cli.top, clean_records, aggregated_players, options)?;
+        }
+        Some(Commands::Tto) => {
+            tto::show_tto(&mut out, clean_records, aggregated_players, cli.top, options)?;
+        }
+        Some(Commands::ByPosition) => {
+            positions::show_by_position(&mut out, aggregated_players);
+        }
+        Some(Commands::Halves { name, season }) => {
+            splits::show_halves(&mut out, &name, season, &dataset.half_splits, player_index)?;
+        }
+        Some(Commands::Surges { min_ab, fades }) => {
+            splits::show_surges(&mut out, min_ab, fades, cli.top, &dataset.half_splits, aggregated_players, options)?;
+        }
+        Some(Commands::Percentile { name, season, min_ab }) => {
+            percentile::show_percentiles(&mut out, &name, season, min_ab, clean_records, player_index, options)?;
+        }
+        Some(Commands::Hist { stat, season, bin_width, min_ab }) => {
+            hist::show_histogram(&mut out, &stat, season, bin_width, min_ab, clean_records, options)?;
+        }
+        Some(Commands::AgingCurve { stat, birthdates, min_ab, min_seasons, chart }) => {
+            let birthdates_by_link = aging::load_birthdates(&birthdates)?;
+            info!("Loaded {} birthdates from {}", birthdates_by_link.len(), birthdates);
+            aging::show_aging_curve(&mut out, &stat, &birthdates_by_link, min_ab, min_seasons, chart, clean_records, options)?;
+        }
+        Some(Commands::LeadersAsof { season, stat, min_ab }) => {
+            asof::show_leaders_as_of(&mut out, &stat, season, cli.top, min_ab, clean_records, options)?;
+        }
+        Some(Commands::Hof { by }) => {
+            hof::show_hof(&mut out, by, cli.top, clean_records, aggregated_players, options);
+        }
+        Some(Commands::Timeline { name }) => {
+            timeline::show_timeline(&mut out, &name, player_index)?;
+        }
+        Some(Commands::Player { name }) => {
+            player::show_player(&mut out, &name, player_index, &dataset.mlb_records, options)?;
+        }
+        Some(Commands::Search { query, fuzzy, limit }) => {
+            search::search(&mut out, &query, fuzzy, limit, aggregated_players)?;
+        }
+        Some(Commands::Summary) => {
+            summary::show_summary(&mut out, clean_records, aggregated_players, &dataset.supplementary, &dataset.rejected);
+        }
+        Some(Commands::Report { args }) => {
+            report::show_report(&mut out, &args, clean_records, aggregated_players, cli.top, options);
+        }
+        Some(Commands::Roster { team, season, by }) => {
+            roster::show_roster(&mut out, &team, season, &by, clean_records, options)?;
+        }
+        Some(Commands::OneMan { stat, min_ab }) => {
+            teams::show_one_man(&mut out, &stat, min_ab, cli.top, clean_records, options)?;
+        }
+        Some(Commands::VersusTeams { team_a, team_b, season, by }) => {
+            versus::show_versus(&mut out, &team_a, &team_b, season, &by, cli.top, clean_records, options)?;
+        }
+        Some(Commands::StatsUsage { clear }) => {
+            if clear {
+                let cleared = usage::clear()?;
+                out.line(if cleared { "Usage journal deleted" } else { "There's no usage journal to delete" });
+            } else {
+                usage::show_usage(&mut out, &usage::load()?, cli.top);
+            }
+        }
+        Some(Commands::Franchises) => {
+            franchises::show_franchises(&mut out, clean_records);
+        }
+        Some(Commands::Export) => {
+            export::show_export(&mut out, clean_records, aggregated_players, cli.top, options);
+        }
+        Some(Commands::ExportCareers { path }) => {
+            let count = career_export::export_careers(&path, aggregated_players)?;
+            out.line(&format!("Wrote {} careers to {} (schema version {})", count, path, career_export::CAREER_SCHEMA_VERSION));
+        }
+        Some(Commands::Batch { path }) => {
+            // every report is turned into a command line and checked before the first one runs
+            let definition = build_cli();
+            let mut reports = Vec::new();
+            for report in batch::load_batch(&path, &definition)? {
+                let matches = definition.clone().try_get_matches_from(&report.args).map_err(|e| error::Error::Usage(format!("{} ({}): {}", path, report.title, e)))?;
+                let report_cli = Cli::from_arg_matches(&matches).map_err(|e| error::Error::Usage(e.to_string()))?;
+                reports.push((report.title, report_cli));
             }
 
-            // create top 10 homeruns
-            println!();
+            let total = reports.len();
+            let mut failures = Vec::new();
+            for (number, (title, report_cli)) in reports.into_iter().enumerate() {
+                info!("Report {} of {}: {}", number + 1, total, title);
+                if let Err(e) = run_command(report_cli, dataset, player_index) {
+                    error!("{} failed: {}", title, e);
+                    failures.push((title, e.exit_code()));
+                }
+            }
 
-            // sort players by homeruns (highest first)
-            let mut sorted_career_by_homeruns = aggregated_players.clone();
-            sorted_career_by_homeruns.sort_by(|a, b| b.total_homeruns.cmp(&a.total_homeruns));
-
-            // take the top 10
-            let top_10_career_homeruns = &sorted_career_by_homeruns[0..10];
-
-            // display the results
-            println!("\nTop 10 most homeruns in a career:");
-            println!("{:<4} {:<15} {:<15} {:<20} {:<6} {:<6} {:<3}", "Rank", "First Name", "Teams", "Last Name", "From", "To", "HRs");
-            println!("{}", "-".repeat(75));
-
-            for (i, player) in top_10_career_homeruns.iter().enumerate() {
-                println!("{:<4} {:<15} {:<15} {:<20} {:<6} {:<6} {:<3}", 
-                        i + 1, 
-                        player.first_name, 
-                        player.last_name,
-                        format!("{} ({})", truncate_string(&player.teams, 16), player.team_count),
-                        player.first_season, 
-                        player.last_season,
-                        player.total_homeruns);
+            if let Some(&(_, code)) = failures.first() {
+                let failed = failures.into_iter().map(|(title, _)| title).collect();
+                return Err(error::Error::Batch { failed, total, code });
             }
+            info!("Ran {} reports from {}", total, path);
+        }
+        Some(Commands::Schema { path }) => {
+            let document = schema::schema_document(&build_cli(), clean_records, aggregated_players);
+            let text = serde_json::to_string_pretty(&document)?;
+            match path {
+                Some(path) => {
+                    std::fs::write(&path, format!("{}\n", text))?;
+                    out.line(&format!("Wrote the schema to {} (schema version {})", path, schema::SCHEMA_VERSION));
+                }
+                None => println!("{}", text),
+            }
+        }
+        Some(Commands::Matrix { stat, players }) => {
+            matrix::show_matrix(&mut out, &stat, &players, player_index, clean_records, aggregated_players)?;
+        }
+        Some(Commands::Quiz { questions, seed }) => {
+            let mut rng = seed.map(fastrand::Rng::with_seed).unwrap_or_default();
+            let questions = quiz::make_questions(questions, &mut rng, clean_records, aggregated_players, options);
+            if questions.is_empty() {
+                out.line("There isn't enough data to make up any questions");
+            } else {
+                quiz::run_quiz(&questions, &mut std::io::stdin().lock(), &mut std::io::stdout())?;
+            }
+        }
+        Some(Commands::Random { fact, min_ab, seed }) => {
+            let mut rng = seed.map(fastrand::Rng::with_seed).unwrap_or_default();
+            if fact {
+                random::show_fact(&mut out, &mut rng, clean_records, aggregated_players, options);
+            } else {
+                random::show_random_player(&mut out, &mut rng, min_ab, aggregated_players);
+            }
+        }
+        Some(Commands::Verify { reference }) => {
+            verify::verify(&mut out, &reference, clean_records, aggregated_players, options)?;
+        }
+        Some(Commands::Diff { old, new }) => {
+            diff::show_diff(&mut out, &old, &new, cli.duplicates, options)?;
         }
 
         None => {
@@ -546,14 +996,46 @@ enum Commands {
             println!();
             println!("Available commands:");
             println!("  homeruns  - Show home run records (single season and career)");
-            println!("  seasons   - Show single season records");  
+            println!("  seasons   - Show single season records");
             println!("  careers   - Show career records");
+            println!("  top       - Rank any stat, best first (--stat, --scope)");
+            println!("  bottom    - Rank any stat, worst first");
+            println!("  hr, avg, rbi, sb, hits - Shortcuts for the most asked-for leaderboards");
+            println!("  speed     - Bill James speed score leaders");
+            println!("  tto       - Three true outcomes trend and leaders");
+            println!("  by-position - Players and leaders by primary position");
+            println!("  halves    - A player's season by first and second half (needs --splits)");
+            println!("  surges    - Biggest second half jumps in OPS (needs --splits)");
+            println!("  hist      - Histogram of a stat across player seasons");
+            println!("  aging-curve - A stat by player age across the league (needs --birthdates)");
+            println!("  hof       - Hall of Fame Monitor and Black Ink scores");
+            println!("  leaders-asof - Career leaders among active players after a season");
+            println!("  percentile - Where a player's season ranked among qualified hitters");
+            println!("  player    - Show a player's page");
+            println!("  timeline  - A player's career team by team");
+            println!("  search    - Find players by name");
+            println!("  summary   - Describe the loaded dataset");
+            println!("  report    - Write the whole almanac as markdown or html");
+            println!("  export    - Export seasons, careers and leaderboards (e.g. to .xlsx)");
+            println!("  export-careers - Write every career to csv or json with a versioned schema");
+            println!("  verify    - Check computed leaders against a reference file");
+            println!("  diff      - What changed between two versions of the season file");
+            println!("  batch     - Run a file of reports on one load of the data");
+            println!("  schema    - Describe every stat and command option as json, for front ends");
+            println!("  matrix    - A few players' careers against each other in one stat");
+            println!("  quiz      - A multiple choice quiz made up from the data");
+            println!("  random    - A random player's career card, or a random record with --fact");
+            println!("  roster    - Every hitter on a team in one season");
+            println!("  one-man   - Biggest shares of a team's home runs, runs or hits");
+            println!("  versus-teams - Compare two teams' offense in one season");
+            println!("  franchises - List franchises and the team codes they've used");
+            println!("  stats-usage - What's been run and how long it took (with --usage-log)");
             println!();
             println!("Usage: cargo run -- <command>");
             println!("For more help: cargo run -- --help");
         }
     }
 
-
+    out.finish()?;
     Ok(())
-}
\ No newline at end of file
+}
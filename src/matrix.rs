@@ -0,0 +1,107 @@
+// a handful of careers against each other in one stat: each player's value, then a grid of every pair
+// with who's ahead and by how much, to settle a "who was better" argument in one go
+// (a grid is a table like any other, so --format csv or --out writes it for a spreadsheet)
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::join::joined_stat_name;
+use crate::error::{Error, Result};
+use crate::lookup::{player_not_found, PlayerIndex};
+use crate::stats::{career_value, format_stat_value, has_career_value, higher_is_better, normalize_stat_name, unknown_stat};
+use crate::table::{Output, Table};
+
+fn full_name(player: &AggregatedPlayer) -> String {
+    format!("{} {}", player.first_name, player.last_name)
+}
+
+// a difference with its sign, in the stat's own format ("+.052", "-120", "+1.5%")
+fn signed(stat: &str, difference: f64) -> String {
+    let sign = if difference > 0.0 { "+" } else if difference < 0.0 { "-" } else { "" };
+    format!("{}{}", sign, format_stat_value(stat, difference.abs()))
+}
+
+// the matrix command: players is a comma separated list of names or links
+pub fn show_matrix(out: &mut Output, stat: &str, players_arg: &str, players: &PlayerIndex, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer]) -> Result<()> {
+    // one of our stats, or a column joined on from another file
+    let joined_name;
+    let stat: &str = match normalize_stat_name(stat) {
+        Some(name) if has_career_value(name) => name,
+        Some(name) => return Err(Error::Usage(format!("{} isn't available for careers yet", name))),
+        None => {
+            joined_name = joined_stat_name(stat, clean_records, aggregated_players).ok_or_else(|| unknown_stat(stat))?;
+            &joined_name
+        }
+    };
+
+    // each name to a player; a name more than one player had goes to the one with the most at bats, with a note
+    let mut picked: Vec<&AggregatedPlayer> = Vec::new();
+    for query in players_arg.split(',').map(str::trim).filter(|q| !q.is_empty()) {
+        let matches = players.find(query);
+        let &player = matches.iter().max_by_key(|p| p.total_at_bats).ok_or_else(|| player_not_found(query))?;
+        if matches.len() > 1 {
+            out.line(&format!(
+                "\"{}\" matches {} players, so it's the one with the most at bats, {} ({}-{}); use a link ({}) for another",
+                query,
+                matches.len(),
+                full_name(player),
+                player.first_season,
+                player.last_season,
+                matches.iter().filter(|p| p.link != player.link).map(|p| p.link.as_str()).collect::<Vec<&str>>().join(", "),
+            ));
+        }
+        if !picked.iter().any(|p| p.link == player.link) {
+            picked.push(player);
+        }
+    }
+    if picked.len() < 2 {
+        return Err(Error::Usage("a matrix needs at least two different players (--players \"babe ruth,ted williams\")".to_string()));
+    }
+
+    let values: Vec<Option<f64>> = picked.iter().map(|p| career_value(stat, p)).collect();
+    let better = higher_is_better(stat);
+    // how far the row's player is ahead of the column's, positive when he's better (fewer, for strikeouts)
+    let ahead_by = |row: usize, column: usize| -> Option<f64> {
+        let difference = values[row]? - values[column]?;
+        Some(if better { difference } else { -difference })
+    };
+
+    let mut table = Table::new(format!("Career {}", stat))
+        .column("Player", 25)
+        .column("From", 6)
+        .column("To", 6)
+        .right("AB", 6)
+        .right(stat, stat.len().max(8))
+        .right("Leads", 8);
+    for (i, player) in picked.iter().enumerate() {
+        let leads = (0..picked.len()).filter(|&j| ahead_by(i, j).is_some_and(|d| d > 0.0)).count();
+        table.row(vec![
+            full_name(player),
+            player.first_season.to_string(),
+            player.last_season.to_string(),
+            player.total_at_bats.to_string(),
+            values[i].map(|v| format_stat_value(stat, v)).unwrap_or_else(|| "--".to_string()),
+            format!("{} of {}", leads, picked.len() - 1),
+        ]);
+    }
+    out.table(&table);
+
+    // the grid: each row's player against each column's, + when the row's player is ahead
+    let names: Vec<String> = picked.iter().map(|p| full_name(p)).collect();
+    let mut grid = Table::new(format!("Career {} head to head (row against column, + means the row's player is ahead)", stat)).column("Player", 25);
+    for name in &names {
+        grid = grid.right(name, name.len().clamp(8, 16));
+    }
+    for (i, name) in names.iter().enumerate() {
+        let mut row = vec![name.clone()];
+        for j in 0..picked.len() {
+            row.push(match ahead_by(i, j) {
+                _ if i == j => String::new(),
+                Some(difference) => signed(stat, difference),
+                None => "--".to_string(),
+            });
+        }
+        grid.row(row);
+    }
+    out.table(&grid);
+    Ok(())
+}
@@ -0,0 +1,143 @@
+// what a career total does with seasons that don't have a count (RBI for much of the 1880s, strikeouts
+// and caught stealing for long stretches before the 1950s): count them as zero, leave the career out of
+// anything built on that count, or estimate them from the league's rate that season
+// (--missing, Options::missing; CareerBuilder applies it as each career is added up)
+
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+
+use crate::careers::AggregatedPlayer;
+use crate::data::{league_season, CleanPlayerSeason, LeagueSeason};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum MissingPolicy {
+    /// a season without the count adds nothing to the career total
+    #[default]
+    Zero,
+    /// careers with a season missing the count are left out of the career lists built on it
+    Skip,
+    /// a season without the count gets the league's rate per at bat that season (zero if nobody that season has it)
+    Estimate,
+}
+
+type OptionalCount = fn(&CleanPlayerSeason) -> Option<u32>;
+
+// the counts that can be missing, with how to get each from a season
+pub const OPTIONAL_COUNTS: &[(&str, OptionalCount)] = &[
+    ("rbi", |s| s.rbi),
+    ("strikeouts", |s| s.strikeouts),
+    ("stolen_bases", |s| s.stolen_bases),
+    ("caught_stealing", |s| s.caught_stealing),
+];
+
+// the career stats built on those counts, and which ones
+const COUNTS_USED: &[(&str, &[&str])] = &[
+    ("rbi", &["rbi"]),
+    ("strikeouts", &["strikeouts"]),
+    ("stolen_bases", &["stolen_bases"]),
+    ("caught_stealing", &["caught_stealing"]),
+    ("strikeout_rate", &["strikeouts"]),
+    ("tto", &["strikeouts"]),
+    ("speed", &["strikeouts", "stolen_bases", "caught_stealing"]),
+    ("baserunning_runs", &["stolen_bases", "caught_stealing"]),
+];
+
+// the counts some season of a career doesn't have (seasons without an at bat or a walk don't count,
+// since there was nothing to record)
+pub fn missing_counts(seasons: &[&CleanPlayerSeason]) -> Vec<&'static str> {
+    OPTIONAL_COUNTS
+        .iter()
+        .filter(|(_, count)| seasons.iter().any(|s| s.at_bats + s.walks > 0 && count(s).is_none()))
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+// with --missing skip, the career stats a career is left out of: the ones built on a count it's missing
+// (stats not built on an optional count never are)
+pub fn skipped_stats(missing: &[&'static str]) -> Vec<&'static str> {
+    COUNTS_USED
+        .iter()
+        .filter(|(_, counts)| counts.iter().any(|count| missing.contains(count)))
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+// the league's rate per at bat for each optional count, season by season (and league by league, when the
+// data has them), from the rows that have it
+pub struct LeagueRates {
+    by_season: HashMap<(LeagueSeason, &'static str), f64>,
+}
+
+impl LeagueRates {
+    pub fn build(clean_records: &[CleanPlayerSeason]) -> LeagueRates {
+        let mut totals: HashMap<(LeagueSeason, &'static str), (u64, u64)> = HashMap::new();
+        for season in clean_records {
+            for &(name, count) in OPTIONAL_COUNTS {
+                if let Some(value) = count(season) {
+                    let entry = totals.entry((league_season(season), name)).or_default();
+                    entry.0 += value as u64;
+                    entry.1 += season.at_bats as u64;
+                }
+            }
+        }
+
+        let by_season = totals
+            .into_iter()
+            .filter(|(_, (_, at_bats))| *at_bats > 0)
+            .map(|(key, (count, at_bats))| (key, count as f64 / at_bats as f64))
+            .collect();
+        LeagueRates { by_season }
+    }
+
+    // a season's estimate for a count it doesn't have: its at bats at the league's rate
+    pub fn estimate(&self, name: &'static str, season: &CleanPlayerSeason) -> f64 {
+        self.by_season.get(&(league_season(season), name)).map_or(0.0, |rate| rate * season.at_bats as f64)
+    }
+}
+
+// with --missing estimate, add the estimates for the seasons missing a count to the career's total
+pub fn add_estimates(player: &mut AggregatedPlayer, seasons: &[&CleanPlayerSeason], rates: &LeagueRates) {
+    for &(name, count) in OPTIONAL_COUNTS {
+        if !player.missing.contains(&name) {
+            continue;
+        }
+        let estimated: f64 = seasons.iter().filter(|s| count(s).is_none()).map(|s| rates.estimate(name, s)).sum();
+        let total = match name {
+            "rbi" => &mut player.total_rbi,
+            "strikeouts" => &mut player.total_strikeouts,
+            "stolen_bases" => &mut player.total_stolen_bases,
+            _ => &mut player.total_caught_stealing,
+        };
+        *total += estimated.round() as u32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::seasons_from_csv;
+
+    // 1885's rbi rate is Jones's alone (Smith doesn't have any); nobody has 1886's
+    const SEASONS: &str = "\
+1885,John,Smith,/player/1,1B,BSN,100,400,60,120,20,5,4,--,30,20,10,5,.300,.350,.420,.770
+1885,Tom,Jones,/player/2,SS,PRO,110,500,70,140,25,6,2,50,35,25,12,6,.280,.330,.380,.710
+1886,John,Smith,/player/1,1B,BSN,100,200,30,60,10,2,2,--,15,10,5,2,.300,.350,.420,.770
+";
+
+    #[test]
+    fn estimates_are_the_league_rate_that_season() {
+        let records = seasons_from_csv(SEASONS);
+        let rates = LeagueRates::build(&records);
+        assert!((rates.estimate("rbi", &records[0]) - 40.0).abs() < 1e-9);
+        assert!((rates.estimate("strikeouts", &records[0]) - 400.0 * 45.0 / 900.0).abs() < 1e-9);
+        // a season nobody has the count for estimates nothing
+        assert_eq!(rates.estimate("rbi", &records[2]), 0.0);
+    }
+
+    #[test]
+    fn skipped_stats_are_the_ones_built_on_a_missing_count() {
+        assert_eq!(skipped_stats(&["caught_stealing"]), vec!["caught_stealing", "speed", "baserunning_runs"]);
+        assert!(skipped_stats(&[]).is_empty());
+    }
+}
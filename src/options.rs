@@ -0,0 +1,24 @@
+// the command line settings that change how the numbers come out rather than what's shown: how ties
+// rank (--ties), what a career does with a season missing a count (--missing), and whether the season
+// qualifier shrinks for a short season (--prorate-short-seasons, with the lengths from --season-lengths);
+// handed to whatever ranks, adds up or qualifies seasons, and the defaults are what the tool does without
+// the options
+
+use crate::missing::MissingPolicy;
+use crate::ranking::TieBreak;
+use crate::schedule::SeasonLengths;
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub ties: TieBreak,
+    pub missing: MissingPolicy,
+    pub prorate: bool,
+    pub season_lengths: SeasonLengths,
+}
+
+impl Options {
+    // a season minimum in at bats, pro-rated to the season's length when asked to
+    pub fn prorated(&self, min_at_bats: u32, season: u32) -> u32 {
+        self.season_lengths.prorated(min_at_bats, season, self.prorate)
+    }
+}
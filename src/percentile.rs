@@ -0,0 +1,123 @@
+// where a player's season stands against everyone else's that year: for every stat, his rank among
+// qualified hitters, his percentile and his z-score (how many standard deviations from the average)
+
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::leaders::SEASON_QUALIFYING_AT_BATS;
+use crate::lookup::{player_not_found, PlayerIndex};
+use crate::options::Options;
+use crate::stats::{all_stats, format_stat_value, higher_is_better, season_value};
+use crate::table::{Output, Table};
+
+// one stat's spread across the qualified hitters of a season
+struct Distribution {
+    values: Vec<f64>,
+    mean: f64,
+    std_dev: f64,
+}
+
+impl Distribution {
+    // worked out when asked for, from the season's qualified rows that have the stat
+    fn for_season(stat: &str, season_rows: &[&CleanPlayerSeason]) -> Option<Distribution> {
+        let values: Vec<f64> = season_rows.iter().filter_map(|s| season_value(stat, s)).collect();
+        if values.len() < 2 {
+            return None;
+        }
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+
+        Some(Distribution { values, mean, std_dev: variance.sqrt() })
+    }
+
+    // how many did better (so rank is that plus one, ties sharing it), and the percentile:
+    // the share of the field he beat, with ties counted as half
+    fn rank_and_percentile(&self, stat: &str, value: f64) -> (usize, f64) {
+        let better = |other: f64| if higher_is_better(stat) { other > value } else { other < value };
+
+        let above = self.values.iter().filter(|&&v| better(v)).count();
+        let equal = self.values.iter().filter(|&&v| v == value).count();
+        let below = self.values.len() - above - equal;
+
+        let percentile = (below as f64 + equal as f64 / 2.0) / self.values.len() as f64 * 100.0;
+        (above + 1, percentile)
+    }
+
+    // standard deviations from the average, turned round for stats where lower is better so plus is always good
+    fn z_score(&self, stat: &str, value: f64) -> Option<f64> {
+        if self.std_dev == 0.0 {
+            return None;
+        }
+        let z = (value - self.mean) / self.std_dev;
+        Some(if higher_is_better(stat) { z } else { -z })
+    }
+}
+
+// the percentile command
+pub fn show_percentiles(out: &mut Output, query: &str, season: u32, min_ab: Option<u32>, clean_records: &[CleanPlayerSeason], players: &PlayerIndex, options: &Options) -> Result<()> {
+    let matches = players.find(query);
+    if matches.is_empty() {
+        return Err(player_not_found(query));
+    }
+
+    let min_at_bats = min_ab.unwrap_or_else(|| options.prorated(SEASON_QUALIFYING_AT_BATS, season));
+    let qualified: Vec<&CleanPlayerSeason> = clean_records
+        .iter()
+        .filter(|s| s.season == season && s.at_bats >= min_at_bats)
+        .collect();
+
+    if qualified.is_empty() {
+        return Err(Error::NotFound { what: "season", path: format!("{} with anyone at {} at bats", season, min_at_bats) });
+    }
+
+    let mut shown = 0;
+    for player in matches {
+        // a season split between teams has a row for each, and each is compared on its own (as in the leaderboards)
+        for row in players.season(&player.link, season) {
+            shown += 1;
+
+            let mut table = Table::new(format!(
+                "{} {}, {} {} against {} qualified hitters (min {} AB)",
+                player.first_name, player.last_name, season, row.team, qualified.len(), min_at_bats
+            ))
+            .column("Stat", 20)
+            .right("Value", 8)
+            .right("Rank", 10)
+            .right("Pctile", 6)
+            .right("Z", 5)
+            .right("Average", 8);
+
+            for stat in all_stats() {
+                let Some(value) = season_value(stat.name(), row) else {
+                    continue;
+                };
+                let Some(distribution) = Distribution::for_season(stat.name(), &qualified) else {
+                    continue;
+                };
+
+                let (rank, percentile) = distribution.rank_and_percentile(stat.name(), value);
+                table.row(vec![
+                    stat.name().to_string(),
+                    format_stat_value(stat.name(), value),
+                    format!("{} of {}", rank, distribution.values.len()),
+                    format!("{:.0}", percentile),
+                    distribution.z_score(stat.name(), value).map(|z| format!("{:+.1}", z)).unwrap_or_else(|| "--".to_string()),
+                    format_stat_value(stat.name(), distribution.mean),
+                ]);
+            }
+
+            out.table(&table);
+
+            if row.at_bats < min_at_bats {
+                out.line(&format!("({} at bats, short of the {} to qualify, so shown against the field without being part of it)", row.at_bats, min_at_bats));
+            }
+        }
+    }
+
+    if shown == 0 {
+        return Err(Error::NotFound { what: "season", path: format!("{} for \"{}\"", season, query) });
+    }
+    out.line("");
+    out.line("Pctile is the share of qualified hitters he did better than (ties count half); Z is standard deviations from the average, plus is better.");
+    Ok(())
+}
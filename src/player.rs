@@ -0,0 +1,260 @@
+// a single player's page: season by season lines, career totals, and any non-MLB seasons
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::careers::{AggregatedPlayer, CareerBuilder};
+use crate::chart::BarChart;
+use crate::data::{CleanPlayerSeason, SupplementarySeason};
+use crate::leader_counts::describe_times_led;
+use crate::error::Result;
+use crate::lookup::{player_not_found, PlayerIndex};
+use crate::options::Options;
+use crate::stats::format_rate;
+use crate::table::{Output, Table};
+
+// missing values show up as "--", same as in the source data
+fn show_optional<T: Display>(value: Option<T>) -> String {
+    match value {
+        Some(v) => v.to_string(),
+        None => "--".to_string(),
+    }
+}
+
+// an empty season-by-season table (the totals row goes under the same headings)
+fn season_table(title: &str) -> Table {
+    Table::new(title)
+        .column("Season", 10)
+        .column("Team", 6)
+        .column("Pos", 4)
+        .right("G", 4)
+        .right("AB", 5)
+        .right("R", 4)
+        .right("H", 4)
+        .right("2B", 4)
+        .right("3B", 4)
+        .right("HR", 4)
+        .right("RBI", 4)
+        .right("BB", 4)
+        .right("SO", 4)
+        .right("SB", 4)
+        .right("CS", 4)
+        .right("AVG", 5)
+        .right("OBP", 5)
+        .right("SLG", 5)
+        .right("OPS", 5)
+}
+
+// one season's stat line
+fn add_season_line(table: &mut Table, label: &str, season: &CleanPlayerSeason) {
+    table.row(vec![
+        label.to_string(),
+        season.team.to_string(),
+        season.position.to_string(),
+        season.games_played.to_string(),
+        season.at_bats.to_string(),
+        season.runs.to_string(),
+        season.hits.to_string(),
+        season.doubles.to_string(),
+        season.triples.to_string(),
+        season.homeruns.to_string(),
+        show_optional(season.rbi),
+        season.walks.to_string(),
+        show_optional(season.strikeouts),
+        show_optional(season.stolen_bases),
+        show_optional(season.caught_stealing),
+        format_rate(season.batting_average),
+        show_optional(season.on_base_percentage.map(format_rate)),
+        format_rate(season.slugging_percentage),
+        show_optional(season.on_base_plus_slugging.map(format_rate)),
+    ]);
+}
+
+// the career totals row underneath a season table
+// (the rates are the career's own, from its totals, not an average of the seasons')
+fn add_totals_line(table: &mut Table, label: &str, career: &AggregatedPlayer) {
+    table.separator();
+    table.row(vec![
+        label.to_string(),
+        String::new(),
+        String::new(),
+        career.total_games_played.to_string(),
+        career.total_at_bats.to_string(),
+        career.total_runs.to_string(),
+        career.total_hits.to_string(),
+        career.total_doubles.to_string(),
+        career.total_triples.to_string(),
+        career.total_homeruns.to_string(),
+        career.total_rbi.to_string(),
+        career.total_walks.to_string(),
+        career.total_strikeouts.to_string(),
+        career.total_stolen_bases.to_string(),
+        career.total_caught_stealing.to_string(),
+        show_optional(career.batting_average.map(format_rate)),
+        show_optional(career.on_base_percentage.map(format_rate)),
+        show_optional(career.slugging_percentage.map(format_rate)),
+        show_optional(career.ops.map(format_rate)),
+    ]);
+}
+
+// bar charts of a career year by year (only html output draws these)
+fn show_trajectory(out: &mut Output, mlb_seasons: &[&CleanPlayerSeason]) {
+    // a season split between teams is one bar, so add the rows for each year together
+    let mut years: Vec<(u32, u32, u32, u32)> = Vec::new();  // (season, at bats, hits, home runs)
+    for season in mlb_seasons {
+        match years.last_mut() {
+            Some(year) if year.0 == season.season => {
+                year.1 += season.at_bats;
+                year.2 += season.hits;
+                year.3 += season.homeruns;
+            }
+            _ => years.push((season.season, season.at_bats, season.hits, season.homeruns)),
+        }
+    }
+
+    let mut hits = BarChart::new("Hits by season");
+    let mut homeruns = BarChart::new("Home runs by season");
+    let mut average = BarChart::new("Batting average by season");
+    average.decimals = 3;
+
+    for &(year, at_bats, year_hits, year_homeruns) in &years {
+        hits.bar(year.to_string(), year_hits as f64);
+        homeruns.bar(year.to_string(), year_homeruns as f64);
+        if at_bats > 0 {
+            average.bar(year.to_string(), year_hits as f64 / at_bats as f64);
+        }
+    }
+
+    out.chart(&hits);
+    out.chart(&homeruns);
+    out.chart(&average);
+}
+
+// columns joined on with --join, season by season and for the career (nothing if there aren't any)
+fn show_joined(out: &mut Output, player: &AggregatedPlayer, seasons: &[&CleanPlayerSeason]) {
+    let mut columns: Vec<&String> = player.extras.keys().collect();
+    for season in seasons {
+        for name in season.extras.keys() {
+            if !columns.contains(&name) {
+                columns.push(name);
+            }
+        }
+    }
+    if columns.is_empty() {
+        return;
+    }
+    columns.sort();
+
+    let mut table = Table::new("Joined columns").column("Season", 10).column("Team", 4);
+    for name in &columns {
+        table = table.right(name.as_str(), name.len().max(6));
+    }
+
+    let value_of = |extras: &BTreeMap<String, f64>, name: &str| show_optional(extras.get(name).map(|v| format!("{:.1}", v)));
+
+    for season in seasons.iter().filter(|s| !s.extras.is_empty()) {
+        let mut row = vec![season.season.to_string(), season.team.to_string()];
+        row.extend(columns.iter().map(|name| value_of(&season.extras, name)));
+        table.row(row);
+    }
+    table.separator();
+    let mut row = vec!["Career".to_string(), String::new()];
+    row.extend(columns.iter().map(|name| value_of(&player.extras, name)));
+    table.row(row);
+
+    out.table(&table);
+}
+
+// print one player's full page
+fn show_player_page(out: &mut Output, player: &AggregatedPlayer, builder: &CareerBuilder, players: &PlayerIndex) {
+    // the MLB seasons, oldest first
+    let mut mlb_seasons = players.mlb_seasons(&player.link).to_vec();
+    mlb_seasons.sort_by_key(|s| s.season);
+
+    out.line("");
+    out.line(&format!("{} {} ({})", player.first_name, player.last_name, player.link));
+    out.line(&"=".repeat(112));
+
+    if mlb_seasons.is_empty() {
+        out.line("No MLB seasons on record");
+    } else {
+        // work the career out from the MLB rows only, whatever else got merged into the records
+        let mlb_career = builder.career(&player.link, &mlb_seasons);
+
+        out.line(&format!("Seasons:   {}-{} ({})", mlb_career.first_season, mlb_career.last_season, mlb_career.seasons_played));
+        out.line(&format!("Positions: {} (mostly {})", mlb_career.positions, mlb_career.primary_position));
+        out.line(&format!("Teams:     {}", mlb_career.teams));
+        if !mlb_career.leagues.is_empty() {
+            out.line(&format!("Leagues:   {}", mlb_career.leagues));
+        }
+        if !mlb_career.times_led.is_empty() {
+            // with a league column, leading is leading his league
+            let led = if mlb_career.leagues.is_empty() { "Led MLB:  " } else { "Led league:" };
+            out.line(&format!("{} {}", led, describe_times_led(&mlb_career.times_led)));
+        }
+
+        let mut table = season_table("MLB seasons");
+        for season in &mlb_seasons {
+            add_season_line(&mut table, &season.season.to_string(), season);
+        }
+        add_totals_line(&mut table, "MLB career", &mlb_career);
+        out.table(&table);
+
+        show_trajectory(out, &mlb_seasons);
+        show_joined(out, player, &mlb_seasons);
+    }
+
+    // anything from winter ball or foreign leagues goes in its own section, one block per league
+    let mut other_seasons: Vec<&SupplementarySeason> = players.supplementary(&player.link).to_vec();
+
+    if other_seasons.is_empty() {
+        return;
+    }
+
+    other_seasons.sort_by_key(|s| (s.league.clone(), s.record.season));
+
+    let mut leagues: Vec<String> = Vec::new();
+    for season in &other_seasons {
+        if !leagues.contains(&season.league) {
+            leagues.push(season.league.clone());
+        }
+    }
+
+    out.line("");
+    out.line("Supplementary (non-MLB) seasons - not counted in MLB records");
+
+    for league in &leagues {
+        let league_seasons: Vec<&CleanPlayerSeason> = other_seasons
+            .iter()
+            .filter(|s| &s.league == league)
+            .map(|s| &s.record)
+            .collect();
+
+        let mut table = season_table(league);
+        for season in &league_seasons {
+            add_season_line(&mut table, &season.season.to_string(), season);
+        }
+        add_totals_line(&mut table, "Total", &builder.career(&player.link, &league_seasons));
+        out.table(&table);
+    }
+}
+
+// the player command
+// (the MLB records are only for the league estimates with --missing estimate)
+pub fn show_player(out: &mut Output, query: &str, players: &PlayerIndex, mlb_records: &[CleanPlayerSeason], options: &Options) -> Result<()> {
+    let matches = players.find(query);
+
+    if matches.is_empty() {
+        return Err(player_not_found(query));
+    }
+
+    if matches.len() > 1 {
+        out.line(&format!("{} players match \"{}\"", matches.len(), query));
+    }
+
+    let builder = CareerBuilder::new(mlb_records, options);
+    for player in matches {
+        show_player_page(out, player, &builder, players);
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+// fielding positions: working out where a player mostly played, filtering leaderboards by it,
+// and the by-position command
+
+use std::collections::HashMap;
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::ranking::{rank_with_ties, SortOrder, TieBreak};
+use crate::stats::format_rate;
+use crate::table::{Output, Table};
+
+// the order positions are listed in, round the diamond and then the rest
+// (X is the data's "not recorded", PH and PR are pinch hitters and runners)
+const POSITION_ORDER: &[&str] = &["P", "C", "1B", "2B", "3B", "SS", "LF", "CF", "RF", "OF", "DH", "PH", "PR", "X"];
+
+// where a player mostly played: the position with the most games, then the most seasons
+// (a season's position is the one the data gives for it, so a season counts in full for that one position)
+// "X" only wins if nothing else was ever recorded
+pub fn primary_position(seasons: &[&CleanPlayerSeason]) -> String {
+    let mut games_and_seasons: HashMap<&str, (u32, u32)> = HashMap::new();
+
+    for season in seasons {
+        let entry = games_and_seasons.entry(season.position.as_ref()).or_default();
+        entry.0 += season.games_played;
+        entry.1 += 1;
+    }
+
+    let known = games_and_seasons.iter().filter(|(position, _)| **position != "X");
+    let best = known
+        .max_by_key(|(position, totals)| (**totals, std::cmp::Reverse(position_rank(position))))
+        .map(|(position, _)| position.to_string());
+
+    best.unwrap_or_else(|| "X".to_string())
+}
+
+// where a position goes in POSITION_ORDER (unknown ones at the end)
+fn position_rank(position: &str) -> usize {
+    POSITION_ORDER.iter().position(|p| *p == position).unwrap_or(POSITION_ORDER.len())
+}
+
+// does a position match what was asked for? any case, and "OF" takes in all three outfield spots
+pub fn position_matches(wanted: &str, position: &str) -> bool {
+    if wanted.eq_ignore_ascii_case("OF") {
+        return matches!(position, "LF" | "CF" | "RF" | "OF");
+    }
+    wanted.eq_ignore_ascii_case(position)
+}
+
+// the by-position command: how many players call each position home, what they hit between them,
+// and who hit the most home runs there
+pub fn show_by_position(out: &mut Output, aggregated_players: &[AggregatedPlayer]) {
+    let mut by_position: HashMap<&str, Vec<&AggregatedPlayer>> = HashMap::new();
+    for player in aggregated_players {
+        by_position.entry(player.primary_position.as_str()).or_default().push(player);
+    }
+
+    let mut positions: Vec<&str> = by_position.keys().copied().collect();
+    positions.sort_by_key(|p| (position_rank(p), p.to_string()));
+
+    let mut table = Table::new("Players by primary position")
+        .column("Pos", 4)
+        .right("Players", 7)
+        .right("AB", 9)
+        .right("H", 8)
+        .right("HR", 6)
+        .right("AVG", 5)
+        .column("Most HR", 24)
+        .right("HR", 4);
+
+    for position in positions {
+        let players = &by_position[position];
+
+        let at_bats: u32 = players.iter().map(|p| p.total_at_bats).sum();
+        let hits: u32 = players.iter().map(|p| p.total_hits).sum();
+        let homeruns: u32 = players.iter().map(|p| p.total_homeruns).sum();
+        let average = if at_bats == 0 { "--".to_string() } else { format_rate(hits as f64 / at_bats as f64) };
+
+        // the leader, with any ties listed together (nobody, if nobody there hit one)
+        let leaders = rank_with_ties(aggregated_players, 1, SortOrder::Descending, TieBreak::Shared, |p| {
+            if p.primary_position == position && p.total_homeruns > 0 { Some(p.total_homeruns as f64) } else { None }
+        });
+        let leader_names: Vec<String> = leaders.iter().map(|r| format!("{} {}", r.item.first_name, r.item.last_name)).collect();
+        let leader_homeruns = leaders.first().map(|r| r.item.total_homeruns.to_string()).unwrap_or_default();
+
+        table.row(vec![
+            position.to_string(),
+            players.len().to_string(),
+            at_bats.to_string(),
+            hits.to_string(),
+            homeruns.to_string(),
+            average,
+            leader_names.join(", "),
+            leader_homeruns,
+        ]);
+    }
+
+    out.table(&table);
+    out.line("");
+    out.line("A player's primary position is the one he played the most games at. Use --position with top, bottom or the shortcuts to rank one position.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::seasons_from_csv;
+
+    #[test]
+    fn the_primary_position_has_the_most_games() {
+        let seasons = seasons_from_csv("\
+1960,Ernie,Banks,/player/110839,SS,CHC,156,597,94,162,32,7,41,117,71,69,1,3,.271,.350,.554,.904
+1961,Ernie,Banks,/player/110839,1B,CHC,138,511,75,142,22,4,29,80,54,75,1,2,.278,.346,.507,.853
+1962,Ernie,Banks,/player/110839,1B,CHC,154,610,87,164,20,6,37,104,30,71,5,1,.269,.306,.503,.809
+1963,Ernie,Banks,/player/110839,X,CHC,130,432,41,98,20,1,18,64,39,73,0,1,.227,.308,.403,.711
+");
+        let rows: Vec<&CleanPlayerSeason> = seasons.iter().collect();
+        assert_eq!(primary_position(&rows), "1B");
+        // X only when nothing else was recorded
+        assert_eq!(primary_position(&rows[3..]), "X");
+    }
+
+    #[test]
+    fn of_takes_in_the_three_outfield_spots() {
+        for position in ["LF", "CF", "RF", "OF"] {
+            assert!(position_matches("of", position), "{}", position);
+        }
+        assert!(position_matches("ss", "SS"));
+        assert!(!position_matches("OF", "1B"));
+    }
+}
@@ -0,0 +1,33 @@
+// progress bars for the slow parts of loading (a big csv can take a while)
+// bars are off unless the command line tool switches them on, so library use stays quiet,
+// and indicatif doesn't draw them at all when stderr isn't a terminal
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+// a bar counting up to `length` (bytes when `bytes` is set, otherwise rows/players)
+pub fn bar(length: u64, message: &str, bytes: bool) -> ProgressBar {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return ProgressBar::hidden();
+    }
+
+    let template = if bytes {
+        "{msg:<22} [{bar:40}] {bytes}/{total_bytes} ({eta})"
+    } else {
+        "{msg:<22} [{bar:40}] {pos}/{len} ({eta})"
+    };
+
+    let bar = ProgressBar::new(length);
+    if let Ok(style) = ProgressStyle::with_template(template) {
+        bar.set_style(style.progress_chars("=> "));
+    }
+    bar.set_message(message.to_string());
+    bar
+}
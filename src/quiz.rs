@@ -0,0 +1,209 @@
+// the quiz command: multiple choice questions made up from the data ("Who hit the most home runs in
+// 1987?", "Which of these players had more career hits?"), asked one at a time with a running score
+// the answers come from the same season leaders and career totals the other commands show
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, BufRead, Write};
+
+use fastrand::Rng;
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::leader_counts::{leader_order, qualified_season_value, season_leaders_for};
+use crate::leaders::{CAREER_QUALIFYING_AT_BATS, SEASON_QUALIFYING_AT_BATS};
+use crate::options::Options;
+use crate::ranking::{rank_with_ties, TieBreak};
+use crate::report::season_player_name;
+use crate::stats::{career_value, format_stat_value, is_rate_stat};
+
+// the season questions: "Who ... in 1987?"
+const SEASON_QUESTIONS: &[(&str, &str)] = &[
+    ("homeruns", "hit the most home runs"),
+    ("rbi", "drove in the most runs"),
+    ("batting_average", "had the highest batting average"),
+    ("hits", "had the most hits"),
+    ("runs", "scored the most runs"),
+    ("doubles", "hit the most doubles"),
+    ("triples", "hit the most triples"),
+    ("walks", "drew the most walks"),
+    ("stolen_bases", "stole the most bases"),
+];
+
+// the career questions: "Which of these players had more career ...?"
+const CAREER_QUESTIONS: &[(&str, &str)] = &[
+    ("hits", "hits"),
+    ("homeruns", "home runs"),
+    ("rbi", "RBI"),
+    ("runs", "runs"),
+    ("doubles", "doubles"),
+    ("walks", "walks"),
+    ("stolen_bases", "stolen bases"),
+];
+
+// how many choices a season question gets (the leader and the next best hitters that year)
+const SEASON_CHOICES: usize = 4;
+
+// one question, with its choices in the order they're shown
+pub struct Question {
+    pub text: String,
+    pub choices: Vec<String>,
+    pub answer: usize,     // which of the choices is right
+    pub detail: String,    // the numbers behind the answer, shown once it's given
+}
+
+// the letter a choice is picked with
+fn letter(index: usize) -> char {
+    (b'a' + index as u8) as char
+}
+
+// "Who hit the most home runs in 1987?": a season with a single leader, against the best of the rest
+fn season_question(rng: &mut Rng, by_season: &BTreeMap<u32, Vec<&CleanPlayerSeason>>, clean_records: &[CleanPlayerSeason], options: &Options) -> Option<Question> {
+    let (stat, phrase) = SEASON_QUESTIONS[rng.usize(..SEASON_QUESTIONS.len())];
+
+    // co-leaders would make two right answers, so only seasons with one leader will do
+    let leaders: Vec<_> = season_leaders_for(stat, clean_records, options).into_iter().filter(|l| l.players.len() == 1).collect();
+    if leaders.is_empty() {
+        return None;
+    }
+    let leader = &leaders[rng.usize(..leaders.len())];
+
+    // the next best that year (in his league, when the data has them), one row per player (and per name, so
+    // no two choices read the same)
+    let rows: Vec<&CleanPlayerSeason> = by_season.get(&leader.season)?.iter().copied().filter(|s| s.league == leader.league).collect();
+    let ranked = rank_with_ties(&rows, 12, leader_order(stat), TieBreak::Shared, |s| qualified_season_value(stat, s, options));
+    let mut seen = HashSet::new();
+    let mut candidates: Vec<(String, f64)> = Vec::new();
+    for row in &ranked {
+        let name = season_player_name(row.item);
+        if seen.insert(row.item.link.clone()) && !candidates.iter().any(|(n, _)| *n == name) {
+            candidates.push((name, row.value));
+        }
+    }
+    if candidates.len() < SEASON_CHOICES {
+        return None;
+    }
+
+    let (answer_name, answer_value) = candidates.remove(0);
+    rng.shuffle(&mut candidates);
+    let mut choices: Vec<(String, f64)> = candidates.into_iter().take(SEASON_CHOICES - 1).collect();
+    choices.push((answer_name.clone(), answer_value));
+    rng.shuffle(&mut choices);
+
+    let league = leader.league.as_deref().map(|league| format!(" the {}", league)).unwrap_or_default();
+    let qualified = if is_rate_stat(stat) { format!(" (min {} AB)", options.prorated(SEASON_QUALIFYING_AT_BATS, leader.season)) } else { String::new() };
+    let detail = choices
+        .iter()
+        .map(|(name, value)| format!("{} {}", name, format_stat_value(stat, *value)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    Some(Question {
+        text: format!("Who {} in{} in {}{}?", phrase, league, leader.season, qualified),
+        answer: choices.iter().position(|(name, _)| *name == answer_name)?,
+        choices: choices.into_iter().map(|(name, _)| name).collect(),
+        detail,
+    })
+}
+
+// "Which of these players had more career hits?": two long careers with different totals
+fn career_question(rng: &mut Rng, careers: &[&AggregatedPlayer]) -> Option<Question> {
+    let (stat, noun) = CAREER_QUESTIONS[rng.usize(..CAREER_QUESTIONS.len())];
+    if careers.len() < 2 {
+        return None;
+    }
+
+    let a = careers[rng.usize(..careers.len())];
+    let b = careers[rng.usize(..careers.len())];
+    let (a_value, b_value) = (career_value(stat, a)?, career_value(stat, b)?);
+    let (a_name, b_name) = (format!("{} {}", a.first_name, a.last_name), format!("{} {}", b.first_name, b.last_name));
+    if a_value == b_value || a_name == b_name {
+        return None;
+    }
+
+    Some(Question {
+        text: format!("Which of these players had more career {}?", noun),
+        answer: if a_value > b_value { 0 } else { 1 },
+        detail: format!("{} {}, {} {}", a_name, format_stat_value(stat, a_value), b_name, format_stat_value(stat, b_value)),
+        choices: vec![a_name, b_name],
+    })
+}
+
+// make up to count questions, half of each kind on average (fewer if the data can't support them)
+pub fn make_questions(count: usize, rng: &mut Rng, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], options: &Options) -> Vec<Question> {
+    let mut by_season: BTreeMap<u32, Vec<&CleanPlayerSeason>> = BTreeMap::new();
+    for season in clean_records {
+        by_season.entry(season.season).or_default().push(season);
+    }
+    // in link order, so a seed makes the same quiz whatever order the careers were added up in
+    let mut careers: Vec<&AggregatedPlayer> = aggregated_players.iter().filter(|p| p.total_at_bats >= CAREER_QUALIFYING_AT_BATS).collect();
+    careers.sort_by(|a, b| a.link.cmp(&b.link));
+
+    let mut questions: Vec<Question> = Vec::new();
+    let mut asked = HashSet::new();
+    // a question can't always be made (a tied leader, two players with the same total), so give it a few goes each
+    for _ in 0..count * 20 {
+        if questions.len() == count {
+            break;
+        }
+        let question = if rng.bool() {
+            season_question(rng, &by_season, clean_records, options)
+        } else {
+            career_question(rng, &careers)
+        };
+        if let Some(question) = question.filter(|q| asked.insert(format!("{} {:?}", q.text, q.choices))) {
+            questions.push(question);
+        }
+    }
+    questions
+}
+
+// ask the questions one at a time, reading answers from input, until they run out or the player quits
+// returns (right, asked)
+pub fn run_quiz(questions: &[Question], input: &mut impl BufRead, output: &mut impl Write) -> io::Result<(usize, usize)> {
+    let mut right = 0;
+    let mut asked = 0;
+
+    'questions: for (number, question) in questions.iter().enumerate() {
+        writeln!(output)?;
+        writeln!(output, "Question {} of {}: {}", number + 1, questions.len(), question.text)?;
+        for (i, choice) in question.choices.iter().enumerate() {
+            writeln!(output, "  {}) {}", letter(i), choice)?;
+        }
+
+        // the letter or the number of a choice; anything else asks again
+        let picked = loop {
+            write!(output, "Your answer (a-{}, q to stop): ", letter(question.choices.len() - 1))?;
+            output.flush()?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(output)?;
+                break 'questions;
+            }
+            let answer = line.trim().to_lowercase();
+            if answer == "q" || answer == "quit" {
+                break 'questions;
+            }
+
+            let by_letter = answer.chars().next().filter(|_| answer.len() == 1).map(|c| (c as usize).wrapping_sub('a' as usize));
+            let by_number = answer.parse::<usize>().ok().and_then(|n| n.checked_sub(1));
+            match by_number.or(by_letter) {
+                Some(i) if i < question.choices.len() => break i,
+                _ => writeln!(output, "Pick one of the letters")?,
+            }
+        };
+
+        asked += 1;
+        if picked == question.answer {
+            right += 1;
+            writeln!(output, "Right! ({})", question.detail)?;
+        } else {
+            writeln!(output, "No, it was {}) {} ({})", letter(question.answer), question.choices[question.answer], question.detail)?;
+        }
+        writeln!(output, "Score: {} of {}", right, asked)?;
+    }
+
+    writeln!(output)?;
+    writeln!(output, "Final score: {} of {}", right, asked)?;
+    Ok((right, asked))
+}
@@ -0,0 +1,133 @@
+// the random command: a random player's career card, or with --fact a random record (a top ten season
+// or career in a random stat), for bots and shell prompts; --seed picks the same one again
+
+use fastrand::Rng;
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::leader_counts::describe_times_led;
+use crate::leaders::{CAREER_QUALIFYING_AT_BATS, SEASON_QUALIFYING_AT_BATS};
+use crate::options::Options;
+use crate::ranking::{rank_by, SortOrder};
+use crate::report::{label, season_player_name};
+use crate::schedule::prorated_note;
+use crate::stats::{all_stats, career_value, format_rate, format_stat_value, has_career_value, higher_is_better, is_rate_stat, season_value, unavailable_reason};
+use crate::table::Output;
+
+// how far down the all time lists a fact can come from
+const FACT_PLACES: usize = 10;
+
+// a career in a few lines: who, when, where, and the totals and slash line
+pub fn show_career_card(out: &mut Output, player: &AggregatedPlayer) {
+    let slash = |rate: Option<f64>| rate.map(format_rate).unwrap_or_else(|| "--".to_string());
+
+    out.line(&format!("{} {} ({})", player.first_name, player.last_name, player.link));
+    out.line(&format!("Seasons:   {}-{} ({})", player.first_season, player.last_season, player.seasons_played));
+    out.line(&format!("Positions: {} (mostly {})", player.positions, player.primary_position));
+    out.line(&format!("Teams:     {}", player.teams));
+    if !player.leagues.is_empty() {
+        out.line(&format!("Leagues:   {}", player.leagues));
+    }
+    out.line(&format!(
+        "Career:    {} G, {} H, {} HR, {} RBI, {} SB, {}/{}/{}",
+        player.total_games_played,
+        player.total_hits,
+        player.total_homeruns,
+        player.total_rbi,
+        player.total_stolen_bases,
+        slash(player.batting_average),
+        slash(player.on_base_percentage),
+        slash(player.slugging_percentage),
+    ));
+    if !player.times_led.is_empty() {
+        // with a league column, leading is leading his league
+        let led = if player.leagues.is_empty() { "Led MLB:  " } else { "Led league:" };
+        out.line(&format!("{} {}", led, describe_times_led(&player.times_led)));
+    }
+}
+
+// the random command without --fact: any player with at least min_ab career at bats
+pub fn show_random_player(out: &mut Output, rng: &mut Rng, min_ab: u32, aggregated_players: &[AggregatedPlayer]) {
+    // in link order, so a seed picks the same player whatever order the careers were added up in
+    let mut players: Vec<&AggregatedPlayer> = aggregated_players.iter().filter(|p| p.total_at_bats >= min_ab).collect();
+    players.sort_by(|a, b| a.link.cmp(&b.link));
+    if players.is_empty() {
+        out.line(&format!("No players with {} career at bats", min_ab));
+        return;
+    }
+
+    show_career_card(out, players[rng.usize(..players.len())]);
+}
+
+// "#3 all time", or "tied for #3" when someone else has the same value
+fn place(rank: usize, tied: bool) -> String {
+    if tied { format!("tied for #{} all time", rank) } else { format!("#{} all time", rank) }
+}
+
+// the random command with --fact: a random stat, single seasons or careers, and a random place in its top ten
+// (best first, with the same qualifying minimums as top, so only stats where more is better)
+pub fn show_fact(out: &mut Output, rng: &mut Rng, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], options: &Options) {
+    let stats: Vec<&str> = all_stats()
+        .into_iter()
+        .map(|s| s.name())
+        .filter(|&stat| higher_is_better(stat) && unavailable_reason(stat, clean_records).is_none())
+        .collect();
+    if stats.is_empty() {
+        out.line("There isn't enough data for a fact");
+        return;
+    }
+
+    let stat = stats[rng.usize(..stats.len())];
+    let career = has_career_value(stat) && rng.bool();
+
+    let min_at_bats = match (is_rate_stat(stat), career) {
+        (false, _) => 0,
+        (true, true) => CAREER_QUALIFYING_AT_BATS,
+        (true, false) => SEASON_QUALIFYING_AT_BATS,
+    };
+    let prorated_qualifier = if career { "" } else { prorated_note(options.prorate) };
+    let qualifier = if min_at_bats > 0 { format!(" (min {} AB{})", min_at_bats, prorated_qualifier) } else { String::new() };
+
+    let fact = if career {
+        let ranked = rank_by(aggregated_players, FACT_PLACES, SortOrder::Descending, options, |p| {
+            (p.total_at_bats >= min_at_bats).then(|| career_value(stat, p)).flatten()
+        });
+        (!ranked.is_empty()).then(|| {
+            let row = &ranked[rng.usize(..ranked.len())];
+            let tied = ranked.iter().filter(|r| r.rank == row.rank).count() > 1;
+            let player = row.item;
+            format!(
+                "{} {} ({}-{}): {} {}, {} for a career{}",
+                player.first_name,
+                player.last_name,
+                player.first_season,
+                player.last_season,
+                format_stat_value(stat, row.value),
+                label(stat),
+                place(row.rank, tied),
+                qualifier,
+            )
+        })
+    } else {
+        let ranked = rank_by(clean_records, FACT_PLACES, SortOrder::Descending, options, |s| {
+            (s.at_bats >= options.prorated(min_at_bats, s.season)).then(|| season_value(stat, s)).flatten()
+        });
+        (!ranked.is_empty()).then(|| {
+            let row = &ranked[rng.usize(..ranked.len())];
+            let tied = ranked.iter().filter(|r| r.rank == row.rank).count() > 1;
+            let season = row.item;
+            format!(
+                "{} ({}, {}): {} {}, {} for a season{}",
+                season_player_name(season),
+                season.team,
+                season.season,
+                format_stat_value(stat, row.value),
+                label(stat),
+                place(row.rank, tied),
+                qualifier,
+            )
+        })
+    };
+
+    out.line(&fact.unwrap_or_else(|| format!("Nobody has {} on record", label(stat))));
+}
@@ -0,0 +1,254 @@
+// turning a list of seasons or careers into a ranked leaderboard
+// every leaderboard goes through here so they all treat ties the same way:
+//   - the list is sorted highest first or lowest first (and "worst" picks whichever of those is bad for the stat)
+//   - players with the same value share a rank (1, 2, 2, 4)
+//   - within a tie, fewer at-bats is listed first, then alphabetical by last name, first name
+//   - anyone tied with the last place that fits in the top n is shown too
+//
+// publications don't all do ties that way, so --ties can break them instead (see TieBreak): then every
+// row gets its own rank, decided by fewer at-bats, the earlier season or the name, and the top n is n rows
+
+use std::cmp::Ordering;
+
+use clap::ValueEnum;
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::options::Options;
+
+// what the ranking needs to know about a row to break ties
+pub trait Rankable {
+    fn at_bats(&self) -> u32;
+    fn sort_name(&self) -> (String, String);  // (last name, first name)
+    fn season(&self) -> u32;                   // the season, or the first one for a career
+}
+
+impl Rankable for CleanPlayerSeason {
+    fn at_bats(&self) -> u32 {
+        self.at_bats
+    }
+
+    fn sort_name(&self) -> (String, String) {
+        (self.last_name.to_lowercase(), self.first_name.as_deref().unwrap_or("").to_lowercase())
+    }
+
+    fn season(&self) -> u32 {
+        self.season
+    }
+}
+
+// so lists of references (seasons grouped by year or team, say) can be ranked without copying them
+impl<T: Rankable> Rankable for &T {
+    fn at_bats(&self) -> u32 {
+        (*self).at_bats()
+    }
+
+    fn sort_name(&self) -> (String, String) {
+        (*self).sort_name()
+    }
+
+    fn season(&self) -> u32 {
+        (*self).season()
+    }
+}
+
+// a row with its place in the list it came from, so what's ranked can be found again by index
+impl<T: Rankable> Rankable for (usize, T) {
+    fn at_bats(&self) -> u32 {
+        self.1.at_bats()
+    }
+
+    fn sort_name(&self) -> (String, String) {
+        self.1.sort_name()
+    }
+
+    fn season(&self) -> u32 {
+        self.1.season()
+    }
+}
+
+impl Rankable for AggregatedPlayer {
+    fn at_bats(&self) -> u32 {
+        self.total_at_bats
+    }
+
+    fn sort_name(&self) -> (String, String) {
+        (self.last_name.to_lowercase(), self.first_name.to_lowercase())
+    }
+
+    fn season(&self) -> u32 {
+        self.first_season
+    }
+}
+
+// which end of the list comes first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortOrder {
+    Descending,
+    Ascending,
+}
+
+impl SortOrder {
+    // the order that puts the best values first, or the worst ones when worst is set
+    // e.g. the best batting averages are the highest, but the best strikeout totals are the lowest
+    pub fn for_stat(higher_is_better: bool, worst: bool) -> SortOrder {
+        if higher_is_better != worst {
+            SortOrder::Descending
+        } else {
+            SortOrder::Ascending
+        }
+    }
+}
+
+// what happens to players with the same value
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum TieBreak {
+    /// tied players share a rank (1, 2, 2, 4), fewer at-bats listed first
+    #[default]
+    Shared,
+    /// fewer at-bats ranks higher
+    FewerAtBats,
+    /// the earlier season (or the earlier start, for careers) ranks higher
+    EarliestSeason,
+    /// alphabetical by last name, then first name
+    Alphabetical,
+}
+
+// one row of a ranked leaderboard
+pub struct RankedRow<'a, T> {
+    pub rank: usize,
+    pub value: f64,
+    pub item: &'a T,
+}
+
+// the order rows are listed in: value in the chosen direction, then the tie-breakers
+fn display_order<T: Rankable>(order: SortOrder, tie_break: TieBreak, a: &(f64, &T), b: &(f64, &T)) -> Ordering {
+    let by_value = match order {
+        SortOrder::Descending => b.0.total_cmp(&a.0),
+        SortOrder::Ascending => a.0.total_cmp(&b.0),
+    };
+
+    let by_at_bats = || a.1.at_bats().cmp(&b.1.at_bats());
+    let by_season = || a.1.season().cmp(&b.1.season());
+    let by_name = || a.1.sort_name().cmp(&b.1.sort_name());
+
+    match tie_break {
+        TieBreak::Shared | TieBreak::FewerAtBats => by_value.then_with(by_at_bats).then_with(by_name),
+        TieBreak::EarliestSeason => by_value.then_with(by_season).then_with(by_at_bats).then_with(by_name),
+        TieBreak::Alphabetical => by_value.then_with(by_name).then_with(by_at_bats),
+    }
+}
+
+// rank everything that has a value for the stat and keep the top n places, breaking ties the way the
+// options (--ties) say
+pub fn rank_by<'a, T: Rankable>(items: &'a [T], top: usize, order: SortOrder, options: &Options, value: impl Fn(&T) -> Option<f64>) -> Vec<RankedRow<'a, T>> {
+    rank_with_ties(items, top, order, options.ties, value)
+}
+
+// the same, with the tie policy given rather than the options'
+pub fn rank_with_ties<'a, T: Rankable>(items: &'a [T], top: usize, order: SortOrder, tie_break: TieBreak, value: impl Fn(&T) -> Option<f64>) -> Vec<RankedRow<'a, T>> {
+    let mut rows: Vec<(f64, &T)> = items
+        .iter()
+        .filter_map(|item| value(item).map(|v| (v, item)))
+        .collect();
+
+    rows.sort_by(|a, b| display_order(order, tie_break, a, b));
+
+    let mut ranked: Vec<RankedRow<T>> = Vec::new();
+
+    for (i, (value, item)) in rows.into_iter().enumerate() {
+        // same value as the row above means same rank when ties are shared, otherwise the rank is the position
+        let rank = match ranked.last() {
+            Some(previous) if previous.value == value && tie_break == TieBreak::Shared => previous.rank,
+            _ => i + 1,
+        };
+
+        // ties share a rank, so this keeps everyone tied at the cutoff
+        if rank > top {
+            break;
+        }
+
+        ranked.push(RankedRow { rank, value, item });
+    }
+
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Row {
+        last: &'static str,
+        at_bats: u32,
+        season: u32,
+        value: Option<f64>,
+    }
+
+    impl Rankable for Row {
+        fn at_bats(&self) -> u32 {
+            self.at_bats
+        }
+
+        fn sort_name(&self) -> (String, String) {
+            (self.last.to_lowercase(), String::new())
+        }
+
+        fn season(&self) -> u32 {
+            self.season
+        }
+    }
+
+    fn row(last: &'static str, at_bats: u32, season: u32, value: Option<f64>) -> Row {
+        Row { last, at_bats, season, value }
+    }
+
+    // two tied for second, the later of them with fewer at bats; and one with no value at all
+    fn rows() -> Vec<Row> {
+        vec![
+            row("Cobb", 600, 1911, Some(9.0)),
+            row("Aaron", 550, 1957, Some(10.0)),
+            row("Zernial", 500, 1953, Some(9.0)),
+            row("Nobody", 10, 1960, None),
+            row("Bell", 620, 1922, Some(8.0)),
+        ]
+    }
+
+    fn ranked(tie_break: TieBreak, top: usize) -> Vec<(usize, &'static str)> {
+        let rows = rows();
+        rank_with_ties(&rows, top, SortOrder::Descending, tie_break, |r| r.value).iter().map(|r| (r.rank, r.item.last)).collect()
+    }
+
+    #[test]
+    fn shared_ties_share_a_rank_and_skip_the_next() {
+        assert_eq!(ranked(TieBreak::Shared, 10), vec![(1, "Aaron"), (2, "Zernial"), (2, "Cobb"), (4, "Bell")]);
+    }
+
+    #[test]
+    fn shared_ties_at_the_cutoff_are_all_kept() {
+        assert_eq!(ranked(TieBreak::Shared, 2), vec![(1, "Aaron"), (2, "Zernial"), (2, "Cobb")]);
+    }
+
+    #[test]
+    fn broken_ties_keep_exactly_n_rows() {
+        assert_eq!(ranked(TieBreak::FewerAtBats, 2), vec![(1, "Aaron"), (2, "Zernial")]);
+        assert_eq!(ranked(TieBreak::EarliestSeason, 2), vec![(1, "Aaron"), (2, "Cobb")]);
+        assert_eq!(ranked(TieBreak::Alphabetical, 3), vec![(1, "Aaron"), (2, "Cobb"), (3, "Zernial")]);
+    }
+
+    #[test]
+    fn values_are_ordered_by_total_cmp() {
+        // negative values (baserunning runs, say), and a negative zero below zero
+        let rows = vec![row("A", 1, 1, Some(0.0)), row("B", 1, 1, Some(-2.5)), row("C", 1, 1, Some(-0.0)), row("D", 1, 1, Some(1.5))];
+        let names = |order| rank_with_ties(&rows, 10, order, TieBreak::FewerAtBats, |r| r.value).iter().map(|r| r.item.last).collect::<Vec<&str>>();
+        assert_eq!(names(SortOrder::Descending), vec!["D", "A", "C", "B"]);
+        assert_eq!(names(SortOrder::Ascending), vec!["B", "C", "A", "D"]);
+    }
+
+    #[test]
+    fn worst_flips_the_order() {
+        assert_eq!(SortOrder::for_stat(true, false), SortOrder::Descending);
+        assert_eq!(SortOrder::for_stat(true, true), SortOrder::Ascending);
+        assert_eq!(SortOrder::for_stat(false, false), SortOrder::Ascending);
+    }
+}
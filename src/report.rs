@@ -0,0 +1,260 @@
+// the almanac: every record we can work out, one section after another, as a single document to share
+
+use std::collections::BTreeMap;
+
+use clap::{Args, ValueEnum};
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::leader_counts::{leader_order, qualified_season_value, season_leaders_for};
+use crate::leaders::{CAREER_QUALIFYING_AT_BATS, SEASON_QUALIFYING_AT_BATS};
+use crate::options::Options;
+use crate::ranking::{rank_by, rank_with_ties, Rankable, RankedRow, SortOrder, TieBreak};
+use crate::schedule::prorated_note;
+use crate::stats::{all_stats, career_value, find_stat, format_stat_value, has_career_value, is_rate_stat, unavailable_stats};
+use crate::table::{Output, Table};
+
+// the career totals that get a club of their own
+pub const MILESTONES: &[(&str, u32)] = &[
+    ("hits", 3000),
+    ("homeruns", 500),
+    ("rbi", 1500),
+    ("runs", 1500),
+    ("walks", 1500),
+    ("stolen_bases", 500),
+];
+
+// the stats with a leader listed for every season, and a record for every team
+const SEASON_LEADER_STATS: &[&str] = &["homeruns", "batting_average", "hits", "rbi", "runs", "stolen_bases"];
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Section {
+    Careers,
+    Seasons,
+    Milestones,
+    Teams,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ReportArgs {
+    /// only include these sections (can be repeated); every section is included by default
+    #[arg(long, value_enum)]
+    pub section: Vec<Section>,
+
+    /// include every section (the same as leaving out --section)
+    #[arg(long, conflicts_with = "section")]
+    pub all: bool,
+}
+
+// a stat's description without the note in brackets, for headings ("batting average (rate)" -> "batting average")
+pub fn label(stat: &str) -> &'static str {
+    let description = find_stat(stat).map(|s| s.description()).unwrap_or("");
+    description.split(" (").next().unwrap_or(description).trim_end_matches(':')
+}
+
+pub fn season_player_name(season: &CleanPlayerSeason) -> String {
+    match &season.first_name {
+        Some(first) => format!("{} {}", first, season.last_name),
+        None => season.last_name.clone(),
+    }
+}
+
+// everyone sharing first place, joined up ("Babe Ruth, Lou Gehrig")
+fn joined<T: Rankable>(leaders: &[RankedRow<T>], part: impl Fn(&T) -> String) -> String {
+    leaders.iter().map(|row| part(row.item)).collect::<Vec<String>>().join(", ")
+}
+
+// the top players in every stat we can total up over a career
+fn career_leaders(out: &mut Output, aggregated_players: &[AggregatedPlayer], top: usize, unavailable: &[&str], options: &Options) {
+    out.heading(2, "Career leaders");
+
+    for stat in all_stats() {
+        if !has_career_value(stat.name()) || unavailable.contains(&stat.name()) {
+            continue;
+        }
+
+        let rate = is_rate_stat(stat.name());
+        let ranked = rank_by(aggregated_players, top, leader_order(stat.name()), options, |p| {
+            if rate && p.total_at_bats < CAREER_QUALIFYING_AT_BATS { None } else { career_value(stat.name(), p) }
+        });
+
+        let title = if rate {
+            format!("Career {} (min {} AB)", label(stat.name()), CAREER_QUALIFYING_AT_BATS)
+        } else {
+            format!("Career {}", label(stat.name()))
+        };
+
+        let mut table = Table::new(title)
+            .column("Rank", 4)
+            .column("Player", 25)
+            .column("From", 6)
+            .column("To", 6)
+            .right(stat.name(), stat.name().len().max(8));
+
+        for row in &ranked {
+            let player = row.item;
+            table.row(vec![
+                row.rank.to_string(),
+                format!("{} {}", player.first_name, player.last_name),
+                player.first_season.to_string(),
+                player.last_season.to_string(),
+                format_stat_value(stat.name(), row.value),
+            ]);
+        }
+
+        out.table(&table);
+    }
+}
+
+// who led the majors in each stat, year by year
+fn season_leaders(out: &mut Output, clean_records: &[CleanPlayerSeason], unavailable: &[&str], options: &Options) {
+    out.heading(2, "Season by season leaders");
+
+    for &stat in SEASON_LEADER_STATS {
+        if unavailable.contains(&stat) {
+            continue;
+        }
+
+        let title = if is_rate_stat(stat) {
+            format!("Season leaders: {} (min {} AB{})", label(stat), SEASON_QUALIFYING_AT_BATS, prorated_note(options.prorate))
+        } else {
+            format!("Season leaders: {}", label(stat))
+        };
+
+        let leaders = season_leaders_for(stat, clean_records, options);
+        let by_league = leaders.iter().any(|l| l.league.is_some());
+
+        let mut table = Table::new(title).column("Season", 6);
+        if by_league {
+            table = table.column("League", 6);
+        }
+        table = table.column("Player", 30).column("Team", 12).right(stat, stat.len().max(8));
+
+        for leader in &leaders {
+            let names: Vec<String> = leader.players.iter().map(|s| season_player_name(s)).collect();
+            let teams: Vec<String> = leader.players.iter().map(|s| s.team.to_string()).collect();
+
+            let mut row = vec![leader.season.to_string()];
+            if by_league {
+                row.push(leader.league.as_deref().unwrap_or("--").to_string());
+            }
+            row.extend([names.join(", "), teams.join(", "), format_stat_value(stat, leader.value)]);
+            table.row(row);
+        }
+
+        out.table(&table);
+    }
+}
+
+// everyone past the big career milestones
+fn milestone_clubs(out: &mut Output, aggregated_players: &[AggregatedPlayer], unavailable: &[&str], options: &Options) {
+    out.heading(2, "Milestone clubs");
+
+    for &(stat, threshold) in MILESTONES {
+        if unavailable.contains(&stat) {
+            continue;
+        }
+        let members = rank_by(aggregated_players, usize::MAX, SortOrder::Descending, options, |p| {
+            career_value(stat, p).filter(|&value| value >= threshold as f64)
+        });
+
+        let mut table = Table::new(format!("{} {} club ({} members)", threshold, label(stat), members.len()))
+            .column("Rank", 4)
+            .column("Player", 25)
+            .column("From", 6)
+            .column("To", 6)
+            .right(stat, stat.len().max(8));
+
+        for row in &members {
+            let player = row.item;
+            table.row(vec![
+                row.rank.to_string(),
+                format!("{} {}", player.first_name, player.last_name),
+                player.first_season.to_string(),
+                player.last_season.to_string(),
+                format_stat_value(stat, row.value),
+            ]);
+        }
+
+        out.table(&table);
+    }
+}
+
+// the best single season for each franchise (so the Expos' records count for Washington)
+fn team_records(out: &mut Output, clean_records: &[CleanPlayerSeason], unavailable: &[&str], options: &Options) {
+    out.heading(2, "Team records");
+
+    let mut by_team: BTreeMap<&str, Vec<&CleanPlayerSeason>> = BTreeMap::new();
+    for season in clean_records {
+        by_team.entry(season.franchise.as_ref()).or_default().push(season);
+    }
+
+    for &stat in SEASON_LEADER_STATS {
+        if unavailable.contains(&stat) {
+            continue;
+        }
+
+        let title = if is_rate_stat(stat) {
+            format!("Team records: {} in a season (min {} AB{})", label(stat), SEASON_QUALIFYING_AT_BATS, prorated_note(options.prorate))
+        } else {
+            format!("Team records: {} in a season", label(stat))
+        };
+
+        let mut table = Table::new(title)
+            .column("Team", 6)
+            .column("Player", 30)
+            .column("Season", 12)
+            .right(stat, stat.len().max(8));
+
+        for (team, seasons) in &by_team {
+            let leaders = rank_with_ties(seasons, 1, leader_order(stat), TieBreak::Shared, |s| qualified_season_value(stat, s, options));
+            let Some(first) = leaders.first() else {
+                continue;
+            };
+
+            // note the old code when the record was set under one
+            let name = |s: &&CleanPlayerSeason| {
+                if s.team == s.franchise { season_player_name(s) } else { format!("{} ({})", season_player_name(s), s.team) }
+            };
+
+            table.row(vec![
+                team.to_string(),
+                joined(&leaders, name),
+                joined(&leaders, |s| s.season.to_string()),
+                format_stat_value(stat, first.value),
+            ]);
+        }
+
+        out.table(&table);
+    }
+}
+
+// the report command
+pub fn show_report(out: &mut Output, args: &ReportArgs, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], top: usize, options: &Options) {
+    let wanted = |section: Section| args.all || args.section.is_empty() || args.section.contains(&section);
+
+    out.heading(1, "Baseball almanac");
+
+    if let (Some(first), Some(last)) = (clean_records.iter().map(|s| s.season).min(), clean_records.iter().map(|s| s.season).max()) {
+        out.line(&format!("{} player seasons from {} to {}, {} players", clean_records.len(), first, last, aggregated_players.len()));
+    }
+
+    // stats the dataset doesn't have at all are left out rather than shown as lists of zeros
+    let unavailable = unavailable_stats(clean_records);
+    if !unavailable.is_empty() {
+        out.line(&format!("Left out, since the data has no values for them: {}", unavailable.join(", ")));
+    }
+
+    if wanted(Section::Careers) {
+        career_leaders(out, aggregated_players, top, &unavailable, options);
+    }
+    if wanted(Section::Seasons) {
+        season_leaders(out, clean_records, &unavailable, options);
+    }
+    if wanted(Section::Milestones) {
+        milestone_clubs(out, aggregated_players, &unavailable, options);
+    }
+    if wanted(Section::Teams) {
+        team_records(out, clean_records, &unavailable, options);
+    }
+}
@@ -0,0 +1,129 @@
+// a team's hitters for one season: every player row the data has for that team and year, with the
+// team's line added up underneath
+
+use crate::careers::CareerBuilder;
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::options::Options;
+use crate::ranking::{rank_by, Rankable, SortOrder};
+use crate::stats::{format_rate, format_stat_value, higher_is_better, normalize_stat_name, season_value, unknown_stat};
+use crate::table::{Output, Table};
+
+// the stats already in the stat line, so sorting by them doesn't need a column of its own
+const LINE_STATS: &[&str] = &[
+    "games", "at_bats", "runs", "hits", "doubles", "triples", "homeruns", "rbi", "walks", "strikeouts",
+    "stolen_bases", "caught_stealing", "batting_average", "on_base_percentage", "slugging_percentage", "ops",
+];
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "--".to_string())
+}
+
+// the roster command
+pub fn show_roster(out: &mut Output, team: &str, season: u32, by: &str, clean_records: &[CleanPlayerSeason], options: &Options) -> Result<()> {
+    let stat = normalize_stat_name(by).ok_or_else(|| unknown_stat(by))?;
+
+    // the code as the data has it, or the franchise's (WSH finds the 1994 Expos), in any case
+    let rows: Vec<&CleanPlayerSeason> = clean_records
+        .iter()
+        .filter(|s| s.season == season && (s.team.eq_ignore_ascii_case(team) || s.franchise.eq_ignore_ascii_case(team)))
+        .collect();
+
+    if rows.is_empty() {
+        return Err(Error::NotFound { what: "team", path: format!("{} in {}", team.to_uppercase(), season) });
+    }
+
+    // sorted the same way as the leaderboards, with anyone missing the stat at the end by name
+    let order = SortOrder::for_stat(higher_is_better(stat), false);
+    let mut sorted: Vec<&CleanPlayerSeason> = rank_by(&rows, usize::MAX, order, options, |s| season_value(stat, s)).iter().map(|row| *row.item).collect();
+    let mut missing: Vec<&CleanPlayerSeason> = rows.iter().copied().filter(|s| season_value(stat, s).is_none()).collect();
+    missing.sort_by_key(|s| s.sort_name());
+    sorted.extend(missing);
+
+    let team_name = rows[0].team.clone();
+    let extra_column = !LINE_STATS.contains(&stat);
+
+    let mut table = Table::new(format!("{} {}, {} players by {}", team_name, season, rows.len(), stat))
+        .column("Player", 25)
+        .column("Pos", 4)
+        .right("G", 4)
+        .right("AB", 5)
+        .right("R", 4)
+        .right("H", 4)
+        .right("2B", 4)
+        .right("3B", 4)
+        .right("HR", 4)
+        .right("RBI", 4)
+        .right("BB", 4)
+        .right("SO", 4)
+        .right("SB", 4)
+        .right("CS", 4)
+        .right("AVG", 5)
+        .right("OBP", 5)
+        .right("SLG", 5)
+        .right("OPS", 5);
+    if extra_column {
+        table = table.right(stat, stat.len().max(6));
+    }
+
+    for s in &sorted {
+        let mut row = vec![
+            format!("{} {}", s.first_name.as_deref().unwrap_or(""), s.last_name).trim().to_string(),
+            s.position.to_string(),
+            s.games_played.to_string(),
+            s.at_bats.to_string(),
+            s.runs.to_string(),
+            s.hits.to_string(),
+            s.doubles.to_string(),
+            s.triples.to_string(),
+            s.homeruns.to_string(),
+            optional(s.rbi),
+            s.walks.to_string(),
+            optional(s.strikeouts),
+            optional(s.stolen_bases),
+            optional(s.caught_stealing),
+            format_rate(s.batting_average),
+            optional(s.on_base_percentage.map(format_rate)),
+            format_rate(s.slugging_percentage),
+            optional(s.on_base_plus_slugging.map(format_rate)),
+        ];
+        if extra_column {
+            row.push(optional(season_value(stat, s).map(|v| format_stat_value(stat, v))));
+        }
+        table.row(row);
+    }
+
+    // the team's line is its players' rows added up, the same way a career is (see versus-teams)
+    let totals = CareerBuilder::new(clean_records, options).career(&team_name, &rows);
+    let rate = |count: u32| if totals.total_at_bats > 0 { format_rate(count as f64 / totals.total_at_bats as f64) } else { "--".to_string() };
+    let total_bases = totals.total_hits + totals.total_doubles + 2 * totals.total_triples + 3 * totals.total_homeruns;
+
+    table.separator();
+    let mut row = vec![
+        "Team".to_string(),
+        String::new(),
+        String::new(),
+        totals.total_at_bats.to_string(),
+        totals.total_runs.to_string(),
+        totals.total_hits.to_string(),
+        totals.total_doubles.to_string(),
+        totals.total_triples.to_string(),
+        totals.total_homeruns.to_string(),
+        totals.total_rbi.to_string(),
+        totals.total_walks.to_string(),
+        totals.total_strikeouts.to_string(),
+        totals.total_stolen_bases.to_string(),
+        totals.total_caught_stealing.to_string(),
+        rate(totals.total_hits),
+        String::new(),
+        rate(total_bases),
+        String::new(),
+    ];
+    if extra_column {
+        row.push(String::new());
+    }
+    table.row(row);
+
+    out.table(&table);
+    Ok(())
+}
@@ -0,0 +1,125 @@
+// how many games each season was scheduled for, so the season qualifier for rate stats can shrink with a
+// short season: 502 at bats is 3.1 a game over 162 games, which nobody could reach in 2020's 60
+// the strike years and 2020 are built in; --season-lengths adds or corrects others (the 154 game years,
+// say), and the qualifier only pro-rates with --prorate-short-seasons (Options::prorate), so the usual
+// lists stay as they were; the lengths go around in the options (Options::season_lengths)
+
+use std::collections::BTreeMap;
+
+use csv::ReaderBuilder;
+use serde::Deserialize;
+
+use crate::data::{current_year, implausible_season};
+use crate::error::{check_exists, Error, Result};
+
+pub const FULL_SEASON_GAMES: u32 = 162;
+
+// the seasons cut short, with the games each team played (about; it varied by a few games from team to team)
+const SHORT_SEASONS: &[(u32, u32)] = &[
+    (1981, 107),  // the players' strike, June to August
+    (1994, 115),  // the strike that ended the season in August
+    (1995, 144),  // started late, after the strike
+    (2020, 60),   // the pandemic
+];
+
+// the season lengths: any from a --season-lengths file, checked before the built-in ones
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeasonLengths {
+    from_file: BTreeMap<u32, u32>,
+}
+
+// one row of a season lengths file
+#[derive(Debug, Deserialize)]
+struct SeasonLength {
+    season: u32,
+    games: u32,
+}
+
+impl SeasonLengths {
+    // read a season,games csv
+    pub fn load(file_path: &str) -> Result<SeasonLengths> {
+        check_exists("season lengths file", file_path)?;
+        let mut reader = ReaderBuilder::new().has_headers(true).trim(csv::Trim::All).from_path(file_path)?;
+
+        let this_year = current_year();
+        let mut from_file = BTreeMap::new();
+        for (line, result) in reader.deserialize().enumerate() {
+            let row: SeasonLength = result?;
+            if let Some(reason) = implausible_season(row.season, this_year) {
+                return Err(Error::Data(format!("{} line {}: {}", file_path, line + 2, reason)));
+            }
+            if row.games == 0 || row.games > FULL_SEASON_GAMES {
+                return Err(Error::Data(format!("{} line {}: {} games isn't a season (1 to {})", file_path, line + 2, row.games, FULL_SEASON_GAMES)));
+            }
+            from_file.insert(row.season, row.games);
+        }
+        Ok(SeasonLengths { from_file })
+    }
+
+    // how many seasons the file had
+    pub fn len(&self) -> usize {
+        self.from_file.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.from_file.is_empty()
+    }
+
+    // the games a season was scheduled for: the file's, the built-in short seasons, or a full 162
+    pub fn games(&self, season: u32) -> u32 {
+        if let Some(&games) = self.from_file.get(&season) {
+            return games;
+        }
+        SHORT_SEASONS.iter().find(|(year, _)| *year == season).map_or(FULL_SEASON_GAMES, |(_, games)| *games)
+    }
+
+    // a season minimum in at bats, pro-rated to the season's length when --prorate-short-seasons is on
+    pub fn prorated(&self, min_at_bats: u32, season: u32, prorate: bool) -> u32 {
+        if !prorate {
+            return min_at_bats;
+        }
+        (min_at_bats as f64 * self.games(season) as f64 / FULL_SEASON_GAMES as f64).round() as u32
+    }
+}
+
+// what goes after "min 502 AB" in a title when the minimum is pro-rated
+pub fn prorated_note(prorate: bool) -> &'static str {
+    if prorate { ", pro-rated for short seasons" } else { "" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::Scratch;
+
+    #[test]
+    fn short_seasons_pro_rate_the_qualifier() {
+        let lengths = SeasonLengths::default();
+        assert_eq!(lengths.prorated(502, 2020, true), 186);
+        assert_eq!(lengths.prorated(502, 1994, true), 356);
+        // a full season, or pro-rating off, leaves it alone
+        assert_eq!(lengths.prorated(502, 2019, true), 502);
+        assert_eq!(lengths.prorated(502, 2020, false), 502);
+    }
+
+    #[test]
+    fn a_lengths_file_adds_and_corrects_seasons() {
+        let scratch = Scratch::new("season_lengths");
+        let path = scratch.write("lengths.csv", "season,games\n1918, 128\n2020,58\n");
+        let lengths = SeasonLengths::load(&path).unwrap();
+        assert_eq!(lengths.len(), 2);
+        assert_eq!(lengths.games(1918), 128);
+        assert_eq!(lengths.prorated(502, 1918, true), 397);
+        assert_eq!(lengths.prorated(502, 2020, true), 180);
+        assert_eq!(lengths.games(1981), 107);
+    }
+
+    #[test]
+    fn a_lengths_file_has_to_make_sense() {
+        let scratch = Scratch::new("bad_season_lengths");
+        for bad in ["season,games\n1918,0\n", "season,games\n1918,163\n", "season,games\n1850,154\n"] {
+            let path = scratch.write("lengths.csv", bad);
+            assert!(matches!(SeasonLengths::load(&path), Err(Error::Data(_))), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// the schema command: everything a front end needs to build its own query screens, as one json document
+// (every stat with its type, the scopes it can be ranked in and its qualifiers, and every command with
+// its options), so a web page or a script can offer the stats this build and this data have without
+// keeping its own list
+//
+// the options come from the command line definition itself, so they can't drift from what the tool takes
+
+use std::any::TypeId;
+
+use clap::Command;
+use serde_json::{json, Value};
+
+use crate::adjusted::can_adjust;
+use crate::careers::AggregatedPlayer;
+use crate::data::{leagues_in, CleanPlayerSeason};
+use crate::join::joined_columns;
+use crate::leaders::{default_min_attempts, Scope, CAREER_QUALIFYING_AT_BATS, SEASON_QUALIFYING_AT_BATS};
+use crate::stats::{all_stats, has_career_value, is_rate_stat, unavailable_reason, Stat, StatFormat};
+
+// bumped when a field is renamed or removed (adding one doesn't change it)
+pub const SCHEMA_VERSION: u32 = 1;
+
+// the scopes top and bottom rank in; rookie and final are seasons too, so every stat has them
+const SCOPES: &[(&str, &str)] = &[
+    ("season", "single seasons"),
+    ("career", "whole careers"),
+    ("rookie", "each player's first season in the data"),
+    ("final", "each player's last season in the data"),
+];
+
+fn format_name(format: StatFormat) -> &'static str {
+    match format {
+        StatFormat::Count => "count",
+        StatFormat::Rate => "rate",
+        StatFormat::Percent => "percent",
+        StatFormat::Decimal => "decimal",
+        StatFormat::Runs => "runs",
+    }
+}
+
+fn scopes(season: bool, career: bool) -> Vec<&'static str> {
+    SCOPES.iter().map(|(name, _)| *name).filter(|&name| if name == "career" { career } else { season }).collect()
+}
+
+// one of our stats (built in, or added with stats::register)
+fn stat_entry(stat: &dyn Stat, clean_records: &[CleanPlayerSeason]) -> Value {
+    let name = stat.name();
+    let career = stat.has_career_value();
+    let rate = is_rate_stat(name);
+    json!({
+        "name": name,
+        "aliases": stat.aliases(),
+        "description": stat.description(),
+        "type": format_name(stat.format()),
+        "higher_is_better": stat.higher_is_better(),
+        "scopes": scopes(stat.has_season_value(), career),
+        // rate stats only rank qualified players unless --min-ab says otherwise
+        "min_ab": if rate {
+            json!({ "season": SEASON_QUALIFYING_AT_BATS, "career": if career { json!(CAREER_QUALIFYING_AT_BATS) } else { Value::Null } })
+        } else {
+            Value::Null
+        },
+        // and the ones built on steal attempts, a minimum of those (--min-attempts)
+        "min_attempts": match default_min_attempts(name, Scope::Season) {
+            0 => Value::Null,
+            season => json!({ "season": season, "career": default_min_attempts(name, Scope::Career) }),
+        },
+        "adjustable": can_adjust(name),
+        "joined": false,
+        // (null when there's no data to look in)
+        "available": if clean_records.is_empty() { Value::Null } else { json!(unavailable_reason(name, clean_records).is_none()) },
+    })
+}
+
+// a column from a --join file: a number, a career one only when it was joined on link alone
+fn joined_entry(name: &str, clean_records: &[CleanPlayerSeason]) -> Value {
+    let on_seasons = clean_records.iter().any(|s| s.extras.contains_key(name));
+    let scopes: Vec<&str> = scopes(on_seasons, has_career_value(name));
+    json!({
+        "name": name,
+        "aliases": [],
+        "description": "joined from a --join file",
+        "type": "number",
+        "higher_is_better": true,
+        "scopes": scopes,
+        "min_ab": Value::Null,
+        "min_attempts": Value::Null,
+        "adjustable": on_seasons && can_adjust(name),
+        "joined": true,
+        "available": true,
+    })
+}
+
+// what kind of value an option takes, from how the command line parses it ("stat" for a name from the stats list)
+fn value_type(arg: &clap::Arg) -> &'static str {
+    if !arg.get_action().takes_values() {
+        return "flag";
+    }
+    if arg.get_id() == "stat" {
+        return "stat";
+    }
+    if !arg.get_possible_values().is_empty() {
+        return "choice";
+    }
+    let parsed = arg.get_value_parser().type_id();
+    if [TypeId::of::<u32>(), TypeId::of::<usize>(), TypeId::of::<u64>(), TypeId::of::<i64>()].iter().any(|&t| parsed == t) {
+        "integer"
+    } else if parsed == TypeId::of::<f64>() {
+        "number"
+    } else {
+        "string"
+    }
+}
+
+// an option or argument, leaving out --help and --version
+fn option_entries(command: &Command, global: bool) -> Vec<Value> {
+    command
+        .get_arguments()
+        .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version") && arg.is_global_set() == global)
+        .map(|arg| {
+            // a flag's true and false aren't choices anyone types
+            let choices: Vec<Value> = arg
+                .get_possible_values()
+                .iter()
+                .filter(|value| arg.get_action().takes_values() && !value.is_hide_set())
+                .map(|value| json!({ "name": value.get_name(), "description": value.get_help().map(|h| h.to_string()) }))
+                .collect();
+            let default: Vec<String> = arg.get_default_values().iter().map(|v| v.to_string_lossy().into_owned()).collect();
+            json!({
+                "name": arg.get_long().map(|long| format!("--{}", long)).unwrap_or_else(|| arg.get_id().to_string()),
+                "positional": arg.is_positional(),
+                "description": arg.get_help().map(|h| h.to_string()),
+                "type": value_type(arg),
+                "choices": choices,
+                "default": default.first(),
+                "required": arg.is_required_set(),
+                "multiple": arg.get_num_args().is_some_and(|n| n.max_values() > 1) || matches!(arg.get_action(), clap::ArgAction::Append),
+            })
+        })
+        .collect()
+}
+
+// the whole document: the stats (ours, then any joined columns), the scopes, the options every command
+// takes, and each command with its own
+pub fn schema_document(cli: &Command, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer]) -> Value {
+    let mut stats: Vec<Value> = all_stats().into_iter().map(|stat| stat_entry(stat, clean_records)).collect();
+    stats.extend(joined_columns(clean_records, aggregated_players).iter().map(|name| joined_entry(name, clean_records)));
+
+    let commands: Vec<Value> = cli
+        .get_subcommands()
+        .filter(|command| !command.is_hide_set())
+        .map(|command| {
+            json!({
+                "name": command.get_name(),
+                "description": command.get_about().map(|about| about.to_string()),
+                "options": option_entries(command, false),
+            })
+        })
+        .collect();
+
+    let first_season = clean_records.iter().map(|s| s.season).min();
+    let last_season = clean_records.iter().map(|s| s.season).max();
+
+    // null without a season file to describe
+    let data = if clean_records.is_empty() {
+        Value::Null
+    } else {
+        json!({
+            "season_rows": clean_records.len(),
+            "players": aggregated_players.len(),
+            "first_season": first_season,
+            "last_season": last_season,
+            "leagues": leagues_in(clean_records),
+        })
+    };
+
+    json!({
+        "schema": "baseball-stats",
+        "schema_version": SCHEMA_VERSION,
+        "data": data,
+        "stats": stats,
+        "scopes": SCOPES.iter().map(|(name, description)| json!({ "name": name, "description": description })).collect::<Vec<Value>>(),
+        "global_options": option_entries(cli, true),
+        "commands": commands,
+    })
+}
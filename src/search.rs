@@ -0,0 +1,149 @@
+// finding players by name
+// names go into an fst (a compact sorted automaton) so prefix and fuzzy lookups stay fast
+// and small even with millions of historical and minor league names loaded
+
+use std::collections::{BTreeMap, HashSet};
+
+use fst::automaton::{Levenshtein, Str};
+use fst::{Automaton, IntoStreamer, Map, Streamer};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::careers::AggregatedPlayer;
+use crate::error::{Error, Result};
+use crate::lookup::player_not_found;
+use crate::table::{Output, Table};
+
+// the index: every searchable name maps to a list of players who have it
+pub struct NameIndex {
+    names: Map<Vec<u8>>,
+    postings: Vec<Vec<u32>>,  // indices into the aggregated players list
+}
+
+// lower case, single spaces and no accents, so "  Carlos   BELTRAN" and "carlos beltrán" are the same key
+// (accents come off by splitting each letter into its base and its marks and dropping the marks; the
+// names themselves are never changed, only the keys they're found by)
+pub fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+impl NameIndex {
+    // build the index from the career records
+    // each player is findable by "first last" and by last name on its own
+    pub fn build(aggregated_players: &[AggregatedPlayer]) -> Result<NameIndex> {
+        // the fst needs its keys added in sorted order, which a btreemap gives us for free
+        let mut keys: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+
+        for (i, player) in aggregated_players.iter().enumerate() {
+            let full_name = normalize_name(&format!("{} {}", player.first_name, player.last_name));
+            let last_name = normalize_name(&player.last_name);
+
+            keys.entry(full_name).or_default().push(i as u32);
+            if !last_name.is_empty() {
+                keys.entry(last_name).or_default().push(i as u32);
+            }
+        }
+
+        let mut postings = Vec::with_capacity(keys.len());
+        let mut entries = Vec::with_capacity(keys.len());
+
+        for (key, players) in keys {
+            entries.push((key, postings.len() as u64));
+            postings.push(players);
+        }
+
+        let names = Map::from_iter(entries)?;
+
+        Ok(NameIndex { names, postings })
+    }
+
+    // run any fst automaton over the names and collect the players it matches (no repeats)
+    fn collect<A: Automaton>(&self, automaton: A) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut seen = HashSet::new();
+        let mut stream = self.names.search(automaton).into_stream();
+
+        while let Some((_, posting)) = stream.next() {
+            for &player in &self.postings[posting as usize] {
+                let player = player as usize;
+                if seen.insert(player) {
+                    found.push(player);
+                }
+            }
+        }
+
+        found
+    }
+
+    // every player with a name starting with the query
+    pub fn prefix(&self, query: &str) -> Vec<usize> {
+        let query = normalize_name(query);
+        self.collect(Str::new(&query).starts_with())
+    }
+
+    // every player with a name within `distance` typos of the query
+    pub fn fuzzy(&self, query: &str, distance: u32) -> Result<Vec<usize>> {
+        let query = normalize_name(query);
+        let automaton = Levenshtein::new(&query, distance).map_err(|e| Error::Usage(format!("--fuzzy {}: {}", distance, e)))?;
+        Ok(self.collect(automaton))
+    }
+}
+
+// the search command
+pub fn search(out: &mut Output, query: &str, fuzzy: Option<u32>, limit: usize, aggregated_players: &[AggregatedPlayer]) -> Result<()> {
+    let index = NameIndex::build(aggregated_players)?;
+
+    let mut found = match fuzzy {
+        Some(distance) => index.fuzzy(query, distance)?,
+        None => index.prefix(query),
+    };
+
+    // nothing starts with that? try allowing a typo (or two, for longer names) before giving up
+    if found.is_empty() && fuzzy.is_none() {
+        let distance = if query.chars().count() > 5 { 2 } else { 1 };
+        found = index.fuzzy(query, distance)?;
+        if !found.is_empty() {
+            out.line(&format!("No names start with \"{}\", showing close matches instead", query));
+        }
+    }
+
+    if found.is_empty() {
+        return Err(player_not_found(query));
+    }
+
+    let mut players: Vec<&AggregatedPlayer> = found.iter().map(|&i| &aggregated_players[i]).collect();
+    players.sort_by_key(|p| (p.last_name.to_lowercase(), p.first_name.to_lowercase(), p.first_season));
+
+    let mut table = Table::new(format!("{} players matching \"{}\"", players.len(), query))
+        .column("First Name", 15)
+        .column("Last Name", 15)
+        .column("From", 6)
+        .column("To", 6)
+        .column("Teams", 25)
+        .column("Link", 15);
+
+    for player in players.iter().take(limit) {
+        table.row(vec![
+            player.first_name.clone(),
+            player.last_name.clone(),
+            player.first_season.to_string(),
+            player.last_season.to_string(),
+            player.teams.clone(),
+            player.link.clone(),
+        ]);
+    }
+
+    out.table(&table);
+
+    if players.len() > limit {
+        out.line(&format!("... and {} more (use --limit to see them)", players.len() - limit));
+    }
+
+    Ok(())
+}
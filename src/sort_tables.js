@@ -0,0 +1,29 @@
+// click a column heading to sort the table by it, click again to turn it round
+// numbers sort as numbers (".300" and "1,234" included), "--" and blanks go last
+document.querySelectorAll("table").forEach(function (table) {
+  var headings = table.querySelectorAll("thead th");
+  headings.forEach(function (th, column) {
+    th.addEventListener("click", function () {
+      var ascending = !th.classList.contains("sorted-asc");
+      headings.forEach(function (h) { h.classList.remove("sorted-asc", "sorted-desc"); });
+      th.classList.add(ascending ? "sorted-asc" : "sorted-desc");
+
+      var body = table.tBodies[0];
+      var rows = Array.prototype.slice.call(body.rows);
+      var key = function (row) {
+        var text = row.cells[column] ? row.cells[column].textContent.trim() : "";
+        var number = parseFloat(text.replace(/,/g, ""));
+        return { text: text, number: isNaN(number) ? null : number };
+      };
+
+      rows.sort(function (a, b) {
+        var x = key(a), y = key(b);
+        if (x.text === "" || x.text === "--") return 1;
+        if (y.text === "" || y.text === "--") return -1;
+        var order = (x.number !== null && y.number !== null) ? x.number - y.number : x.text.localeCompare(y.text);
+        return ascending ? order : -order;
+      });
+      rows.forEach(function (row) { body.appendChild(row); });
+    });
+  });
+});
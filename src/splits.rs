@@ -0,0 +1,338 @@
+// first half / second half splits, from an optional file (the season data only has whole seasons)
+// the file has one row per player, season and half:
+//   season,link,half,games_played,at_bats,runs,hits,doubles,triples,homeruns,walks,strikeouts
+// where half is 1 or 2 (or first / second), and strikeouts can be blank
+
+use std::collections::HashMap;
+
+use csv::ReaderBuilder;
+use log::warn;
+use serde::Deserialize;
+
+use crate::careers::AggregatedPlayer;
+use crate::data::parse_optional_number;
+use crate::error::{check_exists, Error, Result};
+use crate::leaders::HALF_QUALIFYING_AT_BATS;
+use crate::lookup::{player_not_found, PlayerIndex};
+use crate::options::Options;
+use crate::ranking::{rank_by, Rankable, SortOrder};
+use crate::stats::format_rate;
+use crate::table::{Output, Table};
+
+// what goes in the message when a splits command is run without a file
+pub const SPLITS_FILE_HELP: &str = "needs a splits file: --splits PATH, a csv with season,link,half,games_played,at_bats,runs,hits,doubles,triples,homeruns,walks,strikeouts columns (half is 1 or 2)";
+
+// one row as it comes out of the file
+#[derive(Debug, Deserialize)]
+struct RawSplit {
+    season: u32,
+    link: String,
+    half: String,
+    games_played: u32,
+    at_bats: u32,
+    runs: u32,
+    hits: u32,
+    doubles: u32,
+    triples: u32,
+    homeruns: u32,
+    walks: u32,
+    strikeouts: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Half {
+    First,
+    Second,
+}
+
+// one player's line for half a season
+#[derive(Debug, Clone)]
+pub struct HalfSplit {
+    pub season: u32,
+    pub link: String,
+    pub half: Half,
+    pub games_played: u32,
+    pub at_bats: u32,
+    pub runs: u32,
+    pub hits: u32,
+    pub doubles: u32,
+    pub triples: u32,
+    pub homeruns: u32,
+    pub walks: u32,
+    pub strikeouts: Option<u32>,
+}
+
+impl HalfSplit {
+    // the rates, worked out from the counts the same way as everywhere else (PA = AB + BB)
+    fn average(&self) -> Option<f64> {
+        (self.at_bats > 0).then(|| self.hits as f64 / self.at_bats as f64)
+    }
+
+    fn on_base(&self) -> Option<f64> {
+        let plate_appearances = self.at_bats + self.walks;
+        (plate_appearances > 0).then(|| (self.hits + self.walks) as f64 / plate_appearances as f64)
+    }
+
+    fn slugging(&self) -> Option<f64> {
+        let total_bases = self.hits + self.doubles + 2 * self.triples + 3 * self.homeruns;
+        (self.at_bats > 0).then(|| total_bases as f64 / self.at_bats as f64)
+    }
+
+    fn ops(&self) -> Option<f64> {
+        Some(self.on_base()? + self.slugging()?)
+    }
+}
+
+fn parse_half(value: &str) -> Option<Half> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "first" | "1st" | "h1" => Some(Half::First),
+        "2" | "second" | "2nd" | "h2" => Some(Half::Second),
+        _ => None,
+    }
+}
+
+// read a splits file
+pub fn load_splits(file_path: &str) -> Result<Vec<HalfSplit>> {
+    check_exists("splits file", file_path)?;
+    let mut reader = ReaderBuilder::new().has_headers(true).trim(csv::Trim::All).from_path(file_path)?;
+
+    let mut splits = Vec::new();
+    let mut skipped = 0;
+
+    for result in reader.deserialize() {
+        let raw: RawSplit = result?;
+        let Some(half) = parse_half(&raw.half) else {
+            skipped += 1;
+            continue;
+        };
+
+        splits.push(HalfSplit {
+            season: raw.season,
+            link: raw.link,
+            half,
+            games_played: raw.games_played,
+            at_bats: raw.at_bats,
+            runs: raw.runs,
+            hits: raw.hits,
+            doubles: raw.doubles,
+            triples: raw.triples,
+            homeruns: raw.homeruns,
+            walks: raw.walks,
+            strikeouts: parse_optional_number(&raw.strikeouts),
+        });
+    }
+
+    if skipped > 0 {
+        warn!("{} split rows had a half that wasn't 1 or 2 and were skipped", skipped);
+    }
+
+    Ok(splits)
+}
+
+fn show_rate(value: Option<f64>) -> String {
+    value.map(format_rate).unwrap_or_else(|| "--".to_string())
+}
+
+// the change from one rate to another, +.045 style
+fn show_change(before: Option<f64>, after: Option<f64>) -> String {
+    match (before, after) {
+        (Some(before), Some(after)) => {
+            let change = after - before;
+            let sign = if change < 0.0 { "-" } else { "+" };
+            format!("{}{}", sign, format_rate(change.abs()))
+        }
+        _ => "--".to_string(),
+    }
+}
+
+// the halves command: one player's season, first half against second half
+pub fn show_halves(out: &mut Output, query: &str, season: u32, splits: &[HalfSplit], players: &PlayerIndex) -> Result<()> {
+    if splits.is_empty() {
+        return Err(Error::Usage(format!("halves {}", SPLITS_FILE_HELP)));
+    }
+
+    let matches = players.find(query);
+    if matches.is_empty() {
+        return Err(player_not_found(query));
+    }
+
+    // "betts" matches every Betts, so only the ones with splits that season are shown
+    let mut shown = 0;
+    for player in matches {
+        let halves: Vec<&HalfSplit> = splits.iter().filter(|s| s.link == player.link && s.season == season).collect();
+        let first = halves.iter().find(|s| s.half == Half::First);
+        let second = halves.iter().find(|s| s.half == Half::Second);
+
+        if first.is_none() && second.is_none() {
+            continue;
+        }
+        shown += 1;
+
+        let mut table = Table::new(format!("{} {}, {} by half", player.first_name, player.last_name, season))
+            .column("Half", 7)
+            .right("G", 4)
+            .right("AB", 4)
+            .right("R", 4)
+            .right("H", 4)
+            .right("2B", 4)
+            .right("3B", 4)
+            .right("HR", 4)
+            .right("BB", 4)
+            .right("SO", 4)
+            .right("AVG", 5)
+            .right("OBP", 5)
+            .right("SLG", 5)
+            .right("OPS", 5);
+
+        for (label, split) in [("First", first), ("Second", second)] {
+            let Some(split) = split else {
+                continue;
+            };
+            table.row(vec![
+                label.to_string(),
+                split.games_played.to_string(),
+                split.at_bats.to_string(),
+                split.runs.to_string(),
+                split.hits.to_string(),
+                split.doubles.to_string(),
+                split.triples.to_string(),
+                split.homeruns.to_string(),
+                split.walks.to_string(),
+                split.strikeouts.map(|v| v.to_string()).unwrap_or_else(|| "--".to_string()),
+                show_rate(split.average()),
+                show_rate(split.on_base()),
+                show_rate(split.slugging()),
+                show_rate(split.ops()),
+            ]);
+        }
+
+        // the season as a whole, from the season data, under a separator
+        if let Some(whole) = players.season(&player.link, season).first() {
+            table.separator();
+            table.row(vec![
+                "Season".to_string(),
+                whole.games_played.to_string(),
+                whole.at_bats.to_string(),
+                whole.runs.to_string(),
+                whole.hits.to_string(),
+                whole.doubles.to_string(),
+                whole.triples.to_string(),
+                whole.homeruns.to_string(),
+                whole.walks.to_string(),
+                whole.strikeouts.map(|v| v.to_string()).unwrap_or_else(|| "--".to_string()),
+                format_rate(whole.batting_average),
+                show_rate(whole.on_base_percentage),
+                format_rate(whole.slugging_percentage),
+                show_rate(whole.on_base_plus_slugging),
+            ]);
+        }
+
+        out.table(&table);
+
+        if let (Some(first), Some(second)) = (first, second) {
+            out.line(&format!(
+                "Second half against first: AVG {}, OBP {}, SLG {}, OPS {}",
+                show_change(first.average(), second.average()),
+                show_change(first.on_base(), second.on_base()),
+                show_change(first.slugging(), second.slugging()),
+                show_change(first.ops(), second.ops()),
+            ));
+        }
+    }
+
+    if shown == 0 {
+        return Err(Error::NotFound { what: "splits", path: format!("for \"{}\" in {}", query, season) });
+    }
+    Ok(())
+}
+
+// a player's two halves of one season, for the surges leaderboard
+struct Surge<'a> {
+    first: &'a HalfSplit,
+    second: &'a HalfSplit,
+    player: Option<&'a AggregatedPlayer>,
+}
+
+impl Rankable for Surge<'_> {
+    // ties go to the fewer second half at-bats, like everywhere else
+    fn at_bats(&self) -> u32 {
+        self.second.at_bats
+    }
+
+    fn sort_name(&self) -> (String, String) {
+        match self.player {
+            Some(p) => (p.last_name.to_lowercase(), p.first_name.to_lowercase()),
+            None => (self.first.link.clone(), String::new()),
+        }
+    }
+
+    fn season(&self) -> u32 {
+        self.first.season
+    }
+}
+
+// the surges command: the biggest jumps in OPS from the first half of a season to the second
+// (or the biggest fades, with --fades)
+pub fn show_surges(out: &mut Output, min_ab: Option<u32>, fades: bool, top: usize, splits: &[HalfSplit], aggregated_players: &[AggregatedPlayer], options: &Options) -> Result<()> {
+    if splits.is_empty() {
+        return Err(Error::Usage(format!("surges {}", SPLITS_FILE_HELP)));
+    }
+
+    // both halves have to clear the qualifier, or one hot month looks like a surge
+    let min_at_bats = min_ab.unwrap_or(HALF_QUALIFYING_AT_BATS);
+
+    let players: HashMap<&str, &AggregatedPlayer> = aggregated_players.iter().map(|p| (p.link.as_str(), p)).collect();
+
+    let mut seconds: HashMap<(u32, &str), &HalfSplit> = HashMap::new();
+    for split in splits.iter().filter(|s| s.half == Half::Second) {
+        seconds.insert((split.season, split.link.as_str()), split);
+    }
+
+    let surges: Vec<Surge> = splits
+        .iter()
+        .filter(|s| s.half == Half::First)
+        .filter_map(|first| {
+            let second = seconds.get(&(first.season, first.link.as_str()))?;
+            Some(Surge { first, second, player: players.get(first.link.as_str()).copied() })
+        })
+        .collect();
+
+    let order = if fades { SortOrder::Ascending } else { SortOrder::Descending };
+    let ranked = rank_by(&surges, top, order, options, |s| {
+        if s.first.at_bats < min_at_bats || s.second.at_bats < min_at_bats {
+            return None;
+        }
+        Some(s.second.ops()? - s.first.ops()?)
+    });
+
+    let label = if fades { "fades" } else { "surges" };
+    let mut table = Table::new(format!("Top {} second half {} in OPS (min {} AB each half)", top, label, min_at_bats))
+        .column("Rank", 4)
+        .column("First Name", 15)
+        .column("Last Name", 15)
+        .column("Season", 6)
+        .right("1st OPS", 7)
+        .right("2nd OPS", 7)
+        .right("Change", 6);
+
+    for row in &ranked {
+        let surge = row.item;
+        let (first_name, last_name) = match surge.player {
+            Some(p) => (p.first_name.clone(), p.last_name.clone()),
+            None => (String::new(), surge.first.link.clone()),
+        };
+
+        table.row(vec![
+            row.rank.to_string(),
+            first_name,
+            last_name,
+            surge.first.season.to_string(),
+            show_rate(surge.first.ops()),
+            show_rate(surge.second.ops()),
+            show_change(surge.first.ops(), surge.second.ops()),
+        ]);
+    }
+
+    out.table(&table);
+    Ok(())
+}
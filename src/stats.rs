@@ -0,0 +1,597 @@
+// looking up a stat by name, so commands can take the stat as text instead of having one match arm each
+
+use std::sync::{RwLock, RwLockReadGuard};
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::teams::team_share;
+
+// what a stat is: its standard name, the other names people type, how to work it out for a season and
+// for a career, and how to show it
+// the built-in stats are below; a program using the library can add its own with register, and every
+// command that takes a stat by name (top, hist, roster, export...) will know it
+pub trait Stat: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn description(&self) -> &'static str;
+
+    // None when the data is missing
+    fn season_value(&self, season: &CleanPlayerSeason) -> Option<f64>;
+
+    // only asked for when has_career_value says there is one
+    fn career_value(&self, _player: &AggregatedPlayer) -> Option<f64> {
+        None
+    }
+
+    // can it be worked out for a whole career? (not averages of averages, or a share of a team)
+    fn has_career_value(&self) -> bool {
+        false
+    }
+
+    // is there a value for one season? (not for something measured across seasons, like consistency)
+    fn has_season_value(&self) -> bool {
+        true
+    }
+
+    // not for strikeouts or getting caught stealing
+    fn higher_is_better(&self) -> bool {
+        true
+    }
+
+    fn format(&self) -> StatFormat {
+        StatFormat::Count
+    }
+}
+
+// how a stat's values are shown; anything but a count is a rate, and needs a minimum number of at bats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatFormat {
+    Count,    // a whole number
+    Rate,     // to three places, without the leading zero (.300)
+    Percent,  // a share, as 12.3%
+    Decimal,  // to one place (speed scores)
+    Runs,     // runs to one place, added up like a count (stolen base runs)
+}
+
+// a built-in stat, as plain functions
+struct BuiltinStat {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    description: &'static str,
+    season: fn(&CleanPlayerSeason) -> Option<f64>,
+    career: Option<fn(&AggregatedPlayer) -> Option<f64>>,
+    career_only: bool,
+    higher_is_better: bool,
+    format: StatFormat,
+}
+
+impl Stat for BuiltinStat {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        self.aliases
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn season_value(&self, season: &CleanPlayerSeason) -> Option<f64> {
+        (self.season)(season)
+    }
+
+    fn career_value(&self, player: &AggregatedPlayer) -> Option<f64> {
+        self.career.and_then(|career| career(player))
+    }
+
+    fn has_career_value(&self) -> bool {
+        self.career.is_some()
+    }
+
+    fn has_season_value(&self) -> bool {
+        !self.career_only
+    }
+
+    fn higher_is_better(&self) -> bool {
+        self.higher_is_better
+    }
+
+    fn format(&self) -> StatFormat {
+        self.format
+    }
+}
+
+// a counting stat: the season's number, and the career total
+macro_rules! counting {
+    ($name:expr, $aliases:expr, $description:expr, $season:expr, $career:expr) => {
+        BuiltinStat { name: $name, aliases: $aliases, description: $description, season: $season, career: Some($career), career_only: false, higher_is_better: true, format: StatFormat::Count }
+    };
+}
+
+// a slash line rate: the season row's, and the career's from its summed counts (see careers.rs)
+macro_rules! rate {
+    ($name:expr, $aliases:expr, $description:expr, $season:expr, $career:expr) => {
+        BuiltinStat { name: $name, aliases: $aliases, description: $description, season: $season, career: Some($career), career_only: false, higher_is_better: true, format: StatFormat::Rate }
+    };
+}
+
+// a rate from the season row that isn't worked out for careers
+macro_rules! season_rate {
+    ($name:expr, $aliases:expr, $description:expr, $season:expr, $format:expr) => {
+        BuiltinStat { name: $name, aliases: $aliases, description: $description, season: $season, career: None, career_only: false, higher_is_better: true, format: $format }
+    };
+}
+
+// every stat we understand, in the order we list them
+const BUILTIN_STATS: &[BuiltinStat] = &[
+    counting!("games", &["g", "games_played"], "games played", |s| Some(s.games_played as f64), |p| Some(p.total_games_played as f64)),
+    counting!("at_bats", &["ab"], "at bats", |s| Some(s.at_bats as f64), |p| Some(p.total_at_bats as f64)),
+    counting!("runs", &["r"], "runs scored", |s| Some(s.runs as f64), |p| Some(p.total_runs as f64)),
+    counting!("hits", &["h"], "hits", |s| Some(s.hits as f64), |p| Some(p.total_hits as f64)),
+    counting!("doubles", &["2b"], "doubles", |s| Some(s.doubles as f64), |p| Some(p.total_doubles as f64)),
+    counting!("triples", &["3b"], "triples", |s| Some(s.triples as f64), |p| Some(p.total_triples as f64)),
+    counting!("homeruns", &["hr", "hrs", "home_runs"], "home runs", |s| Some(s.homeruns as f64), |p| Some(p.total_homeruns as f64)),
+    counting!("rbi", &["rbis"], "runs batted in", |s| s.rbi.map(|v| v as f64), |p| Some(p.total_rbi as f64)),
+    counting!("walks", &["bb"], "walks", |s| Some(s.walks as f64), |p| Some(p.total_walks as f64)),
+    BuiltinStat {
+        higher_is_better: false,
+        ..counting!("strikeouts", &["so", "k"], "strikeouts (fewer is better)", |s| s.strikeouts.map(|v| v as f64), |p| Some(p.total_strikeouts as f64))
+    },
+    counting!("stolen_bases", &["sb"], "stolen bases", |s| s.stolen_bases.map(|v| v as f64), |p| Some(p.total_stolen_bases as f64)),
+    BuiltinStat {
+        higher_is_better: false,
+        ..counting!("caught_stealing", &["cs"], "caught stealing (fewer is better)", |s| s.caught_stealing.map(|v| v as f64), |p| Some(p.total_caught_stealing as f64))
+    },
+    rate!("batting_average", &["avg", "ba"], "batting average (rate)", |s| Some(s.batting_average), |p| p.batting_average),
+    rate!("on_base_percentage", &["obp"], "on base percentage (rate)", |s| s.on_base_percentage, |p| p.on_base_percentage),
+    rate!("slugging_percentage", &["slg"], "slugging percentage (rate)", |s| Some(s.slugging_percentage), |p| p.slugging_percentage),
+    rate!("ops", &["on_base_plus_slugging"], "on base plus slugging (rate)", |s| s.on_base_plus_slugging, |p| p.ops),
+    BuiltinStat {
+        name: "strikeout_rate",
+        aliases: &["k_pct", "k%", "so_rate"],
+        description: "strikeouts per plate appearance, SO / (AB + BB) (rate, fewer is better)",
+        season: |s| strikeout_rate(s.strikeouts?, s.at_bats, s.walks),
+        // no strikeouts at all means they weren't kept, not that he never struck out
+        career: Some(|p| if p.total_strikeouts == 0 { None } else { strikeout_rate(p.total_strikeouts, p.total_at_bats, p.total_walks) }),
+        career_only: false,
+        higher_is_better: false,
+        format: StatFormat::Percent,
+    },
+    BuiltinStat {
+        name: "tto",
+        aliases: &["three_true_outcomes", "tto_rate"],
+        description: "three true outcomes: (HR + BB + SO) / (AB + BB) (rate)",
+        season: |s| tto_rate(s.homeruns, s.walks, s.strikeouts?, s.at_bats),
+        career: Some(|p| if p.total_strikeouts == 0 { None } else { tto_rate(p.total_homeruns, p.total_walks, p.total_strikeouts, p.total_at_bats) }),
+        career_only: false,
+        higher_is_better: true,
+        format: StatFormat::Percent,
+    },
+    season_rate!("homerun_share", &["hr_share", "hr_pct_of_team"], "share of his team's home runs that season (rate)", |s| team_share(s.homeruns, s.team_totals.homeruns), StatFormat::Percent),
+    season_rate!("run_share", &["r_share", "runs_share"], "share of his team's runs that season (rate)", |s| team_share(s.runs, s.team_totals.runs), StatFormat::Percent),
+    season_rate!("hit_share", &["h_share", "hits_share"], "share of his team's hits that season (rate)", |s| team_share(s.hits, s.team_totals.hits), StatFormat::Percent),
+    counting!("times_led", &["led", "titles", "black_ink_count"], "stats he led the majors in (HR, RBI, AVG and nine more), a season's or a career's", |s| Some(s.led.len() as f64), |p| Some(p.times_led.values().sum::<u32>() as f64)),
+    BuiltinStat {
+        name: "baserunning_runs",
+        aliases: &["sb_runs", "bsr", "baserunning"],
+        description: "stolen base runs, 0.2 a steal less 0.4 a time caught (needs 10 attempts a season, 50 a career unless --min-attempts says otherwise)",
+        season: |s| Some(baserunning_runs(s.stolen_bases?, s.caught_stealing?)),
+        career: Some(career_baserunning_runs),
+        career_only: false,
+        higher_is_better: true,
+        format: StatFormat::Runs,
+    },
+    BuiltinStat {
+        name: "speed",
+        aliases: &["spd", "speed_score"],
+        description: "Bill James speed score, 0 to 10 (rate)",
+        season: season_speed,
+        career: Some(career_speed),
+        career_only: false,
+        higher_is_better: true,
+        format: StatFormat::Decimal,
+    },
+    BuiltinStat {
+        career_only: true,
+        higher_is_better: false,
+        ..rate!("consistency", &["ops_sd", "volatility", "steadiness"], "OPS consistency (the standard deviation of his OPS over 5 or more qualified seasons; rate, lower is steadier, careers only)", |_| None, career_consistency)
+    },
+];
+
+// stats added with register, after the built-in ones
+// (the built-in ones are looked up without the lock, since every ranking asks for a value per row)
+static REGISTERED: RwLock<Vec<&'static dyn Stat>> = RwLock::new(Vec::new());
+
+// the registered stats, read under the lock (held only as long as the guard is)
+fn registered() -> RwLockReadGuard<'static, Vec<&'static dyn Stat>> {
+    REGISTERED.read().unwrap_or_else(|e| e.into_inner())
+}
+
+// how a name is looked up: "Home-Runs" is homeruns' alias home_runs
+fn lookup_key(name: &str) -> String {
+    name.trim().to_lowercase().replace('-', "_")
+}
+
+// the first of the stats going by a name (already a lookup key), standard or other
+fn named(mut stats: impl Iterator<Item = &'static dyn Stat>, name: &str) -> Option<&'static dyn Stat> {
+    stats.find(|s| s.name() == name || s.aliases().contains(&name))
+}
+
+// a registered stat, under its names as they're looked up
+struct Registered {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    stat: Box<dyn Stat>,
+}
+
+impl Stat for Registered {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn aliases(&self) -> &'static [&'static str] {
+        self.aliases
+    }
+    fn description(&self) -> &'static str {
+        self.stat.description()
+    }
+    fn season_value(&self, season: &CleanPlayerSeason) -> Option<f64> {
+        self.stat.season_value(season)
+    }
+    fn career_value(&self, player: &AggregatedPlayer) -> Option<f64> {
+        self.stat.career_value(player)
+    }
+    fn has_career_value(&self) -> bool {
+        self.stat.has_career_value()
+    }
+    fn has_season_value(&self) -> bool {
+        self.stat.has_season_value()
+    }
+    fn higher_is_better(&self) -> bool {
+        self.stat.higher_is_better()
+    }
+    fn format(&self) -> StatFormat {
+        self.stat.format()
+    }
+}
+
+// add a stat (it lives until the program ends); its name and aliases can't be ones already taken, and are
+// kept the way they're looked up (so one registered as "wOBA" is found as woba, WOBA or wOBA)
+pub fn register(stat: impl Stat + 'static) -> Result<()> {
+    let names: Vec<String> = std::iter::once(stat.name()).chain(stat.aliases().iter().copied()).map(lookup_key).collect();
+
+    // checked and added under the one lock, so two threads can't both take a name
+    let mut registered = REGISTERED.write().unwrap_or_else(|e| e.into_inner());
+    for name in &names {
+        let builtin = BUILTIN_STATS.iter().map(|s| s as &'static dyn Stat);
+        if named(builtin.chain(registered.iter().copied()), name).is_some() {
+            return Err(Error::Usage(format!("there's already a stat called {}", name)));
+        }
+    }
+
+    let names: Vec<&'static str> = names.into_iter().map(|name| &*Box::leak(name.into_boxed_str())).collect();
+    let (name, aliases) = names.split_first().expect("a stat always has a name");
+    let aliases: &'static [&'static str] = Box::leak(aliases.to_vec().into_boxed_slice());
+    registered.push(Box::leak(Box::new(Registered { name, aliases, stat: Box::new(stat) })));
+    Ok(())
+}
+
+// every stat, in the order we list them
+pub fn all_stats() -> Vec<&'static dyn Stat> {
+    let mut stats: Vec<&'static dyn Stat> = BUILTIN_STATS.iter().map(|s| s as &'static dyn Stat).collect();
+    stats.extend(registered().iter());
+    stats
+}
+
+// a stat by its standard name
+fn stat(name: &str) -> Option<&'static dyn Stat> {
+    match BUILTIN_STATS.iter().find(|s| s.name == name) {
+        Some(builtin) => Some(builtin),
+        None => registered().iter().find(|s| s.name() == name).copied(),
+    }
+}
+
+// a stat by whatever the user typed ("HR", "home_runs", "avg")
+pub fn find_stat(name: &str) -> Option<&'static dyn Stat> {
+    let name = lookup_key(name);
+    match named(BUILTIN_STATS.iter().map(|s| s as &'static dyn Stat), &name) {
+        Some(builtin) => Some(builtin),
+        None => named(registered().iter().copied(), &name),
+    }
+}
+
+// just the standard names, for error messages
+pub fn stat_names() -> Vec<&'static str> {
+    all_stats().iter().map(|s| s.name()).collect()
+}
+
+// the error for a stat name we don't know, with the ones we do
+pub fn unknown_stat(name: &str) -> Error {
+    Error::Usage(format!("Unknown stat \"{}\". Try one of: {}", name, stat_names().join(", ")))
+}
+
+// turn whatever the user typed into our standard stat name
+pub fn normalize_stat_name(name: &str) -> Option<&'static str> {
+    find_stat(name).map(|s| s.name())
+}
+
+// the list of valid --stat values that goes at the bottom of --help
+pub fn stat_help() -> String {
+    let mut help = String::from("Stats (name, other names accepted, what it is):\n");
+
+    for stat in all_stats() {
+        help.push_str(&format!("  {:<20} {:<22} {}\n", stat.name(), stat.aliases().join(", "), stat.description()));
+    }
+
+    help
+}
+
+// the counting stats that go into a speed score
+pub struct SpeedLine {
+    pub at_bats: f64,
+    pub runs: f64,
+    pub hits: f64,
+    pub doubles: f64,
+    pub triples: f64,
+    pub homeruns: f64,
+    pub walks: f64,
+    pub strikeouts: f64,
+    pub stolen_bases: f64,
+    pub caught_stealing: f64,
+}
+
+// Bill James' speed score, from the four parts we have data for (no double plays or fielding position):
+// how often steals succeed, how often he tries, how many triples, and how often he scores once on base
+// each part is scored 0 to 10 and the score is their average
+pub fn speed_score(line: &SpeedLine) -> Option<f64> {
+    let singles = line.hits - line.doubles - line.triples - line.homeruns;
+    let balls_in_play = line.at_bats - line.homeruns - line.strikeouts;
+    let times_on_base = line.hits + line.walks - line.homeruns;
+
+    if singles + line.walks <= 0.0 || balls_in_play <= 0.0 || times_on_base <= 0.0 {
+        return None;
+    }
+
+    let attempts = line.stolen_bases + line.caught_stealing;
+    let parts = [
+        ((line.stolen_bases + 3.0) / (attempts + 7.0) - 0.4) * 20.0,
+        (attempts / (singles + line.walks)).sqrt() / 0.07,
+        line.triples / balls_in_play / 0.02 * 10.0,
+        ((line.runs - line.homeruns) / times_on_base - 0.1) / 0.04,
+    ];
+
+    Some(parts.iter().map(|part| part.clamp(0.0, 10.0)).sum::<f64>() / parts.len() as f64)
+}
+
+// a season's speed score needs steals, caught stealing and strikeouts, which early seasons don't have
+fn season_speed(season: &CleanPlayerSeason) -> Option<f64> {
+    speed_score(&SpeedLine {
+        at_bats: season.at_bats as f64,
+        runs: season.runs as f64,
+        hits: season.hits as f64,
+        doubles: season.doubles as f64,
+        triples: season.triples as f64,
+        homeruns: season.homeruns as f64,
+        walks: season.walks as f64,
+        strikeouts: season.strikeouts? as f64,
+        stolen_bases: season.stolen_bases? as f64,
+        caught_stealing: season.caught_stealing? as f64,
+    })
+}
+
+// a career's speed score comes from the career totals, where missing seasons count as zero
+// steals with no caught stealing at all means caught stealing wasn't kept (1800s careers), which would
+// make every attempt look successful, so those careers don't get a score
+fn career_speed(player: &AggregatedPlayer) -> Option<f64> {
+    if player.total_stolen_bases > 0 && player.total_caught_stealing == 0 {
+        return None;
+    }
+
+    speed_score(&SpeedLine {
+        at_bats: player.total_at_bats as f64,
+        runs: player.total_runs as f64,
+        hits: player.total_hits as f64,
+        doubles: player.total_doubles as f64,
+        triples: player.total_triples as f64,
+        homeruns: player.total_homeruns as f64,
+        walks: player.total_walks as f64,
+        strikeouts: player.total_strikeouts as f64,
+        stolen_bases: player.total_stolen_bases as f64,
+        caught_stealing: player.total_caught_stealing as f64,
+    })
+}
+
+// roughly what a steal gains and getting caught costs in runs, from the run expectancy tables: being
+// thrown out costs about twice what a steal gains, so a runner has to make it two times in three to break even
+pub const STOLEN_BASE_RUNS: f64 = 0.2;
+pub const CAUGHT_STEALING_RUNS: f64 = -0.4;
+
+// the runs a runner's steals and times caught were worth
+pub fn baserunning_runs(stolen_bases: u32, caught_stealing: u32) -> f64 {
+    stolen_bases as f64 * STOLEN_BASE_RUNS + caught_stealing as f64 * CAUGHT_STEALING_RUNS
+}
+
+// a career's stolen base runs, from the totals; as with speed, steals and never caught means caught
+// stealing wasn't kept, and every steal would be a free 0.2
+fn career_baserunning_runs(player: &AggregatedPlayer) -> Option<f64> {
+    if player.total_stolen_bases > 0 && player.total_caught_stealing == 0 {
+        return None;
+    }
+    Some(baserunning_runs(player.total_stolen_bases, player.total_caught_stealing))
+}
+
+// a consistency score needs this many qualified seasons, or two good years in a row would look steady
+pub const CONSISTENCY_SEASONS: usize = 5;
+
+// the standard deviation of a career's OPS over its qualified seasons (the sample one, since the seasons
+// are a sample of what he could do): .030 is a steady hitter, .100 one who came and went
+fn career_consistency(player: &AggregatedPlayer) -> Option<f64> {
+    let seasons = &player.qualified_ops;
+    if seasons.len() < CONSISTENCY_SEASONS {
+        return None;
+    }
+    let mean = seasons.iter().sum::<f64>() / seasons.len() as f64;
+    let variance = seasons.iter().map(|ops| (ops - mean).powi(2)).sum::<f64>() / (seasons.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+// we don't have plate appearances, so at bats plus walks stands in for them (no hit by pitch or sacrifices)
+// the counts are added up as whole numbers and only divided at the end
+fn per_plate_appearance(count: u32, at_bats: u32, walks: u32) -> Option<f64> {
+    let plate_appearances = at_bats + walks;
+    if plate_appearances == 0 {
+        return None;
+    }
+    Some(count as f64 / plate_appearances as f64)
+}
+
+// the share of plate appearances ending in a home run, walk or strikeout
+pub fn tto_rate(homeruns: u32, walks: u32, strikeouts: u32, at_bats: u32) -> Option<f64> {
+    per_plate_appearance(homeruns + walks + strikeouts, at_bats, walks)
+}
+
+// the share of plate appearances ending in a strikeout (K%)
+pub fn strikeout_rate(strikeouts: u32, at_bats: u32, walks: u32) -> Option<f64> {
+    per_plate_appearance(strikeouts, at_bats, walks)
+}
+
+// a stat's value for one season (None when the data is missing); anything that isn't a stat is a joined column
+pub fn season_value(stat_name: &str, season: &CleanPlayerSeason) -> Option<f64> {
+    match stat(stat_name) {
+        Some(stat) => stat.season_value(season),
+        None => season.extras.get(stat_name).copied(),
+    }
+}
+
+// why a stat can't be ranked with this data, if it can't: not one row has a value for it
+// (a whole column missing from the file, or a dataset from before it was kept)
+// a careers-only stat can't be checked from the rows, so its lists just come out empty without the data
+pub fn unavailable_reason(stat: &str, clean_records: &[CleanPlayerSeason]) -> Option<String> {
+    if !has_season_value(stat) {
+        return None;
+    }
+    if clean_records.iter().any(|s| season_value(stat, s).is_some()) {
+        return None;
+    }
+    Some(format!("the data has no {} values", stat))
+}
+
+// every stat that can't be ranked with this data
+pub fn unavailable_stats(clean_records: &[CleanPlayerSeason]) -> Vec<&'static str> {
+    stat_names()
+        .into_iter()
+        .filter(|stat| unavailable_reason(stat, clean_records).is_some())
+        .collect()
+}
+
+// can this stat be worked out for a whole career? (joined columns can, added up or joined on link)
+pub fn has_career_value(stat_name: &str) -> bool {
+    stat(stat_name).is_none_or(|stat| stat.has_career_value())
+}
+
+// is there a value for a single season? (joined columns have one when they were joined on season)
+pub fn has_season_value(stat_name: &str) -> bool {
+    stat(stat_name).is_none_or(|stat| stat.has_season_value())
+}
+
+// a stat's value for a whole career (None for the stats has_career_value rules out, or when it can't be worked out)
+pub fn career_value(stat_name: &str, player: &AggregatedPlayer) -> Option<f64> {
+    if player.skipped.contains(&stat_name) {
+        return None;
+    }
+    match stat(stat_name) {
+        Some(stat) if stat.has_career_value() => stat.career_value(player),
+        Some(_) => None,
+        None => player.extras.get(stat_name).copied(),
+    }
+}
+
+// is a bigger number a better number?
+pub fn higher_is_better(stat_name: &str) -> bool {
+    stat(stat_name).is_none_or(|stat| stat.higher_is_better())
+}
+
+// rates (as opposed to counting stats) need a minimum number of at-bats to mean anything
+pub fn is_rate_stat(stat_name: &str) -> bool {
+    stat(stat_name).is_some_and(|stat| !matches!(stat.format(), StatFormat::Count | StatFormat::Runs))
+}
+
+// rates the baseball way, without the leading zero (.300)
+pub fn format_rate(value: f64) -> String {
+    let formatted = format!("{:.3}", value);
+    formatted.strip_prefix('0').unwrap_or(&formatted).to_string()
+}
+
+// each stat the way its format says; joined columns (WAR, ...) to one place
+pub fn format_stat_value(stat_name: &str, value: f64) -> String {
+    match stat(stat_name).map(|stat| stat.format()) {
+        Some(StatFormat::Count) => format!("{}", value.round()),
+        Some(StatFormat::Rate) => format_rate(value),
+        Some(StatFormat::Percent) => format!("{:.1}%", value * 100.0),
+        Some(StatFormat::Decimal | StatFormat::Runs) | None => format!("{:.1}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // weighted on base average, near enough: walks and hits by their run values, over plate appearances
+    struct Woba;
+
+    impl Stat for Woba {
+        fn name(&self) -> &'static str {
+            "wOBA"
+        }
+        fn aliases(&self) -> &'static [&'static str] {
+            &["Weighted-OBA"]
+        }
+        fn description(&self) -> &'static str {
+            "weighted on base average (rate)"
+        }
+        fn season_value(&self, s: &CleanPlayerSeason) -> Option<f64> {
+            let singles = s.hits - s.doubles - s.triples - s.homeruns;
+            let weighted = 0.69 * s.walks as f64 + 0.89 * singles as f64 + 1.27 * s.doubles as f64 + 1.62 * s.triples as f64 + 2.10 * s.homeruns as f64;
+            Some(weighted / (s.at_bats + s.walks) as f64)
+        }
+        fn format(&self) -> StatFormat {
+            StatFormat::Rate
+        }
+    }
+
+    #[test]
+    fn a_mixed_case_stat_is_found_in_any_case() {
+        register(Woba).unwrap();
+        for typed in ["wOBA", "woba", "WOBA", "weighted_oba", "Weighted-OBA"] {
+            assert_eq!(find_stat(typed).map(|s| s.name()), Some("woba"), "{}", typed);
+        }
+        assert_eq!(find_stat("woba").unwrap().description(), "weighted on base average (rate)");
+
+        // and its names are taken now, in any case
+        assert!(matches!(register(Woba), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn a_built_in_name_cant_be_registered() {
+        struct Homers;
+        impl Stat for Homers {
+            fn name(&self) -> &'static str {
+                "HR"
+            }
+            fn description(&self) -> &'static str {
+                "home runs again"
+            }
+            fn season_value(&self, s: &CleanPlayerSeason) -> Option<f64> {
+                Some(s.homeruns as f64)
+            }
+        }
+        assert!(matches!(register(Homers), Err(Error::Usage(_))));
+        assert_eq!(find_stat("hr").map(|s| s.name()), Some("homeruns"));
+    }
+}
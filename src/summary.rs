@@ -0,0 +1,142 @@
+// a quick look at what got loaded, for sanity checking a dataset
+
+use crate::careers::AggregatedPlayer;
+use crate::data::{leagues_in, CleanPlayerSeason, RejectedRow, SupplementarySeason};
+use crate::stats::unavailable_stats;
+use crate::table::{wrap_text, Output, Table};
+
+// the most rejected rows listed one by one
+const MAX_REJECTED_SHOWN: usize = 25;
+
+// a check or a number pulled from one row, so the tables below can be lists
+type RowCheck = fn(&CleanPlayerSeason) -> bool;
+type RowNumber = fn(&CleanPlayerSeason) -> u64;
+
+// the summary command
+pub fn show_summary(out: &mut Output, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], supplementary: &[SupplementarySeason], rejected: &[RejectedRow]) {
+    out.line("Dataset summary");
+    out.line("===============");
+
+    if clean_records.is_empty() {
+        out.line("No rows loaded");
+        return;
+    }
+
+    // size and coverage
+    let first_season = clean_records.iter().map(|s| s.season).min().unwrap();
+    let last_season = clean_records.iter().map(|s| s.season).max().unwrap();
+
+    let mut teams: Vec<&str> = clean_records.iter().map(|s| s.team.as_ref()).collect();
+    teams.sort();
+    teams.dedup();
+
+    let season_count = {
+        let mut seasons: Vec<u32> = clean_records.iter().map(|s| s.season).collect();
+        seasons.sort();
+        seasons.dedup();
+        seasons.len()
+    };
+
+    out.line(&format!("Rows:            {}", clean_records.len()));
+    out.line(&format!("Unique players:  {}", aggregated_players.len()));
+    out.line(&format!("Seasons:         {}-{} ({} seasons)", first_season, last_season, season_count));
+    out.line(&format!("Teams:           {}", teams.len()));
+    for line in wrap_text(&teams.join(", "), 70) {
+        out.line(&format!("                 {}", line));
+    }
+
+    let mut franchises: Vec<&str> = clean_records.iter().map(|s| s.franchise.as_ref()).collect();
+    franchises.sort();
+    franchises.dedup();
+    if franchises.len() != teams.len() {
+        out.line(&format!("Franchises:      {} (moved and renamed teams counted once)", franchises.len()));
+    }
+    // rows by league, when the data has a league column
+    let leagues = leagues_in(clean_records);
+    if !leagues.is_empty() {
+        let counts: Vec<String> = leagues
+            .iter()
+            .map(|&league| format!("{} {}", league, clean_records.iter().filter(|s| s.league.as_deref() == Some(league)).count()))
+            .collect();
+        out.line(&format!("Leagues:         {} ({} rows)", leagues.len(), counts.join(", ")));
+    }
+    if !supplementary.is_empty() {
+        out.line(&format!("Supplementary:   {} non-MLB rows loaded", supplementary.len()));
+    }
+
+    // how much of the optional data is missing
+    let count_missing = |missing: RowCheck| clean_records.iter().filter(|s| missing(s)).count();
+    let percent = |count: usize| 100.0 * count as f64 / clean_records.len() as f64;
+
+    let mut missing = Table::new("Rows with missing data")
+        .column("Field", 20)
+        .right("Rows", 8)
+        .right("Percent", 8);
+
+    let fields: [(&str, RowCheck); 6] = [
+        ("RBI", |s| s.rbi.is_none()),
+        ("Strikeouts", |s| s.strikeouts.is_none()),
+        ("Stolen bases", |s| s.stolen_bases.is_none()),
+        ("Caught stealing", |s| s.caught_stealing.is_none()),
+        ("On base percentage", |s| s.on_base_percentage.is_none()),
+        ("OPS", |s| s.on_base_plus_slugging.is_none()),
+    ];
+
+    for (name, is_missing) in fields {
+        let count = count_missing(is_missing);
+        missing.row(vec![name.to_string(), count.to_string(), format!("{:.1}%", percent(count))]);
+    }
+
+    out.table(&missing);
+
+    let unavailable = unavailable_stats(clean_records);
+    if !unavailable.is_empty() {
+        out.line("");
+        out.line(&format!("Not in this data at all, so they can't be ranked: {}", unavailable.join(", ")));
+    }
+
+    // top-line totals across every row
+    let total = |stat: RowNumber| -> u64 { clean_records.iter().map(stat).sum() };
+
+    let mut totals = Table::new("Totals across all rows")
+        .column("Stat", 20)
+        .right("Total", 12);
+
+    let stats: [(&str, RowNumber); 11] = [
+        ("Games", |s| s.games_played as u64),
+        ("At bats", |s| s.at_bats as u64),
+        ("Runs", |s| s.runs as u64),
+        ("Hits", |s| s.hits as u64),
+        ("Doubles", |s| s.doubles as u64),
+        ("Triples", |s| s.triples as u64),
+        ("Home runs", |s| s.homeruns as u64),
+        ("RBI", |s| s.rbi.unwrap_or(0) as u64),
+        ("Walks", |s| s.walks as u64),
+        ("Strikeouts", |s| s.strikeouts.unwrap_or(0) as u64),
+        ("Stolen bases", |s| s.stolen_bases.unwrap_or(0) as u64),
+    ];
+
+    for (name, stat) in stats {
+        totals.row(vec![name.to_string(), total(stat).to_string()]);
+    }
+
+    out.table(&totals);
+
+    // rows that were read but left out: unreadable lines, and seasons that can't be right
+    if !rejected.is_empty() {
+        let mut table = Table::new(format!("Rejected rows ({}, not in any of the above)", rejected.len()))
+            .column("File", 24)
+            .right("Line", 8)
+            .column("Reason", 50);
+
+        for row in rejected.iter().take(MAX_REJECTED_SHOWN) {
+            table.row(vec![row.file.clone(), row.line.to_string(), row.reason.clone()]);
+        }
+        if rejected.len() > MAX_REJECTED_SHOWN {
+            table.separator();
+            table.row(vec![format!("and {} more", rejected.len() - MAX_REJECTED_SHOWN), String::new(), String::new()]);
+        }
+
+        out.table(&table);
+    }
+}
@@ -0,0 +1,813 @@
+// drawing tables in the terminal
+// widths are measured in what the terminal actually shows (so "Peña" is 4 wide, not 5 bytes)
+// and text is only ever cut between whole characters, never halfway through one
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use clap::ValueEnum;
+use log::error;
+
+use rust_xlsxwriter::Workbook;
+
+use crate::chart::Chart;
+use crate::xlsx;
+use serde_json::{json, Map, Value};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+// the ways a report can be written out
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+    Html,
+    Xlsx,
+}
+
+impl Format {
+    // guess the format from a file name, so --out leaders.csv does the obvious thing
+    pub fn from_path(path: &str) -> Option<Format> {
+        match Path::new(path).extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            "md" | "markdown" => Some(Format::Markdown),
+            "html" | "htm" => Some(Format::Html),
+            "xlsx" => Some(Format::Xlsx),
+            "txt" => Some(Format::Text),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Text => "txt",
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::Markdown => "md",
+            Format::Html => "html",
+            Format::Xlsx => "xlsx",
+        }
+    }
+}
+
+// which side of the column a cell sits on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub header: String,
+    pub width: usize,
+    pub align: Align,
+}
+
+// a row is either a line of cells or a dashed line (e.g. above a totals row)
+#[derive(Debug, Clone)]
+pub enum Row {
+    Cells(Vec<String>),
+    Separator,
+}
+
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub title: String,
+    pub columns: Vec<Column>,
+    pub rows: Vec<Row>,
+}
+
+// how wide a bit of text is on screen
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+// shorten text to fit a width, ending in "..." when something had to be cut
+pub fn truncate(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    // leave room for the dots if there's space for them
+    let ellipsis = if max_width > 3 { "..." } else { "" };
+    let budget = max_width - ellipsis.len();
+
+    let mut shortened = String::new();
+    let mut width = 0;
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = display_width(grapheme);
+        if width + grapheme_width > budget {
+            break;
+        }
+        shortened.push_str(grapheme);
+        width += grapheme_width;
+    }
+
+    shortened.push_str(ellipsis);
+    shortened
+}
+
+// split text over as many lines as it needs to fit a width, breaking at spaces where possible
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_inclusive(char::is_whitespace) {
+        // a word too long for a line on its own gets chopped up
+        if display_width(word) > width {
+            for grapheme in word.graphemes(true) {
+                if display_width(&current) + display_width(grapheme) > width {
+                    lines.push(current.trim_end().to_string());
+                    current = String::new();
+                }
+                current.push_str(grapheme);
+            }
+            continue;
+        }
+
+        if display_width(&current) + display_width(word) > width {
+            lines.push(current.trim_end().to_string());
+            current = String::new();
+            if word.trim().is_empty() {
+                continue;  // don't start a new line with a space
+            }
+        }
+        current.push_str(word);
+    }
+
+    if !current.trim().is_empty() || lines.is_empty() {
+        lines.push(current.trim_end().to_string());
+    }
+
+    lines
+}
+
+// pad text out to a column width (on the left or right)
+fn pad(text: &str, width: usize, align: Align) -> String {
+    let padding = " ".repeat(width.saturating_sub(display_width(text)));
+    match align {
+        Align::Left => format!("{}{}", text, padding),
+        Align::Right => format!("{}{}", padding, text),
+    }
+}
+
+impl Table {
+    pub fn new(title: impl Into<String>) -> Table {
+        Table {
+            title: title.into(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    // add a left aligned column
+    pub fn column(mut self, header: &str, width: usize) -> Table {
+        self.columns.push(Column { header: header.to_string(), width, align: Align::Left });
+        self
+    }
+
+    // add a right aligned column (for numbers)
+    pub fn right(mut self, header: &str, width: usize) -> Table {
+        self.columns.push(Column { header: header.to_string(), width, align: Align::Right });
+        self
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(Row::Cells(cells));
+    }
+
+    pub fn separator(&mut self) {
+        self.rows.push(Row::Separator);
+    }
+
+    // the total width of a line, counting the single space between columns
+    fn line_width(&self) -> usize {
+        let widths: usize = self.columns.iter().map(|c| c.width).sum();
+        widths + self.columns.len().saturating_sub(1)
+    }
+
+    // lay out one row of cells, as one line or (when wrapping) several
+    fn render_cells(&self, cells: &[String], wrap: bool) -> Vec<String> {
+        let wrapped: Vec<Vec<String>> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let cell = cells.get(i).map(|c| c.as_str()).unwrap_or("");
+                if wrap {
+                    wrap_text(cell, column.width)
+                } else {
+                    vec![truncate(cell, column.width)]
+                }
+            })
+            .collect();
+
+        let height = wrapped.iter().map(|lines| lines.len()).max().unwrap_or(1);
+
+        (0..height)
+            .map(|line| {
+                let parts: Vec<String> = self
+                    .columns
+                    .iter()
+                    .zip(&wrapped)
+                    .map(|(column, lines)| pad(lines.get(line).map(|l| l.as_str()).unwrap_or(""), column.width, column.align))
+                    .collect();
+                parts.join(" ").trim_end().to_string()
+            })
+            .collect()
+    }
+
+    // the table as plain text: title, headings, a dashed line, then the rows
+    pub fn render_text(&self, wrap: bool) -> String {
+        let mut text = String::new();
+        text.push('\n');
+        if !self.title.is_empty() {
+            text.push_str(&format!("{}:\n", self.title));
+        }
+
+        let headers: Vec<String> = self.columns.iter().map(|c| c.header.clone()).collect();
+        for line in self.render_cells(&headers, wrap) {
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        let dashes = "-".repeat(self.line_width());
+        text.push_str(&dashes);
+        text.push('\n');
+
+        for row in &self.rows {
+            match row {
+                Row::Cells(cells) => {
+                    for line in self.render_cells(cells, wrap) {
+                        text.push_str(&line);
+                        text.push('\n');
+                    }
+                }
+                Row::Separator => {
+                    text.push_str(&dashes);
+                    text.push('\n');
+                }
+            }
+        }
+
+        text
+    }
+
+    // the table as csv: a header row then the rows (dashed lines are left out)
+    pub fn render_csv(&self) -> String {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        let headers: Vec<&str> = self.columns.iter().map(|c| c.header.as_str()).collect();
+        let _ = writer.write_record(&headers);
+        for row in &self.rows {
+            if let Row::Cells(cells) = row {
+                let _ = writer.write_record(cells);
+            }
+        }
+        let bytes = writer.into_inner().unwrap_or_default();
+        String::from_utf8_lossy(&bytes).to_string()
+    }
+
+    // the table as a markdown table, numbers lined up on the right
+    pub fn render_markdown(&self) -> String {
+        let escape = |cell: &str| cell.replace('|', "\\|");
+
+        let mut text = String::new();
+        if !self.title.is_empty() {
+            text.push_str(&format!("### {}\n\n", self.title));
+        }
+
+        let headers: Vec<String> = self.columns.iter().map(|c| escape(&c.header)).collect();
+        text.push_str(&format!("| {} |\n", headers.join(" | ")));
+
+        let rules: Vec<&str> = self
+            .columns
+            .iter()
+            .map(|c| match c.align {
+                Align::Left => "---",
+                Align::Right => "---:",
+            })
+            .collect();
+        text.push_str(&format!("|{}|\n", rules.join("|")));
+
+        for row in &self.rows {
+            if let Row::Cells(cells) = row {
+                let cells: Vec<String> = (0..self.columns.len())
+                    .map(|i| escape(cells.get(i).map(|c| c.as_str()).unwrap_or("")))
+                    .collect();
+                text.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+        }
+
+        text
+    }
+
+    // the table as an html table, with the title above it
+    pub fn render_html(&self) -> String {
+        let mut html = String::new();
+        if !self.title.is_empty() {
+            html.push_str(&format!("<h3>{}</h3>\n", escape_html(&self.title)));
+        }
+
+        let class = |align: Align| match align {
+            Align::Left => "",
+            Align::Right => " class=\"num\"",
+        };
+
+        html.push_str("<table>\n<thead><tr>");
+        for column in &self.columns {
+            html.push_str(&format!("<th{}>{}</th>", class(column.align), escape_html(&column.header)));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+
+        // rows under a dashed line (totals) go in the footer, so sorting the table leaves them at the bottom
+        let mut in_footer = false;
+        for row in &self.rows {
+            match row {
+                Row::Cells(cells) => {
+                    html.push_str("<tr>");
+                    for (i, column) in self.columns.iter().enumerate() {
+                        let cell = cells.get(i).map(|c| c.as_str()).unwrap_or("");
+                        html.push_str(&format!("<td{}>{}</td>", class(column.align), escape_html(cell)));
+                    }
+                    html.push_str("</tr>\n");
+                }
+                Row::Separator if !in_footer => {
+                    in_footer = true;
+                    html.push_str("</tbody>\n<tfoot>\n");
+                }
+                Row::Separator => html.push_str("<tr class=\"separator\"></tr>\n"),
+            }
+        }
+
+        html.push_str(if in_footer { "</tfoot>\n</table>\n" } else { "</tbody>\n</table>\n" });
+        html
+    }
+
+    // the table as a json object; rows are keyed by column heading, and numbers come out as numbers
+    pub fn to_json(&self) -> Value {
+        let rows: Vec<Value> = self
+            .rows
+            .iter()
+            .filter_map(|row| match row {
+                Row::Cells(cells) => Some(cells),
+                Row::Separator => None,
+            })
+            .map(|cells| {
+                let mut object = Map::new();
+                for (i, column) in self.columns.iter().enumerate() {
+                    let cell = cells.get(i).map(|c| c.as_str()).unwrap_or("");
+                    object.insert(column.header.clone(), json_cell(cell));
+                }
+                Value::Object(object)
+            })
+            .collect();
+
+        let columns: Vec<&str> = self.columns.iter().map(|c| c.header.as_str()).collect();
+        json!({ "title": self.title, "columns": columns, "rows": rows })
+    }
+
+    // the table in any format
+    pub fn render(&self, format: Format, wrap: bool) -> String {
+        match format {
+            Format::Text => self.render_text(wrap),
+            Format::Csv => self.render_csv(),
+            Format::Markdown => self.render_markdown(),
+            Format::Html => self.render_html(),
+            // a workbook isn't text; Output writes those itself, so this only happens if one is sent to a stream
+            Format::Xlsx => self.render_csv(),
+            Format::Json => format!("{}\n", serde_json::to_string_pretty(&self.to_json()).unwrap_or_default()),
+        }
+    }
+}
+
+// make text safe to put inside html
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// the start and end of a standalone html page; the styles are inline so the file works on its own
+fn html_page_start(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n",
+        escape_html(title),
+        HTML_STYLE
+    )
+}
+
+const HTML_PAGE_END: &str = concat!("<script>\n", include_str!("sort_tables.js"), "</script>\n</body>\n</html>\n");
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1.5em; }
+th, td { padding: 0.2em 0.6em; border-bottom: 1px solid #ddd; text-align: left; }
+th { background: #f2f2f2; }
+.num { text-align: right; }
+tr.separator { border-top: 2px solid #888; }
+tfoot tr:first-child td { border-top: 2px solid #888; font-weight: bold; }
+th { cursor: pointer; user-select: none; }
+th.sorted-asc::after { content: ' \\25B2'; }
+th.sorted-desc::after { content: ' \\25BC'; }
+svg.chart { display: block; margin-bottom: 1.5em; }
+svg.chart rect { fill: #4a78b5; }
+svg.chart rect:hover { fill: #2c5282; }
+.chart-title { font-weight: bold; font-size: 14px; }
+.chart-value { font-size: 9px; text-anchor: middle; fill: #444; }
+.chart-label { font-size: 10px; text-anchor: end; }
+.chart-band { font-size: 11px; }";
+
+// cells are already formatted for reading, so turn the numbers back into numbers
+// ("--" is how a missing value is shown, which is null in json)
+fn json_cell(cell: &str) -> Value {
+    if cell == "--" {
+        return Value::Null;
+    }
+    if let Ok(whole) = cell.parse::<i64>() {
+        return json!(whole);
+    }
+    if let Ok(number) = cell.parse::<f64>() {
+        return json!(number);
+    }
+    Value::String(cell.to_string())
+}
+
+// turn a table title into a file name, e.g. "Top 10 home runs in a season" -> "top-10-home-runs-in-a-season"
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-').to_string();
+    if slug.is_empty() { "report".to_string() } else { slug }
+}
+
+// an --out file, held in memory and only written when the report is finished, so a report that fails
+// part way leaves no file behind (and doesn't wipe out the one from the last run)
+struct PendingFile {
+    path: PathBuf,
+    buffer: Vec<u8>,
+}
+
+impl Write for PendingFile {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        fs::write(&self.path, &self.buffer)
+    }
+}
+
+// where the report ends up: one stream (stdout or an --out file), an excel workbook with a sheet per table,
+// or a file per table in a folder (queued up and written at the end, by up to `jobs` threads at once)
+enum Target {
+    Stream(Box<dyn Write>),
+    Workbook { path: PathBuf, workbook: Box<Workbook>, used_names: HashSet<String> },
+    Folder { dir: PathBuf, used_names: HashSet<String>, queue: Vec<(PathBuf, Table)>, jobs: usize },
+}
+
+// how many files --out-dir writes at once when --jobs doesn't say: one per core
+pub fn default_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// one table's file in a folder, rendered and written
+fn write_table_file(path: &Path, table: &Table, format: Format, wrap: bool) -> io::Result<()> {
+    match format {
+        // a workbook of its own, with the one sheet
+        Format::Xlsx => {
+            let mut workbook = Workbook::new();
+            xlsx::add_table_sheet(&mut workbook, table, &mut HashSet::new())
+                .and_then(|_| workbook.save(path))
+                .map_err(io::Error::other)
+        }
+        // an html table on its own still needs to be a whole page
+        Format::Html => fs::write(path, format!("{}{}{}", html_page_start(&table.title), table.render_html(), HTML_PAGE_END)),
+        _ => fs::write(path, table.render(format, wrap)),
+    }
+}
+
+// write the queued files with a pool of at most `jobs` threads taking the next one off a shared queue,
+// handing back every file's result in the order they were queued
+fn write_table_files(queue: Vec<(PathBuf, Table)>, format: Format, wrap: bool, jobs: usize) -> Vec<(PathBuf, io::Result<()>)> {
+    let count = queue.len();
+    let work = Mutex::new(queue.into_iter().enumerate());
+    let results = Mutex::new(Vec::with_capacity(count));
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, count.max(1)) {
+            scope.spawn(|| {
+                loop {
+                    // the lock is only held to take the next file, not while it's written
+                    let next = work.lock().unwrap_or_else(|e| e.into_inner()).next();
+                    let Some((number, (path, table))) = next else {
+                        break;
+                    };
+                    let written = write_table_file(&path, &table, format, wrap);
+                    results.lock().unwrap_or_else(|e| e.into_inner()).push((number, path, written));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    results.sort_by_key(|&(number, _, _)| number);
+    results.into_iter().map(|(_, path, written)| (path, written)).collect()
+}
+
+// where reports go; every command prints through one of these
+pub struct Output {
+    pub wrap: bool,  // wrap long cells onto extra lines instead of cutting them short
+    pub format: Format,
+    target: Target,
+    started: bool,  // something has already gone into the stream
+    json_notes: Vec<String>,  // json can't be written bit by bit, so it's collected up and written at the end
+    json_tables: Vec<Value>,
+    error: Option<io::Error>,  // the first write that failed, handed back by finish()
+    failed_files: Vec<(PathBuf, io::Error)>,  // files in a folder that couldn't be written; the rest still are
+}
+
+impl Output {
+    // write to any stream
+    pub fn new(writer: Box<dyn Write>, format: Format, wrap: bool) -> Output {
+        Output {
+            wrap,
+            format,
+            target: Target::Stream(writer),
+            started: false,
+            json_notes: Vec::new(),
+            json_tables: Vec::new(),
+            error: None,
+            failed_files: Vec::new(),
+        }
+    }
+
+    pub fn stdout(format: Format, wrap: bool) -> Output {
+        Output::new(Box::new(io::stdout()), format, wrap)
+    }
+
+    // write everything to one file (once the report is finished)
+    pub fn to_file(path: &str, format: Format, wrap: bool) -> io::Result<Output> {
+        if format == Format::Xlsx {
+            let mut out = Output::stdout(format, wrap);
+            out.target = Target::Workbook { path: PathBuf::from(path), workbook: Box::new(Workbook::new()), used_names: HashSet::new() };
+            return Ok(out);
+        }
+
+        Ok(Output::new(Box::new(PendingFile { path: PathBuf::from(path), buffer: Vec::new() }), format, wrap))
+    }
+
+    // write every table to its own file in a folder, named after its title, up to `jobs` at a time
+    // (the files are written when the report is finished, so a report that fails leaves nothing behind)
+    pub fn to_dir(dir: &str, format: Format, wrap: bool, jobs: usize) -> io::Result<Output> {
+        let mut out = Output::stdout(format, wrap);
+        out.target = Target::Folder { dir: PathBuf::from(dir), used_names: HashSet::new(), queue: Vec::new(), jobs };
+        Ok(out)
+    }
+
+    // hold on to the first error rather than stopping every report to check for it
+    // (a closed pipe, e.g. from `| head`, just means nobody wants the rest)
+    fn record(&mut self, result: io::Result<()>) {
+        if let Err(e) = result
+            && e.kind() != io::ErrorKind::BrokenPipe
+        {
+            self.error.get_or_insert(e);
+        }
+    }
+
+    // a plain line of text
+    pub fn line(&mut self, text: &str) {
+        self.start_page();
+        let result = match (&mut self.target, self.format) {
+            // messages next to files in a folder (or sheets in a workbook) go to the screen
+            (Target::Folder { .. } | Target::Workbook { .. }, _) => writeln!(io::stdout(), "{}", text),
+            (Target::Stream(_), Format::Json) => {
+                if !text.trim().is_empty() {
+                    self.json_notes.push(text.to_string());
+                }
+                Ok(())
+            }
+            // anything but rows would break a csv file, so messages go to stderr
+            (Target::Stream(_), Format::Csv) => writeln!(io::stderr(), "{}", text),
+            // two trailing spaces keep the lines apart in markdown
+            (Target::Stream(writer), Format::Markdown) if !text.is_empty() => {
+                self.started = true;
+                writeln!(writer, "{}  ", text)
+            }
+            // underlines are for the terminal; html has proper headings
+            (Target::Stream(writer), Format::Html) => {
+                if text.trim().is_empty() || text.chars().all(|c| c == '=' || c == '-') {
+                    Ok(())
+                } else {
+                    writeln!(writer, "<p>{}</p>", escape_html(text))
+                }
+            }
+            (Target::Stream(writer), _) => {
+                self.started = true;
+                writeln!(writer, "{}", text)
+            }
+        };
+        self.record(result);
+    }
+
+    // a section heading: level 1 for a document title, level 2 for the sections under it
+    pub fn heading(&mut self, level: u8, text: &str) {
+        self.start_page();
+        let underline = if level <= 1 { "=" } else { "-" };
+        let result = match (&mut self.target, self.format) {
+            (Target::Stream(writer), Format::Text) => {
+                self.started = true;
+                write!(writer, "\n{}\n{}\n", text, underline.repeat(display_width(text)))
+            }
+            (Target::Stream(writer), Format::Markdown) => {
+                let gap = if self.started { "\n" } else { "" };
+                self.started = true;
+                writeln!(writer, "{}{} {}", gap, "#".repeat(level as usize), text)
+            }
+            (Target::Stream(writer), Format::Html) => writeln!(writer, "<h{0}>{1}</h{0}>", level.clamp(1, 2), escape_html(text)),
+            _ => {
+                self.line(text);
+                Ok(())
+            }
+        };
+        self.record(result);
+    }
+
+    // html streams need the top of the page before anything else goes in
+    fn start_page(&mut self) {
+        if self.format != Format::Html || self.started {
+            return;
+        }
+        if let Target::Stream(writer) = &mut self.target {
+            self.started = true;
+            let result = write!(writer, "{}", html_page_start("Baseball statistics"));
+            self.record(result);
+        }
+    }
+
+    // a chart only means something on a web page, so everything else skips it
+    pub fn chart(&mut self, chart: &dyn Chart) {
+        if self.format != Format::Html {
+            return;
+        }
+        self.start_page();
+        if let Target::Stream(writer) = &mut self.target {
+            let result = write!(writer, "{}", chart.render_svg());
+            self.record(result);
+        }
+    }
+
+    pub fn table(&mut self, table: &Table) {
+        self.start_page();
+        let format = self.format;
+        let started = std::mem::replace(&mut self.started, true);
+
+        let result = match &mut self.target {
+            Target::Stream(_) if format == Format::Json => {
+                self.json_tables.push(table.to_json());
+                Ok(())
+            }
+            Target::Stream(writer) => {
+                // csv and markdown tables need a blank line before them (text tables bring their own)
+                let gap = if started && format != Format::Text { "\n" } else { "" };
+                write!(writer, "{}{}", gap, table.render(format, self.wrap))
+            }
+            Target::Workbook { workbook, used_names, .. } => xlsx::add_table_sheet(workbook, table, used_names).map_err(io::Error::other),
+            Target::Folder { dir, used_names, queue, .. } => {
+                // two tables with the same title (e.g. two players' pages) get numbered
+                let slug = slugify(&table.title);
+                let mut name = slug.clone();
+                let mut n = 2;
+                while !used_names.insert(name.clone()) {
+                    name = format!("{}-{}", slug, n);
+                    n += 1;
+                }
+                queue.push((dir.join(format!("{}.{}", name, format.extension())), table.clone()));
+                Ok(())
+            }
+        };
+        self.record(result);
+    }
+
+    // write out anything held back and flush, reporting the first thing that went wrong
+    pub fn finish(mut self) -> io::Result<()> {
+        let mut results = Vec::new();
+
+        if let Target::Stream(writer) = &mut self.target {
+            if self.format == Format::Json {
+                let document = json!({ "notes": self.json_notes, "tables": self.json_tables });
+                results.push(
+                    serde_json::to_writer_pretty(&mut *writer, &document)
+                        .map_err(io::Error::other)
+                        .and_then(|_| writeln!(writer)),
+                );
+            }
+            if self.format == Format::Html && self.started {
+                results.push(write!(writer, "{}", HTML_PAGE_END));
+            }
+            results.push(writer.flush());
+        }
+
+        if let Target::Workbook { path, workbook, .. } = &mut self.target {
+            results.push(workbook.save(path.as_path()).map_err(io::Error::other));
+        }
+
+        if let Target::Folder { dir, queue, jobs, .. } = &mut self.target
+            && !queue.is_empty()
+        {
+            match fs::create_dir_all(dir) {
+                Ok(()) => {
+                    // one file that can't be written shouldn't stop the others, so they're all listed at the end
+                    for (path, written) in write_table_files(std::mem::take(queue), self.format, self.wrap, *jobs) {
+                        match written {
+                            Ok(()) => results.push(writeln!(io::stdout(), "Wrote {}", path.display())),
+                            Err(e) => self.failed_files.push((path, e)),
+                        }
+                    }
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+
+        if let Target::Folder { used_names, .. } = &self.target
+            && !self.failed_files.is_empty()
+        {
+            for (path, e) in &self.failed_files {
+                error!("couldn't write {}: {}", path.display(), e);
+            }
+            results.push(Err(io::Error::other(format!("{} of {} files couldn't be written", self.failed_files.len(), used_names.len()))));
+        }
+
+        for result in results {
+            self.record(result);
+        }
+
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_cuts_to_the_width_with_dots() {
+        assert_eq!(truncate("Ken Griffey", 11), "Ken Griffey");
+        assert_eq!(truncate("Ken Griffey Jr.", 10), "Ken Gri...");
+        // too narrow for the dots
+        assert_eq!(truncate("Griffey", 3), "Gri");
+    }
+
+    #[test]
+    fn truncate_counts_characters_not_bytes() {
+        // "á" is two bytes but one column
+        assert_eq!(truncate("Beltrán", 7), "Beltrán");
+        assert_eq!(truncate("Carlos Beltrán", 13), "Carlos Bel...");
+        assert_eq!(truncate("Beltrán", 6), "Bel...");
+        // a combining accent stays with its letter
+        assert_eq!(truncate("Belta\u{301}n", 5), "Be...");
+        assert_eq!(truncate("Beltra\u{301}n", 7), "Beltra\u{301}n");
+        // wide characters take two columns, and one that doesn't fit whole is left out
+        assert_eq!(truncate("大谷翔平", 8), "大谷翔平");
+        assert_eq!(truncate("大谷翔平", 6), "大...");
+    }
+
+    #[test]
+    fn wrap_breaks_at_spaces() {
+        assert_eq!(wrap_text("Carlos Beltrán", 8), vec!["Carlos", "Beltrán"]);
+        assert_eq!(wrap_text("Carlos Beltrán", 14), vec!["Carlos Beltrán"]);
+        assert_eq!(wrap_text("Ivan Rodríguez Torres", 10), vec!["Ivan", "Rodríguez", "Torres"]);
+        assert_eq!(wrap_text("", 5), vec![""]);
+    }
+
+    #[test]
+    fn wrap_chops_a_word_too_long_for_a_line() {
+        assert_eq!(wrap_text("Yastrzemski", 4), vec!["Yast", "rzem", "ski"]);
+        assert_eq!(wrap_text("Beltrán", 3), vec!["Bel", "trá", "n"]);
+    }
+
+    #[test]
+    fn columns_truncate_or_wrap() {
+        let mut table = Table::new("").column("Player", 8).right("HR", 3);
+        table.row(vec!["Carlos Beltrán".to_string(), "41".to_string()]);
+        assert_eq!(table.render_text(false), "\nPlayer    HR\n------------\nCarlo...  41\n");
+        assert_eq!(table.render_text(true), "\nPlayer    HR\n------------\nCarlos    41\nBeltrán\n");
+    }
+}
@@ -0,0 +1,136 @@
+// team seasons: every player row for a team in a year added up, and handed back to each row so a
+// season can be measured against its own team (what share of the team's home runs he hit)
+// the team is the code as played, so a player traded mid-season is measured against each team apart
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::leaders::SEASON_QUALIFYING_AT_BATS;
+use crate::options::Options;
+use crate::ranking::{rank_by, SortOrder};
+use crate::schedule::prorated_note;
+use crate::stats::{format_stat_value, normalize_stat_name};
+use crate::table::{Output, Table};
+
+// a team's season totals, for the share stats
+#[derive(Debug, Clone, Default)]
+pub struct TeamTotals {
+    pub homeruns: u32,
+    pub runs: u32,
+    pub hits: u32,
+}
+
+// one pass over the rows to add up each team season, then one more to give every row its team's totals
+// (the loader does this, so every set of rows it hands back has them)
+pub fn assign_team_totals(records: &mut [CleanPlayerSeason]) {
+    let mut totals: HashMap<(Arc<str>, u32), TeamTotals> = HashMap::new();
+    for season in records.iter() {
+        let team = totals.entry((season.team.clone(), season.season)).or_default();
+        team.homeruns += season.homeruns;
+        team.runs += season.runs;
+        team.hits += season.hits;
+    }
+
+    for season in records.iter_mut() {
+        if let Some(team) = totals.get(&(season.team.clone(), season.season)) {
+            season.team_totals = team.clone();
+        }
+    }
+}
+
+// a player's share of his team's total (None when the team had none)
+pub fn team_share(count: u32, team_total: u32) -> Option<f64> {
+    (team_total > 0).then(|| count as f64 / team_total as f64)
+}
+
+// the counts a share stat comes from: (the player's, the team's)
+fn share_parts(stat: &str, season: &CleanPlayerSeason) -> Option<(u32, u32)> {
+    match stat {
+        "homerun_share" => Some((season.homeruns, season.team_totals.homeruns)),
+        "run_share" => Some((season.runs, season.team_totals.runs)),
+        "hit_share" => Some((season.hits, season.team_totals.hits)),
+        _ => None,
+    }
+}
+
+// the share stat for a counting stat, or the share stat itself ("hr" and "hr_share" both give homerun_share)
+fn share_stat(name: &str) -> Option<&'static str> {
+    match normalize_stat_name(name)? {
+        "homeruns" | "homerun_share" => Some("homerun_share"),
+        "runs" | "run_share" => Some("run_share"),
+        "hits" | "hit_share" => Some("hit_share"),
+        _ => None,
+    }
+}
+
+// the one-man command: the seasons where one player carried the biggest share of his team's offense
+pub fn show_one_man(out: &mut Output, stat: &str, min_ab: Option<u32>, top: usize, clean_records: &[CleanPlayerSeason], options: &Options) -> Result<()> {
+    let share = share_stat(stat).ok_or_else(|| Error::Usage(format!("one-man works with homeruns, runs or hits, not \"{}\"", stat)))?;
+
+    let min_at_bats = min_ab.unwrap_or(SEASON_QUALIFYING_AT_BATS);
+    let season_min = |season: u32| if min_ab.is_some() { min_at_bats } else { options.prorated(min_at_bats, season) };
+    let ranked = rank_by(clean_records, top, SortOrder::Descending, options, |s| {
+        if s.at_bats < season_min(s.season) {
+            return None;
+        }
+        let (count, team_total) = share_parts(share, s)?;
+        team_share(count, team_total)
+    });
+
+    let (label, header) = match share {
+        "homerun_share" => ("home runs", "HR"),
+        "run_share" => ("runs", "R"),
+        _ => ("hits", "H"),
+    };
+    let team_header = format!("Team {}", header);
+
+    let prorated_qualifier = if min_ab.is_none() { prorated_note(options.prorate) } else { "" };
+    let mut table = Table::new(format!("Top {} one-man offenses: biggest share of the team's {} (min {} AB{})", top, label, min_at_bats, prorated_qualifier))
+        .column("Rank", 4)
+        .column("Player", 25)
+        .column("Team", 6)
+        .column("Season", 8)
+        .right(header, 4)
+        .right(&team_header, team_header.len())
+        .right("Share", 6);
+
+    for row in &ranked {
+        let season = row.item;
+        let (count, team_total) = share_parts(share, season).unwrap_or_default();
+        table.row(vec![
+            row.rank.to_string(),
+            format!("{} {}", season.first_name.as_deref().unwrap_or(""), season.last_name).trim().to_string(),
+            season.team.to_string(),
+            season.season.to_string(),
+            count.to_string(),
+            team_total.to_string(),
+            format_stat_value(share, row.value),
+        ]);
+    }
+
+    out.table(&table);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::seasons_from_csv;
+
+    #[test]
+    fn every_row_gets_its_team_seasons_totals() {
+        // two Giants and a Dodger in 1951
+        let records = seasons_from_csv("\
+1951,Bobby,Thomson,/player/123645,CF,NYG,148,518,89,152,27,8,32,101,73,57,2,4,.293,.385,.562,.947
+1951,Willie,Mays,/player/118495,CF,NYG,121,464,59,127,22,5,20,68,57,60,7,4,.274,.356,.472,.828
+1951,Ralph,Branca,/player/111098,P,BRO,42,70,3,10,1,0,1,4,2,24,0,0,.143,.167,.200,.367
+");
+        assert_eq!(records[0].team_totals.homeruns, 52);
+        assert_eq!(records[1].team_totals.hits, 279);
+        assert_eq!(records[2].team_totals.homeruns, 1);
+        assert_eq!(team_share(records[0].homeruns, records[0].team_totals.homeruns), Some(32.0 / 52.0));
+        assert_eq!(team_share(0, 0), None);
+    }
+}
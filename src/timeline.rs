@@ -0,0 +1,132 @@
+// a player's career as he played it: each stint with a team in order, and how it started, going by
+// where the team changes (a move in the middle of a season, between seasons, or after time away)
+// the data has no transactions, so a trade, a sale and free agency all look the same here
+
+use std::sync::Arc;
+
+use crate::chart::TimelineChart;
+use crate::data::CleanPlayerSeason;
+use crate::error::Result;
+use crate::lookup::{player_not_found, PlayerIndex};
+use crate::table::{Output, Table};
+
+// an unbroken run of seasons with one team (the team code as played, not the franchise)
+struct Stint {
+    team: Arc<str>,
+    from: u32,
+    to: u32,
+    seasons: u32,
+    games: u32,
+    start: String,  // how it began
+}
+
+// how a stint began, from where the one before it ended
+fn how_it_started(previous: Option<&Stint>, season: u32, returning: bool) -> String {
+    let Some(previous) = previous else {
+        return "debut".to_string();
+    };
+
+    let how = if season == previous.to {
+        "mid-season".to_string()
+    } else if season == previous.to + 1 {
+        "off-season".to_string()
+    } else if season == previous.to + 2 {
+        format!("after missing {}", previous.to + 1)
+    } else {
+        format!("after missing {}-{}", previous.to + 1, season - 1)
+    };
+
+    if returning { format!("{}, back again", how) } else { how }
+}
+
+// the stints, oldest first: a new one starts whenever the team changes or a season is missed
+// (a season split between teams keeps the order its rows came in, which is the order he played for them)
+fn stints(seasons: &[&CleanPlayerSeason]) -> Vec<Stint> {
+    let mut stints: Vec<Stint> = Vec::new();
+
+    for season in seasons {
+        if let Some(current) = stints.last_mut()
+            && current.team == season.team
+            && (season.season == current.to || season.season == current.to + 1)
+        {
+            if season.season != current.to {
+                current.seasons += 1;
+            }
+            current.to = season.season;
+            current.games += season.games_played;
+            continue;
+        }
+
+        // back with a team he'd left for another one (coming back to the same team after time away isn't)
+        let returning = stints.iter().any(|s| s.team == season.team) && stints.last().is_some_and(|s| s.team != season.team);
+        let start = how_it_started(stints.last(), season.season, returning);
+        stints.push(Stint {
+            team: season.team.clone(),
+            from: season.season,
+            to: season.season,
+            seasons: 1,
+            games: season.games_played,
+            start,
+        });
+    }
+
+    stints
+}
+
+// the stints drawn across the years of the career, one character a season (= where he was with that team)
+fn band(stint: &Stint, first: u32, last: u32) -> String {
+    (first..=last).map(|year| if (stint.from..=stint.to).contains(&year) { '=' } else { ' ' }).collect()
+}
+
+// the timeline command
+pub fn show_timeline(out: &mut Output, query: &str, players: &PlayerIndex) -> Result<()> {
+    let matches = players.find(query);
+    if matches.is_empty() {
+        return Err(player_not_found(query));
+    }
+
+    for player in matches {
+        let mut seasons: Vec<&CleanPlayerSeason> = players.seasons(&player.link).to_vec();
+        seasons.sort_by_key(|s| s.season);
+
+        let stints = stints(&seasons);
+        let (Some(first), Some(last)) = (stints.first().map(|s| s.from), stints.last().map(|s| s.to)) else {
+            continue;
+        };
+
+        let mut teams: Vec<&str> = stints.iter().map(|s| s.team.as_ref()).collect();
+        teams.sort();
+        teams.dedup();
+
+        let years = format!("{}-{}", first, last);
+        let band_width = ((last - first + 1) as usize).max(years.len());
+        let plural = |count: usize, word: &str| if count == 1 { format!("1 {}", word) } else { format!("{} {}s", count, word) };
+        let mut table = Table::new(format!("{} {}: {} with {}", player.first_name, player.last_name, plural(stints.len(), "stint"), plural(teams.len(), "team")))
+            .column("Team", 4)
+            .column("From", 6)
+            .column("To", 6)
+            .right("Seasons", 7)
+            .right("G", 5)
+            .column("How it started", 32)
+            .column(&years, band_width);
+
+        let mut chart = TimelineChart::new(format!("{} {}, team by team", player.first_name, player.last_name));
+
+        for stint in &stints {
+            table.row(vec![
+                stint.team.to_string(),
+                stint.from.to_string(),
+                stint.to.to_string(),
+                stint.seasons.to_string(),
+                stint.games.to_string(),
+                stint.start.clone(),
+                band(stint, first, last),
+            ]);
+            chart.band(stint.team.to_string(), stint.from, stint.to);
+        }
+
+        out.table(&table);
+        out.chart(&chart);
+    }
+    Ok(())
+}
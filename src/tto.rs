@@ -0,0 +1,140 @@
+// three true outcomes: how much of the game is now home runs, walks and strikeouts,
+// league-wide season by season and decade by decade, plus the players who lean on them most
+
+use std::collections::BTreeMap;
+
+use crate::careers::AggregatedPlayer;
+use crate::chart::BarChart;
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::leaders::{show_leaders, LeaderArgs, Scope};
+use crate::options::Options;
+use crate::stats::{format_stat_value, tto_rate};
+use crate::table::{Output, Table};
+
+const TREND_WIDTH: usize = 30;  // characters in the longest text bar
+
+// the league's totals for one season (or decade)
+#[derive(Default)]
+struct Outcomes {
+    at_bats: u32,
+    homeruns: u32,
+    walks: u32,
+    strikeouts: u32,
+}
+
+impl Outcomes {
+    fn add(&mut self, season: &CleanPlayerSeason, strikeouts: u32) {
+        self.at_bats += season.at_bats;
+        self.homeruns += season.homeruns;
+        self.walks += season.walks;
+        self.strikeouts += strikeouts;
+    }
+
+    fn rate(&self) -> Option<f64> {
+        tto_rate(self.homeruns, self.walks, self.strikeouts, self.at_bats)
+    }
+}
+
+// the tto command
+pub fn show_tto(out: &mut Output, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], top: usize, options: &Options) -> Result<()> {
+    // only rows with strikeouts recorded count, otherwise the early seasons would look tto-free
+    let mut by_season: BTreeMap<u32, Outcomes> = BTreeMap::new();
+    let mut by_decade: BTreeMap<u32, Outcomes> = BTreeMap::new();
+
+    for season in clean_records {
+        let Some(strikeouts) = season.strikeouts else {
+            continue;
+        };
+        by_season.entry(season.season).or_default().add(season, strikeouts);
+        by_decade.entry(season.season / 10 * 10).or_default().add(season, strikeouts);
+    }
+
+    if by_season.is_empty() {
+        return Err(Error::Data("no seasons have strikeouts recorded, so there are no three true outcomes to show".to_string()));
+    }
+
+    let highest = by_season.values().filter_map(|o| o.rate()).fold(0.0, f64::max);
+
+    let mut table = Table::new("Three true outcomes by season (league-wide, PA = AB + BB)")
+        .column("Season", 6)
+        .right("PA", 8)
+        .right("HR", 6)
+        .right("BB", 7)
+        .right("SO", 7)
+        .right("TTO", 6)
+        .column("", TREND_WIDTH);
+
+    let mut chart = BarChart::new("Three true outcomes, % of plate appearances");
+    chart.decimals = 1;
+
+    for (year, outcomes) in &by_season {
+        let Some(rate) = outcomes.rate() else {
+            continue;
+        };
+
+        // a bar of #s so the trend shows up in a terminal too
+        let bar = "#".repeat((rate / highest * TREND_WIDTH as f64).round() as usize);
+
+        table.row(vec![
+            year.to_string(),
+            (outcomes.at_bats + outcomes.walks).to_string(),
+            outcomes.homeruns.to_string(),
+            outcomes.walks.to_string(),
+            outcomes.strikeouts.to_string(),
+            format_stat_value("tto", rate),
+            bar,
+        ]);
+        chart.bar(year.to_string(), rate * 100.0);
+    }
+
+    out.table(&table);
+    out.chart(&chart);
+
+    // decades smooth out the year to year noise, and the change column shows the shift
+    let mut decades = Table::new("Three true outcomes by decade")
+        .column("Decade", 6)
+        .right("TTO", 6)
+        .right("Change", 7);
+
+    let mut previous: Option<f64> = None;
+    let mut first: Option<(u32, f64)> = None;
+    let mut last: Option<(u32, f64)> = None;
+
+    for (decade, outcomes) in &by_decade {
+        let Some(rate) = outcomes.rate() else {
+            continue;
+        };
+
+        let change = match previous {
+            Some(before) => format!("{:+.1}", (rate - before) * 100.0),
+            None => String::new(),
+        };
+        decades.row(vec![format!("{}s", decade), format_stat_value("tto", rate), change]);
+
+        previous = Some(rate);
+        first.get_or_insert((*decade, rate));
+        last = Some((*decade, rate));
+    }
+
+    out.table(&decades);
+
+    if let (Some((first_decade, first_rate)), Some((last_decade, last_rate))) = (first, last) {
+        out.line("");
+        out.line(&format!(
+            "From the {}s to the {}s the share of plate appearances ending in a home run, walk or strikeout went from {} to {} ({:+.1} points)",
+            first_decade,
+            last_decade,
+            format_stat_value("tto", first_rate),
+            format_stat_value("tto", last_rate),
+            (last_rate - first_rate) * 100.0
+        ));
+    }
+
+    // and the players most made of them, through the usual leaderboard (qualified players only)
+    for scope in [Scope::Season, Scope::Career] {
+        let args = LeaderArgs { stat: "tto".to_string(), scope, min_ab: None, min_attempts: None, ascending: false, position: None, adjusted: false };
+        show_leaders(out, &args, false, top, clean_records, aggregated_players, options)?;
+    }
+    Ok(())
+}
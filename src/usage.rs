@@ -0,0 +1,165 @@
+// a journal of how the tool gets used, kept on this machine only and only if asked for (--usage-log, or
+// BASEBALL_STATS_USAGE_LOG=1 to keep it on): one line per run with the command, how long it took and
+// how big the data was, so it's easy to see which reports are slow or run the most
+// nothing is ever sent anywhere; stats-usage reads it back and --clear deletes it
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::table::{Output, Table};
+
+pub const USAGE_ENV_VAR: &str = "BASEBALL_STATS_USAGE_LOG";
+
+// one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageEntry {
+    pub at: u64,         // when it finished, in seconds since 1970 (UTC)
+    pub command: String,
+    pub millis: u64,     // how long the whole run took, loading included
+    pub rows: usize,     // season rows loaded
+    pub players: usize,
+}
+
+impl UsageEntry {
+    pub fn new(command: &str, elapsed: Duration, rows: usize, players: usize) -> UsageEntry {
+        UsageEntry {
+            at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            command: command.to_string(),
+            millis: elapsed.as_millis() as u64,
+            rows,
+            players,
+        }
+    }
+}
+
+// is the journal on? (the flag for one run, the environment variable for every run)
+pub fn enabled(flag: bool) -> bool {
+    flag || std::env::var(USAGE_ENV_VAR).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+}
+
+// where it's kept: the user's cache folder (~/.cache on linux), or nowhere if there isn't one
+pub fn journal_path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("rust_baseball_stats").join("usage.jsonl"))
+}
+
+// add a run to the end of the journal
+pub fn record(entry: &UsageEntry) -> io::Result<()> {
+    let path = journal_path().ok_or_else(|| io::Error::other("no cache folder to keep the usage journal in"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(entry).map_err(io::Error::other)?)
+}
+
+// every run in the journal (none if there isn't one yet; lines that can't be read are skipped)
+pub fn load() -> io::Result<Vec<UsageEntry>> {
+    let Some(path) = journal_path().filter(|p| p.exists()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = Vec::new();
+    for line in BufReader::new(fs::File::open(path)?).lines() {
+        if let Ok(entry) = serde_json::from_str(&line?) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+// delete the journal, if there is one
+pub fn clear() -> io::Result<bool> {
+    match journal_path().filter(|p| p.exists()) {
+        Some(path) => fs::remove_file(path).map(|_| true),
+        None => Ok(false),
+    }
+}
+
+// seconds since 1970 as "2024-05-01 14:03 UTC" (days to a date with Howard Hinnant's civil calendar method)
+fn format_time(at: u64) -> String {
+    let days = (at / 86_400) as i64;
+    let minutes_into_day = at % 86_400 / 60;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, minutes_into_day / 60, minutes_into_day % 60)
+}
+
+fn format_millis(millis: u64) -> String {
+    format!("{:.2}s", millis as f64 / 1000.0)
+}
+
+// the stats-usage command: runs and times per command, then the most recent runs
+pub fn show_usage(out: &mut Output, entries: &[UsageEntry], recent: usize) {
+    let where_kept = journal_path().map(|p| p.display().to_string()).unwrap_or_else(|| "(no cache folder)".to_string());
+
+    if entries.is_empty() {
+        out.line(&format!("No usage recorded yet. Run with --usage-log (or set {}=1) to keep a journal in {}", USAGE_ENV_VAR, where_kept));
+        return;
+    }
+
+    let mut by_command: BTreeMap<&str, Vec<&UsageEntry>> = BTreeMap::new();
+    for entry in entries {
+        by_command.entry(entry.command.as_str()).or_default().push(entry);
+    }
+
+    // most used first
+    let mut commands: Vec<(&str, Vec<&UsageEntry>)> = by_command.into_iter().collect();
+    commands.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(b.0)));
+
+    let mut table = Table::new(format!("Usage by command ({} runs)", entries.len()))
+        .column("Command", 16)
+        .right("Runs", 5)
+        .right("Average", 8)
+        .right("Slowest", 8)
+        .right("Total", 9)
+        .column("Last run", 20);
+
+    for (command, runs) in &commands {
+        let total: u64 = runs.iter().map(|e| e.millis).sum();
+        table.row(vec![
+            command.to_string(),
+            runs.len().to_string(),
+            format_millis(total / runs.len() as u64),
+            format_millis(runs.iter().map(|e| e.millis).max().unwrap_or(0)),
+            format_millis(total),
+            format_time(runs.iter().map(|e| e.at).max().unwrap_or(0)),
+        ]);
+    }
+    out.table(&table);
+
+    let mut table = Table::new(format!("Last {} runs", recent.min(entries.len())))
+        .column("When", 20)
+        .column("Command", 16)
+        .right("Time", 8)
+        .right("Rows", 7)
+        .right("Players", 7);
+
+    for entry in entries.iter().rev().take(recent) {
+        table.row(vec![
+            format_time(entry.at),
+            entry.command.clone(),
+            format_millis(entry.millis),
+            entry.rows.to_string(),
+            entry.players.to_string(),
+        ]);
+    }
+    out.table(&table);
+
+    out.line("");
+    out.line(&format!("Kept in {} (only on this machine; stats-usage --clear deletes it)", where_kept));
+}
@@ -0,0 +1,332 @@
+// checking the leaders we compute against a file of known (authoritative) records
+// handy for spotting rows a scraper dropped, doubled up or got wrong
+//
+// the reference file is a csv with these columns:
+//   scope,stat,rank,first_name,last_name,season,value
+//   season,homeruns,1,Barry,Bonds,2001,73
+//   career,hits,1,Pete,Rose,,4256
+// season can be left blank for career records
+
+use csv::ReaderBuilder;
+use serde::Deserialize;
+
+use crate::careers::AggregatedPlayer;
+use crate::data::CleanPlayerSeason;
+use crate::error::{check_exists, Result};
+use crate::leaders::{CAREER_QUALIFYING_AT_BATS, SEASON_QUALIFYING_AT_BATS};
+use crate::options::Options;
+use crate::ranking::{rank_with_ties, SortOrder, TieBreak};
+use crate::stats::{career_value, has_career_value, higher_is_better, is_rate_stat, normalize_stat_name, season_value};
+use crate::table::{Output, Table};
+
+// rate stats in the source are rounded to 3 places, so allow for that when comparing
+const VALUE_TOLERANCE: f64 = 0.0005;
+
+// one row of the reference file
+#[derive(Debug, Deserialize)]
+struct ReferenceRecord {
+    scope: String,
+    stat: String,
+    rank: usize,
+    first_name: String,
+    last_name: String,
+    season: Option<u32>,
+    value: f64,
+}
+
+// one row of a leaderboard we worked out from the dataset
+struct Entry {
+    rank: usize,
+    first_name: String,
+    last_name: String,
+    season: Option<u32>,
+    value: f64,
+}
+
+// something that didn't line up
+struct Discrepancy {
+    scope: String,
+    stat: String,
+    rank: String,
+    player: String,
+    season: String,
+    reference: String,
+    dataset: String,
+    problem: String,
+}
+
+// show whole numbers as whole numbers and rates to 3 places
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value)
+    } else {
+        format!("{:.3}", value)
+    }
+}
+
+// the whole season leaderboard for a stat, ranked the same way as every other leaderboard: best first
+// and rates only over qualified seasons, the way the top command does it (with ties shared whatever
+// --ties says, since that's how the reference lists rank them)
+fn season_entries(stat: &str, clean_records: &[CleanPlayerSeason], options: &Options) -> Vec<Entry> {
+    let min_at_bats = if is_rate_stat(stat) { SEASON_QUALIFYING_AT_BATS } else { 0 };
+    let order = SortOrder::for_stat(higher_is_better(stat), false);
+    rank_with_ties(clean_records, usize::MAX, order, TieBreak::Shared, |s| {
+        if s.at_bats < options.prorated(min_at_bats, s.season) {
+            return None;
+        }
+        season_value(stat, s)
+    })
+        .into_iter()
+        .map(|row| Entry {
+            rank: row.rank,
+            first_name: row.item.first_name.clone().unwrap_or_default(),
+            last_name: row.item.last_name.clone(),
+            season: Some(row.item.season),
+            value: row.value,
+        })
+        .collect()
+}
+
+// the whole career leaderboard for a stat, with the same career qualifier for rates
+fn career_entries(stat: &str, aggregated_players: &[AggregatedPlayer]) -> Vec<Entry> {
+    let min_at_bats = if is_rate_stat(stat) { CAREER_QUALIFYING_AT_BATS } else { 0 };
+    let order = SortOrder::for_stat(higher_is_better(stat), false);
+    rank_with_ties(aggregated_players, usize::MAX, order, TieBreak::Shared, |p| {
+        if p.total_at_bats < min_at_bats {
+            return None;
+        }
+        career_value(stat, p)
+    })
+        .into_iter()
+        .map(|row| Entry {
+            rank: row.rank,
+            first_name: row.item.first_name.clone(),
+            last_name: row.item.last_name.clone(),
+            season: None,
+            value: row.value,
+        })
+        .collect()
+}
+
+// does a computed entry belong to the same player (and season) as a reference row?
+fn same_player(entry: &Entry, reference: &ReferenceRecord) -> bool {
+    let same_name = entry.first_name.eq_ignore_ascii_case(&reference.first_name)
+        && entry.last_name.eq_ignore_ascii_case(&reference.last_name);
+
+    match (entry.season, reference.season) {
+        (Some(a), Some(b)) => same_name && a == b,
+        _ => same_name,
+    }
+}
+
+// "hr" and "homeruns" are the same leaderboard, but keep stats we don't know as typed
+fn category_stat(stat: &str) -> String {
+    normalize_stat_name(stat).map(|s| s.to_string()).unwrap_or_else(|| stat.to_string())
+}
+
+// read the reference file
+fn load_reference(path: &str) -> Result<Vec<ReferenceRecord>> {
+    check_exists("reference file", path)?;
+    let mut reader = ReaderBuilder::new()
+        .has_headers(true)
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+
+    let mut records = Vec::new();
+    for result in reader.deserialize() {
+        let record: ReferenceRecord = result?;
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+// the verify command
+pub fn verify(out: &mut Output, reference_path: &str, clean_records: &[CleanPlayerSeason], aggregated_players: &[AggregatedPlayer], options: &Options) -> Result<()> {
+    let references = load_reference(reference_path)?;
+    out.line(&format!("Verifying against {} ({} reference records)", reference_path, references.len()));
+
+    // work through the reference one leaderboard (scope + stat) at a time
+    let mut categories: Vec<(String, String)> = Vec::new();
+    for reference in &references {
+        let category = (reference.scope.to_lowercase(), category_stat(&reference.stat));
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+
+    let mut discrepancies = Vec::new();
+    let mut matched = 0;
+    let mut value_mismatches = 0;
+    let mut rank_mismatches = 0;
+    let mut missing = 0;
+    let mut unlisted = 0;
+    let mut unsupported = 0;
+
+    for (scope, stat_text) in &categories {
+        let rows: Vec<&ReferenceRecord> = references
+            .iter()
+            .filter(|r| &r.scope.to_lowercase() == scope && &category_stat(&r.stat) == stat_text)
+            .collect();
+
+        // build the matching leaderboard from the dataset, if we know how
+        let stat = normalize_stat_name(stat_text);
+        let entries = match (scope.as_str(), stat) {
+            ("season", Some(stat)) => Some(season_entries(stat, clean_records, options)),
+            ("career", Some(stat)) if has_career_value(stat) => {
+                Some(career_entries(stat, aggregated_players))
+            }
+            _ => None,
+        };
+
+        let Some(entries) = entries else {
+            for row in &rows {
+                unsupported += 1;
+                discrepancies.push(Discrepancy {
+                    scope: scope.clone(),
+                    stat: stat_text.clone(),
+                    rank: row.rank.to_string(),
+                    player: format!("{} {}", row.first_name, row.last_name),
+                    season: row.season.map(|s| s.to_string()).unwrap_or_default(),
+                    reference: format_value(row.value),
+                    dataset: String::new(),
+                    problem: "can't compute this scope/stat".to_string(),
+                });
+            }
+            continue;
+        };
+
+        // check each reference row against the dataset
+        for row in &rows {
+            let closest = entries
+                .iter()
+                .filter(|e| same_player(e, row))
+                .min_by(|a, b| (a.value - row.value).abs().total_cmp(&(b.value - row.value).abs()));
+
+            let problem = match closest {
+                None => {
+                    missing += 1;
+                    Some((String::new(), "not in dataset".to_string()))
+                }
+                Some(entry) if (entry.value - row.value).abs() > VALUE_TOLERANCE => {
+                    value_mismatches += 1;
+                    Some((format_value(entry.value), "value differs".to_string()))
+                }
+                Some(entry) => {
+                    if entry.rank != row.rank {
+                        rank_mismatches += 1;
+                        Some((format_value(entry.value), format!("dataset ranks them #{}", entry.rank)))
+                    } else {
+                        matched += 1;
+                        None
+                    }
+                }
+            };
+
+            if let Some((dataset, problem)) = problem {
+                discrepancies.push(Discrepancy {
+                    scope: scope.clone(),
+                    stat: stat_text.clone(),
+                    rank: row.rank.to_string(),
+                    player: format!("{} {}", row.first_name, row.last_name),
+                    season: row.season.map(|s| s.to_string()).unwrap_or_default(),
+                    reference: format_value(row.value),
+                    dataset,
+                    problem,
+                });
+            }
+        }
+
+        // and anyone the dataset puts in the same range that the reference doesn't list
+        let deepest_rank = rows.iter().map(|r| r.rank).max().unwrap_or(0);
+        for entry in &entries {
+            if entry.rank > deepest_rank {
+                break;
+            }
+            if !rows.iter().any(|r| same_player(entry, r)) {
+                unlisted += 1;
+                discrepancies.push(Discrepancy {
+                    scope: scope.clone(),
+                    stat: stat_text.clone(),
+                    rank: entry.rank.to_string(),
+                    player: format!("{} {}", entry.first_name, entry.last_name),
+                    season: entry.season.map(|s| s.to_string()).unwrap_or_default(),
+                    reference: String::new(),
+                    dataset: format_value(entry.value),
+                    problem: "not in reference".to_string(),
+                });
+            }
+        }
+    }
+
+    // the report
+    if discrepancies.is_empty() {
+        out.line("");
+        out.line("No discrepancies found");
+    } else {
+        let mut table = Table::new("Discrepancies")
+            .column("Scope", 7)
+            .column("Stat", 20)
+            .column("Rank", 5)
+            .column("Player", 25)
+            .column("Season", 7)
+            .column("Reference", 10)
+            .column("Dataset", 10)
+            .column("Problem", 30);
+
+        for d in discrepancies {
+            table.row(vec![d.scope, d.stat, d.rank, d.player, d.season, d.reference, d.dataset, d.problem]);
+        }
+
+        out.table(&table);
+    }
+
+    out.line("");
+    out.line("Summary:");
+    out.line(&format!("  matched:           {}", matched));
+    out.line(&format!("  value differs:     {}", value_mismatches));
+    out.line(&format!("  rank differs:      {}", rank_mismatches));
+    out.line(&format!("  not in dataset:    {}", missing));
+    out.line(&format!("  not in reference:  {}", unlisted));
+    out.line(&format!("  unsupported:       {}", unsupported));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::careers::aggregate_players;
+    use crate::data::seasons_from_csv;
+    use crate::scratch::Scratch;
+    use crate::table::Format;
+
+    // a batting average list: Duffy's .440 leads, and the 3 for 3 cup of coffee doesn't qualify
+    const SEASONS: &str = "\
+1894,Hugh,Duffy,/player/113376,OF,BSN,125,539,160,237,51,16,18,145,66,15,48,--,.440,.502,.694,1.196
+1887,Tip,O'Neill,/player/119797,OF,STL,124,517,167,225,52,19,14,123,50,--,30,--,.435,.490,.691,1.180
+1894,Joe,Brief,/player/900001,P,BSN,1,3,1,3,0,0,0,0,0,0,--,--,1.000,1.000,1.000,2.000
+";
+
+    const REFERENCE: &str = "\
+scope,stat,rank,first_name,last_name,season,value
+season,batting_average,1,Hugh,Duffy,1894,.440
+season,batting_average,2,Tip,O'Neill,1887,.435
+";
+
+    #[test]
+    fn rate_stat_reference_uses_the_qualifier() {
+        let scratch = Scratch::new("verify");
+        let (reference, report) = (scratch.write("reference.csv", REFERENCE), scratch.path("report.txt"));
+
+        let clean_records = seasons_from_csv(SEASONS);
+        let aggregated_players = aggregate_players(&clean_records, &Options::default());
+        let mut out = Output::to_file(&report, Format::Text, false).unwrap();
+        verify(&mut out, &reference, &clean_records, &aggregated_players, &Options::default()).unwrap();
+        out.finish().unwrap();
+
+        let text = std::fs::read_to_string(&report).unwrap();
+        assert!(text.contains("No discrepancies found"), "{}", text);
+        assert!(text.contains("matched:           2"), "{}", text);
+    }
+}
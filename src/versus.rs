@@ -0,0 +1,127 @@
+// two teams' offense side by side for one season (rivalries, world series previews)
+
+use crate::careers::{AggregatedPlayer, CareerBuilder};
+use crate::data::CleanPlayerSeason;
+use crate::error::{Error, Result};
+use crate::options::Options;
+use crate::ranking::{rank_by, SortOrder};
+use crate::stats::{format_rate, format_stat_value, higher_is_better, normalize_stat_name, season_value, unknown_stat};
+use crate::table::{Output, Table};
+
+fn rate(numerator: u32, at_bats: u32) -> f64 {
+    if at_bats > 0 { numerator as f64 / at_bats as f64 } else { 0.0 }
+}
+
+// total bases, for slugging
+fn total_bases(line: &AggregatedPlayer) -> u32 {
+    let singles = line.total_hits - line.total_doubles - line.total_triples - line.total_homeruns;
+    singles + 2 * line.total_doubles + 3 * line.total_triples + 4 * line.total_homeruns
+}
+
+// the versus-teams command (its own options, then the data and the options it was loaded with)
+#[allow(clippy::too_many_arguments)]
+pub fn show_versus(out: &mut Output, team_a: &str, team_b: &str, season: u32, by: &str, top: usize, clean_records: &[CleanPlayerSeason], options: &Options) -> Result<()> {
+    let stat = normalize_stat_name(by).ok_or_else(|| unknown_stat(by))?;
+
+    let rows_for = |team: &str| -> Vec<&CleanPlayerSeason> {
+        clean_records
+            .iter()
+            .filter(|s| s.season == season && (s.team.eq_ignore_ascii_case(team) || s.franchise.eq_ignore_ascii_case(team)))
+            .collect()
+    };
+
+    let a_rows = rows_for(team_a);
+    let b_rows = rows_for(team_b);
+
+    for (team, rows) in [(team_a, &a_rows), (team_b, &b_rows)] {
+        if rows.is_empty() {
+            return Err(Error::NotFound { what: "team", path: format!("{} in {}", team.to_uppercase(), season) });
+        }
+    }
+
+    // use the codes the way the data writes them (MON, not WSH, for the 1994 Expos)
+    let a_name = a_rows[0].team.to_string();
+    let b_name = b_rows[0].team.to_string();
+    // a team's line is just its players' seasons added up, the same way a career is
+    let builder = CareerBuilder::new(clean_records, options);
+    let a = builder.career(&a_name, &a_rows);
+    let b = builder.career(&b_name, &b_rows);
+
+    // stat, each side's value, and whether more is better
+    let comparisons: Vec<(&str, f64, f64, bool)> = vec![
+        ("Players", a.seasons_played as f64, b.seasons_played as f64, true),
+        ("AB", a.total_at_bats as f64, b.total_at_bats as f64, true),
+        ("R", a.total_runs as f64, b.total_runs as f64, true),
+        ("H", a.total_hits as f64, b.total_hits as f64, true),
+        ("2B", a.total_doubles as f64, b.total_doubles as f64, true),
+        ("3B", a.total_triples as f64, b.total_triples as f64, true),
+        ("HR", a.total_homeruns as f64, b.total_homeruns as f64, true),
+        ("RBI", a.total_rbi as f64, b.total_rbi as f64, true),
+        ("BB", a.total_walks as f64, b.total_walks as f64, true),
+        ("SO", a.total_strikeouts as f64, b.total_strikeouts as f64, higher_is_better("strikeouts")),
+        ("SB", a.total_stolen_bases as f64, b.total_stolen_bases as f64, true),
+        ("CS", a.total_caught_stealing as f64, b.total_caught_stealing as f64, higher_is_better("caught_stealing")),
+        ("AVG", rate(a.total_hits, a.total_at_bats), rate(b.total_hits, b.total_at_bats), true),
+        ("SLG", rate(total_bases(&a), a.total_at_bats), rate(total_bases(&b), b.total_at_bats), true),
+    ];
+
+    let mut table = Table::new(format!("{} vs {}, {}", a_name, b_name, season))
+        .column("Stat", 8)
+        .right(&a_name, 8)
+        .right(&b_name, 8)
+        .column("Edge", 6);
+
+    for (label, a_value, b_value, more_is_better) in comparisons {
+        let show = |value: f64| {
+            if label == "AVG" || label == "SLG" { format_rate(value) } else { format!("{}", value.round()) }
+        };
+
+        // "Players" is just how many people batted, so nobody gets the edge for it
+        let edge = if label == "Players" || a_value == b_value {
+            String::new()
+        } else if (a_value > b_value) == more_is_better {
+            a_name.clone()
+        } else {
+            b_name.clone()
+        };
+
+        table.row(vec![label.to_string(), show(a_value), show(b_value), edge]);
+    }
+
+    out.table(&table);
+
+    // each side's best players, by whichever stat was asked for
+    for (name, rows) in [(&a_name, &a_rows), (&b_name, &b_rows)] {
+        let order = SortOrder::for_stat(higher_is_better(stat), false);
+        let ranked = rank_by(rows, top, order, options, |s| season_value(stat, s));
+
+        let mut table = Table::new(format!("{} top contributors by {}", name, stat))
+            .column("Rank", 4)
+            .column("Player", 25)
+            .column("Pos", 4)
+            .right("AB", 5)
+            .right("H", 4)
+            .right("HR", 4)
+            .right("RBI", 4)
+            .right("AVG", 5)
+            .right(stat, stat.len().max(8));
+
+        for row in &ranked {
+            let player = row.item;
+            table.row(vec![
+                row.rank.to_string(),
+                format!("{} {}", player.first_name.as_deref().unwrap_or(""), player.last_name).trim().to_string(),
+                player.position.to_string(),
+                player.at_bats.to_string(),
+                player.hits.to_string(),
+                player.homeruns.to_string(),
+                player.rbi.map(|v| v.to_string()).unwrap_or("--".to_string()),
+                format_rate(player.batting_average),
+                format_stat_value(stat, row.value),
+            ]);
+        }
+
+        out.table(&table);
+    }
+    Ok(())
+}
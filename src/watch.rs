@@ -0,0 +1,53 @@
+// --watch: after the command has run, wait for one of the files it read to change and run it again,
+// until ctrl-c, so a csv regenerated every night refreshes whatever's reading it (a report written with
+// --out, or the screen)
+// main keeps what it read from each file between runs, so only the file that changed is read again (the
+// careers and everything else built from the rows are still worked out again, since any row can move them)
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::error::Result;
+
+// a file being written sends a burst of events; it's done once they stop for this long
+const SETTLE: Duration = Duration::from_millis(500);
+
+// block until one of the files is written or replaced, and hand back the one that was
+pub fn wait_for_change(paths: &[String]) -> Result<String> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+
+    // the folders rather than the files, since a regenerated file is often a new one moved into place
+    let mut folders = HashSet::new();
+    let mut files: Vec<(PathBuf, &String)> = Vec::new();
+    for path in paths {
+        let Some(name) = Path::new(path).file_name() else {
+            continue;
+        };
+        let folder = match Path::new(path).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => fs::canonicalize(parent)?,
+            _ => fs::canonicalize(".")?,
+        };
+        if folders.insert(folder.clone()) {
+            watcher.watch(&folder, RecursiveMode::NonRecursive)?;
+        }
+        files.push((folder.join(name), path));
+    }
+
+    loop {
+        let event = receiver.recv().map_err(io::Error::other)??;
+        if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+            continue;
+        }
+        if let Some((_, path)) = files.iter().find(|(file, _)| event.paths.contains(file)) {
+            while receiver.recv_timeout(SETTLE).is_ok() {}
+            return Ok(path.to_string());
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// excel output: every table becomes its own worksheet, headings in bold with the header row frozen
+
+use std::collections::HashSet;
+
+use rust_xlsxwriter::{Format as CellFormat, Workbook, XlsxError};
+
+use crate::table::{Row, Table};
+
+// excel's limit on worksheet names
+const MAX_SHEET_NAME: usize = 31;
+
+// a worksheet name from a table title: no characters excel refuses, short enough, and not already taken
+fn sheet_name(title: &str, used_names: &mut HashSet<String>) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) { '-' } else { c })
+        .collect();
+    let cleaned = if cleaned.trim().is_empty() { "Sheet".to_string() } else { cleaned.trim().to_string() };
+
+    let mut n = 1;
+    loop {
+        let suffix = if n == 1 { String::new() } else { format!(" ({})", n) };
+        let room = MAX_SHEET_NAME - suffix.chars().count();
+        let name = format!("{}{}", cleaned.chars().take(room).collect::<String>().trim_end(), suffix);
+
+        // excel compares sheet names ignoring case
+        if used_names.insert(name.to_lowercase()) {
+            return name;
+        }
+        n += 1;
+    }
+}
+
+// add a table to the workbook as a new worksheet
+pub fn add_table_sheet(workbook: &mut Workbook, table: &Table, used_names: &mut HashSet<String>) -> Result<(), XlsxError> {
+    let name = sheet_name(&table.title, used_names);
+    let bold = CellFormat::new().set_bold();
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(&name)?;
+
+    for (col, column) in table.columns.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, &column.header, &bold)?;
+        sheet.set_column_width(col as u16, column.width.max(column.header.len()) as f64 + 1.0)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+
+    // the cells were formatted for reading, so numbers get turned back into numbers
+    // (rates like ".300" included), and "--" (missing) is left blank
+    let mut row_number: u32 = 1;
+    for row in &table.rows {
+        let Row::Cells(cells) = row else {
+            continue;
+        };
+
+        for (col, cell) in cells.iter().enumerate() {
+            let col = col as u16;
+            if cell == "--" || cell.is_empty() {
+                continue;
+            }
+            match cell.parse::<f64>() {
+                Ok(number) => sheet.write_number(row_number, col, number)?,
+                Err(_) => sheet.write_string(row_number, col, cell)?,
+            };
+        }
+        row_number += 1;
+    }
+
+    Ok(())
+}
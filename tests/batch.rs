@@ -0,0 +1,48 @@
+// the batch command run the way a script would run it: a report that fails shouldn't stop the rest,
+// but the batch has to end with that report's exit code and say which one it was
+
+mod common;
+
+use std::process::Command;
+
+use common::Scratch;
+
+const SEASONS: &str = "\
+season,first_name,last_name,link,position,team,games_played,at_bats,runs,hits,doubles,triples,homeruns,rbi,walks,strikeouts,stolen_bases,caught_stealing,batting_average,on_base_percentage,slugging_percentage,on_base_plus_slugging
+2001,Barry,Bonds,/player/111188,LF,SF,153,476,129,156,32,2,73,137,177,93,13,3,.328,.515,.863,1.379
+1998,Mark,McGwire,/player/118743,1B,STL,155,509,130,152,21,0,70,147,162,155,1,0,.299,.470,.752,1.222
+";
+
+const BATCH: &str = "\
+[[report]]
+title = \"season home runs\"
+command = \"top\"
+stat = \"homeruns\"
+out = \"hr.csv\"
+
+[[report]]
+title = \"a stat nobody has heard of\"
+command = \"top\"
+stat = \"bogus\"
+out = \"bogus.csv\"
+";
+
+#[test]
+fn a_failed_report_fails_the_batch() {
+    let scratch = Scratch::new("batch");
+    scratch.write("mlb_season_data.csv", SEASONS);
+    scratch.write("reports.toml", BATCH);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_baseball_stats"))
+        .current_dir(&scratch.dir)
+        .args(["batch", "reports.toml"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    // an unknown stat is a usage error, and the good report still ran
+    assert_eq!(output.status.code(), Some(2), "{}", stderr);
+    assert!(stderr.contains("1 of 2 reports failed: a stat nobody has heard of"), "{}", stderr);
+    assert!(scratch.dir.join("hr.csv").exists(), "the report that worked wasn't written");
+    assert!(!scratch.dir.join("bogus.csv").exists(), "the report that failed left a file behind");
+}
@@ -0,0 +1,38 @@
+// a folder of files for one test, under the temp folder, deleted when the test is done with it (even when
+// an assert fails first)
+//
+// the library's own tests use it as well (see the end of lib.rs)
+
+use std::fs;
+use std::path::PathBuf;
+
+pub struct Scratch {
+    pub dir: PathBuf,
+}
+
+impl Scratch {
+    // a folder for this test alone, since tests run side by side (in this process and in others)
+    pub fn new(name: &str) -> Scratch {
+        let dir = std::env::temp_dir().join(format!("baseball_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        Scratch { dir }
+    }
+
+    // where a file in it goes, as the string the loaders and writers take
+    pub fn path(&self, name: &str) -> String {
+        self.dir.join(name).to_string_lossy().into_owned()
+    }
+
+    // write a file into it, handing back its path
+    pub fn write(&self, name: &str, contents: &str) -> String {
+        let path = self.path(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}